@@ -0,0 +1,156 @@
+//! Procedural macros for `dioxus-primitives`.
+//!
+//! These are re-exported from the main crate; depend on `dioxus-primitives`
+//! rather than on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{DeriveInput, Ident, LitStr, parse_macro_input};
+
+/// Generate a provider component and consumer hooks for a context value.
+///
+/// For a struct `FooContext` (or `Foo`) this generates:
+///
+/// - `FooProvider { value, children }`, a component providing the value;
+/// - `use_foo_context(consumer_name) -> FooContext`, panicking with
+///   "`consumer_name` must be used within `FooProvider`" when no provider exists;
+/// - `try_use_foo_context(consumer_name) -> Result<FooContext, ContextError>`.
+///
+/// The struct must implement `Clone` and `PartialEq`.
+///
+/// Attributes:
+///
+/// - `#[context(root = "Foo")]` overrides the root component name used in the
+///   missing provider message;
+/// - `#[context(default)]` falls back to `Default::default()` instead of failing
+///   when no provider exists.
+#[proc_macro_derive(ContextValue, attributes(context))]
+pub fn derive_context_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_context_value(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_context_value(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`ContextValue` cannot be derived for generic types",
+        ));
+    }
+
+    let mut root_name = None;
+    let mut use_default = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("context"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("root") {
+                let lit: LitStr = meta.value()?.parse()?;
+                root_name = Some(lit.value());
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                use_default = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `context` attribute, expected `root` or `default`"))
+            }
+        })?;
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+
+    // `FooContext` 與 `Foo` 都會產生 `FooProvider` 與 `use_foo_context`
+    let base_name = ident.to_string();
+    let base_name = base_name
+        .strip_suffix("Context")
+        .filter(|name| !name.is_empty())
+        .unwrap_or(&base_name)
+        .to_string();
+
+    let provider = Ident::new(&format!("{}Provider", base_name), ident.span());
+    let provider_props = Ident::new(&format!("{}ProviderProps", base_name), ident.span());
+    let snake_name = to_snake_case(&base_name);
+    let use_hook = Ident::new(&format!("use_{}_context", snake_name), Span::call_site());
+    let try_use_hook = Ident::new(
+        &format!("try_use_{}_context", snake_name),
+        Span::call_site(),
+    );
+    let root_name = root_name.unwrap_or_else(|| provider.to_string());
+
+    let default_context = if use_default {
+        quote! { || ::core::option::Option::Some(<#ident as ::core::default::Default>::default()) }
+    } else {
+        quote! { || ::core::option::Option::None }
+    };
+
+    let context_mod = quote! { ::dioxus_primitives::primitives::context };
+
+    Ok(quote! {
+        #[derive(::dioxus::prelude::Props, ::core::clone::Clone, ::core::cmp::PartialEq)]
+        #vis struct #provider_props {
+            pub value: #ident,
+            pub children: ::dioxus::prelude::Element,
+        }
+
+        #[allow(non_snake_case)]
+        #vis fn #provider(props: #provider_props) -> ::dioxus::prelude::Element {
+            #context_mod::create_context::use_provide_context_value(props.value);
+
+            props.children
+        }
+
+        #vis fn #try_use_hook(
+            consumer_name: &str,
+        ) -> ::core::result::Result<#ident, #context_mod::error::ContextError> {
+            #context_mod::create_context::try_use_context_value(
+                consumer_name,
+                #root_name,
+                #default_context,
+            )
+        }
+
+        #vis fn #use_hook(consumer_name: &str) -> #ident {
+            #try_use_hook(consumer_name).unwrap_or_else(|err| panic!("{}", err))
+        }
+    })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (index, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            // 連續的大寫（例如 `HTMLMenu`）只在下一個字母是小寫時斷開
+            let prev_lower = name[..index]
+                .chars()
+                .next_back()
+                .is_some_and(|prev| prev.is_lowercase() || prev.is_ascii_digit());
+            let next_lower = name[index + ch.len_utf8()..]
+                .chars()
+                .next()
+                .is_some_and(|next| next.is_lowercase());
+            let prev_upper = name[..index]
+                .chars()
+                .next_back()
+                .is_some_and(|prev| prev.is_uppercase());
+
+            if index > 0 && (prev_lower || (prev_upper && next_lower)) {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+
+    snake
+}
@@ -1 +1,3 @@
-mod primitives;
+pub mod primitives;
+
+pub use dioxus_primitives_macro::ContextValue;
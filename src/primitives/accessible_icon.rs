@@ -0,0 +1,26 @@
+//! AccessibleIcon Primitive
+//!
+//! Makes an icon meaningful to assistive technology: the icon itself is
+//! hidden from screen readers, which read `label` instead.
+
+use super::visually_hidden::VisuallyHidden;
+use dioxus::prelude::*;
+
+/// Wraps icon `children` (e.g. an inline SVG) with an accessible `label`.
+///
+/// For icon-only buttons the label becomes the button's accessible name.
+#[component]
+pub fn AccessibleIcon(
+    /// The text read by screen readers in place of the icon.
+    label: String,
+    children: Element,
+) -> Element {
+    rsx! {
+        span {
+            aria_hidden: "true",
+            display: "contents",
+            {children}
+        }
+        VisuallyHidden { {label} }
+    }
+}
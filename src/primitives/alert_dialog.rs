@@ -0,0 +1,133 @@
+//! AlertDialog Primitive
+//!
+//! A [`Dialog`](super::dialog::Dialog) that interrupts the user to confirm an
+//! action, e.g. before deleting something. It uses `role="alertdialog"`, is
+//! not closed by pressing outside of it, and focuses its
+//! [`AlertDialogCancel`] button when it opens, so pressing Enter right away
+//! never confirms a destructive action.
+
+pub use super::dialog::{
+    DialogDescription as AlertDialogDescription, DialogOverlay as AlertDialogOverlay,
+    DialogPortal as AlertDialogPortal, DialogTitle as AlertDialogTitle,
+    DialogTrigger as AlertDialogTrigger,
+};
+
+use super::dialog::{Dialog, DialogClose, DialogContent};
+use super::dismissable_layer::DismissEvent;
+use super::focus_scope::AutoFocusEvent;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone, Copy)]
+struct AlertDialogContentContext {
+    cancel: Signal<Option<Rc<MountedData>>>,
+}
+
+/// The root of an alert dialog; takes the same props as
+/// [`Dialog`](super::dialog::Dialog).
+#[component]
+pub fn AlertDialog(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    children: Element,
+) -> Element {
+    rsx! {
+        Dialog { open, default_open, on_open_change, {children} }
+    }
+}
+
+/// The alert dialog window. Unlike [`DialogContent`], pressing outside of it
+/// does nothing, and focus moves to the [`AlertDialogCancel`] button when it
+/// opens.
+#[component]
+pub fn AlertDialogContent(
+    /// Keep the content mounted while closed, for animation libraries.
+    #[props(default)]
+    force_mount: bool,
+    /// Fired before focus moves to the cancel button on open. Prevent it to
+    /// focus another element yourself.
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired when focus returns to the trigger on close.
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on Escape; prevent it to keep the dialog open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context_provider(|| AlertDialogContentContext {
+        cancel: Signal::new(None),
+    });
+
+    let own_attributes = vec![Attribute::new("role", "alertdialog", None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        DialogContent {
+            force_mount,
+            on_open_auto_focus: move |event: AutoFocusEvent| {
+                if let Some(handler) = on_open_auto_focus {
+                    handler.call(event.clone());
+                }
+                if event.is_default_prevented() {
+                    return;
+                }
+                event.prevent_default();
+                // 取消按鈕可能比內容晚掛載，等到下一輪再聚焦
+                spawn(async move {
+                    let cancel = ctx.cancel.peek().clone();
+                    if let Some(cancel) = cancel {
+                        let _ = cancel.set_focus(true).await;
+                    }
+                });
+            },
+            on_close_auto_focus,
+            on_escape_key_down,
+            on_pointer_down_outside: move |event: DismissEvent| event.prevent_default(),
+            on_interact_outside: move |event: DismissEvent| event.prevent_default(),
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The button that closes the alert dialog without taking the action.
+/// Focused when the dialog opens.
+#[component]
+pub fn AlertDialogCancel(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<AlertDialogContentContext>();
+
+    use_drop(move || ctx.cancel.set(None));
+
+    let own_attributes = vec![dioxus_elements::events::onmounted(
+        move |event: MountedEvent| ctx.cancel.set(Some(event.data())),
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        DialogClose { as_child, ..attributes, {children} }
+    }
+}
+
+/// The button that confirms the action and closes the alert dialog. Handle
+/// the action in its `onclick`.
+#[component]
+pub fn AlertDialogAction(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        DialogClose { as_child, ..attributes, {children} }
+    }
+}
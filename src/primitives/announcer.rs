@@ -0,0 +1,160 @@
+//! Announcer Primitive
+//!
+//! Announces messages to screen readers through `aria-live` regions, for
+//! changes that do not move focus: a toast appearing, the number of results
+//! in a combobox, or the outcome of an async form submission.
+
+use super::context::create_context::use_synced_signal;
+use super::portal::Portal;
+use super::time::sleep;
+use super::visually_hidden::VISUALLY_HIDDEN_STYLE;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// How urgently a message is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Politeness {
+    /// Read once the screen reader is idle.
+    #[default]
+    Polite,
+    /// Interrupt what the screen reader is currently reading.
+    Assertive,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Announcement {
+    message: String,
+    // 用來判斷清除計時器是否仍屬於目前的訊息
+    sequence: u64,
+}
+
+#[derive(Clone, Copy)]
+struct AnnouncerContext {
+    scope: ScopeId,
+    polite: Signal<Option<Announcement>>,
+    assertive: Signal<Option<Announcement>>,
+    next_sequence: Signal<u64>,
+    clear_after: Signal<Duration>,
+}
+
+/// Renders the `aria-live` regions used by [`use_announce`] under
+/// `document.body`.
+///
+/// Place it once near the root of the app.
+#[component]
+pub fn Announcer(
+    /// How long a message stays in its region before it is cleared.
+    #[props(default = Duration::from_secs(5))]
+    clear_after: Duration,
+    children: Element,
+) -> Element {
+    let clear_after_signal = use_synced_signal(clear_after);
+    let ctx = use_context_provider(|| AnnouncerContext {
+        scope: current_scope_id().expect("Announcer must be rendered in a component"),
+        polite: Signal::new(None),
+        assertive: Signal::new(None),
+        next_sequence: Signal::new(0),
+        clear_after: clear_after_signal,
+    });
+
+    let polite = ctx
+        .polite
+        .read()
+        .as_ref()
+        .map(|announcement| announcement.message.clone());
+    let assertive = ctx
+        .assertive
+        .read()
+        .as_ref()
+        .map(|announcement| announcement.message.clone());
+
+    rsx! {
+        {children}
+        Portal {
+            div {
+                role: "status",
+                aria_live: "polite",
+                aria_atomic: "true",
+                style: VISUALLY_HIDDEN_STYLE,
+                {polite}
+            }
+            div {
+                role: "alert",
+                aria_live: "assertive",
+                aria_atomic: "true",
+                style: VISUALLY_HIDDEN_STYLE,
+                {assertive}
+            }
+        }
+    }
+}
+
+/// Announces messages through the nearest [`Announcer`].
+#[derive(Clone, Copy)]
+pub struct Announce {
+    ctx: Option<AnnouncerContext>,
+}
+
+impl Announce {
+    /// Announce `message`. Repeating the message that is currently announced
+    /// in the same region does nothing.
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) {
+        let Some(ctx) = self.ctx else {
+            return;
+        };
+        let message = message.into();
+        let mut region = match politeness {
+            Politeness::Polite => ctx.polite,
+            Politeness::Assertive => ctx.assertive,
+        };
+        if region
+            .peek()
+            .as_ref()
+            .is_some_and(|current| current.message == message)
+        {
+            return;
+        }
+
+        let mut next_sequence = ctx.next_sequence;
+        let sequence = *next_sequence.peek() + 1;
+        next_sequence.set(sequence);
+        region.set(Some(Announcement { message, sequence }));
+
+        // 在 Announcer 的 scope 中計時，發出訊息的元件卸載後仍會清除
+        let clear_after = *ctx.clear_after.peek();
+        ctx.scope.in_runtime(|| {
+            spawn(async move {
+                sleep(clear_after).await;
+                let expired = region
+                    .peek()
+                    .as_ref()
+                    .is_some_and(|current| current.sequence == sequence);
+                if expired {
+                    region.set(None);
+                }
+            });
+        });
+    }
+
+    /// Whether an [`Announcer`] is above the caller to announce messages.
+    pub fn is_available(&self) -> bool {
+        self.ctx.is_some()
+    }
+
+    pub fn polite(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Polite);
+    }
+
+    pub fn assertive(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Assertive);
+    }
+}
+
+/// A handle to announce messages through the nearest [`Announcer`].
+///
+/// Without an `Announcer` above the caller, announcements are ignored.
+pub fn use_announce() -> Announce {
+    Announce {
+        ctx: try_use_context::<AnnouncerContext>(),
+    }
+}
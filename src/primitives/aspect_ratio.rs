@@ -0,0 +1,40 @@
+//! AspectRatio Primitive
+//!
+//! Keeps its content at a fixed width to height ratio, e.g. for images, video
+//! embeds or maps. The outer element takes the available width and a bottom
+//! padding proportional to it; the content fills an absolutely positioned
+//! element inside.
+
+use super::slot::merge_attributes;
+use dioxus::prelude::*;
+
+/// Sizes its children to `ratio`, the width divided by the height.
+#[component]
+pub fn AspectRatio(
+    #[props(default = 1.0)] ratio: f64,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    // padding 的百分比以寬度為基準
+    let padding = format!("{}%", 100.0 / ratio);
+    let own_attributes = vec![Attribute::new(
+        "style",
+        "position: absolute; top: 0; right: 0; bottom: 0; left: 0;",
+        None,
+        false,
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div {
+            position: "relative",
+            width: "100%",
+            padding_bottom: padding,
+            "data-primitive-aspect-ratio-wrapper": "",
+            div {
+                ..attributes,
+                {children}
+            }
+        }
+    }
+}
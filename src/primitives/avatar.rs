@@ -0,0 +1,131 @@
+//! Avatar Primitive
+//!
+//! An image representing a user, with a fallback, e.g. their initials, shown
+//! while the image loads or when it fails to.
+
+use super::hooks::use_timer::use_timeout;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// The loading status of an [`AvatarImage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageLoadingStatus {
+    /// No image has been rendered.
+    #[default]
+    Idle,
+    Loading,
+    Loaded,
+    Error,
+}
+
+#[derive(Clone, Copy)]
+struct AvatarContext {
+    status: Signal<ImageLoadingStatus>,
+}
+
+/// The root of an avatar, holding an [`AvatarImage`] and an
+/// [`AvatarFallback`].
+#[component]
+pub fn Avatar(
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    use_context_provider(|| AvatarContext {
+        status: Signal::new(ImageLoadingStatus::Idle),
+    });
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The image, shown only once it has loaded.
+#[component]
+pub fn AvatarImage(
+    src: String,
+    /// Fired whenever the loading status changes, e.g. to show a skeleton
+    /// while loading.
+    on_loading_status_change: Option<EventHandler<ImageLoadingStatus>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<AvatarContext>();
+    let mut status = ctx.status;
+
+    // 換成新的圖片時重新載入
+    use_effect(use_reactive!(|(src,)| {
+        let _ = src;
+        status.set(ImageLoadingStatus::Loading);
+    }));
+    use_effect(move || {
+        let status = status();
+        if let Some(handler) = on_loading_status_change {
+            handler.call(status);
+        }
+    });
+    use_drop(move || status.set(ImageLoadingStatus::Idle));
+
+    let loaded = status() == ImageLoadingStatus::Loaded;
+    let own_attributes = vec![
+        Attribute::new("src", src, None, false),
+        // 載入完成前仍要渲染才會開始載入，只是先不顯示
+        Attribute::new("hidden", !loaded, None, false),
+        dioxus_elements::events::onload(move |_: ImageEvent| {
+            status.set(ImageLoadingStatus::Loaded)
+        }),
+        dioxus_elements::events::onerror(move |_: ImageEvent| {
+            status.set(ImageLoadingStatus::Error)
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        img { ..attributes }
+    }
+}
+
+/// Shown while the image is not loaded: before it loads, when it fails to,
+/// or when there is no [`AvatarImage`].
+#[component]
+pub fn AvatarFallback(
+    /// Wait this long before showing the fallback, so it does not flash
+    /// while a fast image loads.
+    delay: Option<Duration>,
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<AvatarContext>();
+    let mut can_render = use_signal(|| delay.is_none());
+    let mut timer = use_timeout(delay.unwrap_or_default(), move || can_render.set(true));
+    use_effect(move || {
+        if delay.is_some() {
+            timer.start();
+        }
+    });
+
+    if !can_render() || (ctx.status)() == ImageLoadingStatus::Loaded {
+        return rsx! {};
+    }
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Checkbox Primitive
+//!
+//! A `button` with `role="checkbox"`, which can be checked, unchecked or
+//! indeterminate. Space toggles it; Enter does not, as with native
+//! checkboxes. When given a `name`, a hidden native checkbox is rendered
+//! next to it so the value is submitted with its form.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+
+/// The state of a [`Checkbox`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckedState {
+    Checked,
+    #[default]
+    Unchecked,
+    /// Neither checked nor unchecked, e.g. a "select all" checkbox while only
+    /// some items are selected. Toggling it checks it.
+    Indeterminate,
+}
+
+impl CheckedState {
+    /// The value of the `data-state` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckedState::Checked => "checked",
+            CheckedState::Unchecked => "unchecked",
+            CheckedState::Indeterminate => "indeterminate",
+        }
+    }
+
+    pub(crate) fn aria_checked(&self) -> &'static str {
+        match self {
+            CheckedState::Checked => "true",
+            CheckedState::Unchecked => "false",
+            CheckedState::Indeterminate => "mixed",
+        }
+    }
+
+    pub(crate) fn toggled(self) -> Self {
+        match self {
+            CheckedState::Checked => CheckedState::Unchecked,
+            CheckedState::Unchecked | CheckedState::Indeterminate => CheckedState::Checked,
+        }
+    }
+}
+
+impl From<bool> for CheckedState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckedState::Checked
+        } else {
+            CheckedState::Unchecked
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct CheckboxContext {
+    checked: Memo<CheckedState>,
+    disabled: Signal<bool>,
+}
+
+/// A checkbox, owning its checked state.
+///
+/// Pass `checked` and `on_checked_change` to control the state, or
+/// `default_checked` to let the checkbox manage it.
+#[component]
+pub fn Checkbox(
+    checked: Option<CheckedState>,
+    #[props(default)] default_checked: CheckedState,
+    on_checked_change: Option<EventHandler<CheckedState>>,
+    #[props(default)] disabled: bool,
+    #[props(default)] required: bool,
+    /// The name under which the checkbox is submitted with its form.
+    name: Option<String>,
+    /// The value submitted while checked.
+    #[props(default = String::from("on"))]
+    value: String,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (checked, set_checked) =
+        use_controllable_state(checked, || default_checked, on_checked_change);
+    let disabled_signal = use_synced_signal(disabled);
+    use_context_provider(|| CheckboxContext {
+        checked,
+        disabled: disabled_signal,
+    });
+
+    let state = checked();
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("role", "checkbox", None, false),
+        Attribute::new("aria-checked", state.aria_checked(), None, false),
+        Attribute::new("aria-required", required, None, false),
+        Attribute::new("value", value.clone(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-state", state.as_str(), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            // 原生核取方塊不會因 Enter 切換
+            if event.key() == Key::Enter {
+                event.prevent_default();
+            }
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !disabled {
+                set_checked.call(state.toggled());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    let control = match as_child {
+        Some(as_child) => as_child.call(attributes),
+        None => rsx! {
+            button {
+                ..attributes,
+                {children}
+            }
+        },
+    };
+
+    rsx! {
+        {control}
+        if let Some(name) = name {
+            // 隱藏的原生核取方塊，讓表單送出與驗證照常運作
+            input {
+                r#type: "checkbox",
+                aria_hidden: "true",
+                tabindex: "-1",
+                name,
+                value,
+                checked: state == CheckedState::Checked,
+                required,
+                disabled,
+                style: "position: absolute; pointer-events: none; opacity: 0; margin: 0; width: 1px; height: 1px;",
+            }
+        }
+    }
+}
+
+/// Rendered while the checkbox is checked or indeterminate, e.g. for a check
+/// mark icon.
+#[component]
+pub fn CheckboxIndicator(
+    /// Keep the indicator mounted while unchecked, for animation libraries;
+    /// see [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CheckboxContext>();
+    let state = (ctx.checked)();
+
+    let own_attributes = vec![
+        Attribute::new("data-state", state.as_str(), None, false),
+        Attribute::new(
+            "data-disabled",
+            ctx.disabled.read().then_some(""),
+            None,
+            false,
+        ),
+        Attribute::new("pointer-events", "none", Some("style"), false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: state != CheckedState::Unchecked,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| {
+                let attributes = merge_attributes(presence, attributes.clone());
+                if let Some(as_child) = as_child {
+                    return as_child.call(attributes);
+                }
+                rsx! {
+                    span {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
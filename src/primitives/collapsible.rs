@@ -0,0 +1,187 @@
+//! Collapsible Primitive
+//!
+//! A panel shown and hidden by a button. The content exposes its size as
+//! `--primitive-collapsible-content-height` and
+//! `--primitive-collapsible-content-width`, measured with its overflow
+//! included, so opening and closing can be animated in CSS alone:
+//!
+//! ```css
+//! .content[data-state="open"] { animation: slide-down 200ms; }
+//! .content[data-state="closed"] { animation: slide-up 200ms; }
+//! @keyframes slide-down {
+//!     from { height: 0; }
+//!     to { height: var(--primitive-collapsible-content-height); }
+//! }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct CollapsibleContext {
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    disabled: Signal<bool>,
+    content_id: String,
+}
+
+fn state_str(open: bool) -> &'static str {
+    if open { "open" } else { "closed" }
+}
+
+/// The root of a collapsible, owning its open state.
+///
+/// Pass `open` and `on_open_change` to control the state, or
+/// `default_open` to let the collapsible manage it.
+#[component]
+pub fn Collapsible(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Prevent the trigger from toggling the content.
+    #[props(default)]
+    disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let disabled_signal = use_synced_signal(disabled);
+    let content_id = use_id();
+    use_context_provider(|| CollapsibleContext {
+        open,
+        set_open,
+        disabled: disabled_signal,
+        content_id,
+    });
+
+    let open = open();
+    let own_attributes = vec![
+        Attribute::new("data-state", state_str(open), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The button that toggles the content.
+#[component]
+pub fn CollapsibleTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CollapsibleContext>();
+    let open = (ctx.open)();
+    let set_open = ctx.set_open;
+    let disabled = (ctx.disabled)();
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", ctx.content_id.clone(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-state", state_str(open), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !disabled {
+                set_open.call(!open);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The collapsible panel, rendered while open and until its exit animation
+/// finishes.
+#[component]
+pub fn CollapsibleContent(
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`]. The content is `hidden` while closed.
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CollapsibleContext>();
+    let open = (ctx.open)();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let mut size = use_signal(|| None::<(f64, f64)>);
+
+    // 量測包含溢出部分的大小，動畫中限制了高度也不影響結果
+    use_effect(use_reactive!(|(open,)| {
+        let Some(node) = node() else {
+            return;
+        };
+        if !open {
+            return;
+        }
+        spawn(async move {
+            if let Ok(scroll) = node.get_scroll_size().await {
+                size.set(Some((scroll.width, scroll.height)));
+            }
+        });
+    }));
+
+    let mut style = String::new();
+    if let Some((width, height)) = size() {
+        style = format!(
+            "--primitive-collapsible-content-width: {width}px; \
+             --primitive-collapsible-content-height: {height}px;"
+        );
+    }
+    let own_attributes = vec![
+        Attribute::new("id", ctx.content_id.clone(), None, false),
+        Attribute::new("style", style, None, false),
+        Attribute::new("hidden", force_mount && !open, None, false),
+        Attribute::new("data-state", state_str(open), None, false),
+        Attribute::new(
+            "data-disabled",
+            ctx.disabled.read().then_some(""),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| node.set(Some(event.data()))),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,761 @@
+//! ColorPicker Primitive
+//!
+//! The parts of a color picker: a saturation/brightness area, hue and alpha
+//! sliders, swatches, a hex input and an eye dropper. Every part is dragged
+//! with a pointer or adjusted with the keyboard, but none draws a gradient;
+//! the root exposes the color as CSS variables for your styles:
+//!
+//! - `--primitive-color-picker-hue`: the hue, in degrees without a unit
+//! - `--primitive-color-picker-color`: the color, e.g. `#ff000080`
+//! - `--primitive-color-picker-opaque-color`: the color without its alpha
+//! - `--primitive-color-picker-hue-color`: the pure hue, for the area
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     ColorPicker { value: color(), on_value_change: move |next| color.set(next),
+//!         ColorPickerArea { ColorPickerThumb {} }
+//!         ColorPickerHueSlider { ColorPickerThumb {} }
+//!         ColorPickerAlphaSlider { ColorPickerThumb {} }
+//!         ColorPickerInput {}
+//!         ColorPickerEyeDropper { "Pick" }
+//!     }
+//! }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_rect::{Rect, use_rect};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+use std::fmt;
+use std::rc::Rc;
+
+/// A color in HSV, so the hue survives while the color is gray or black.
+///
+/// `hue` is in degrees from 0 to 360; `saturation`, `brightness` and
+/// `alpha` are from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub hue: f64,
+    pub saturation: f64,
+    pub brightness: f64,
+    pub alpha: f64,
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::hsv(0.0, 0.0, 0.0)
+    }
+}
+
+impl Color {
+    /// An opaque color from its hue, saturation and brightness.
+    pub fn hsv(hue: f64, saturation: f64, brightness: f64) -> Self {
+        Color {
+            hue: hue.rem_euclid(360.0),
+            saturation: saturation.clamp(0.0, 1.0),
+            brightness: brightness.clamp(0.0, 1.0),
+            alpha: 1.0,
+        }
+    }
+
+    /// An opaque color from its red, green and blue components.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        let (r, g, b) = (
+            red as f64 / 255.0,
+            green as f64 / 255.0,
+            blue as f64 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        Color::hsv(hue, saturation, max)
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Color {
+            alpha: alpha.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// The red, green and blue components, from 0 to 255.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let chroma = self.brightness * self.saturation;
+        let sector = self.hue / 60.0;
+        let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = self.brightness - chroma;
+        let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+        (channel(r), channel(g), channel(b))
+    }
+
+    /// Parse `#rgb`, `#rrggbb` or `#rrggbbaa`; the `#` is optional.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if !hex.is_ascii() {
+            return None;
+        }
+        let byte = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        match hex.len() {
+            3 => {
+                let digit = |index: usize| {
+                    u8::from_str_radix(&hex[index..index + 1], 16)
+                        .ok()
+                        .map(|d| d * 17)
+                };
+                Some(Color::rgb(digit(0)?, digit(1)?, digit(2)?))
+            }
+            6 => Some(Color::rgb(byte(0)?, byte(2)?, byte(4)?)),
+            8 => Some(Color::rgb(byte(0)?, byte(2)?, byte(4)?).with_alpha(byte(6)? as f64 / 255.0)),
+            _ => None,
+        }
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` if the color is not opaque.
+    pub fn to_hex(&self) -> String {
+        let (r, g, b) = self.to_rgb();
+        if self.alpha >= 1.0 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            let a = (self.alpha * 255.0).round() as u8;
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ColorPickerContext {
+    color: Memo<Color>,
+    set_color: Callback<Color>,
+    commit: Callback<Color>,
+    disabled: Signal<bool>,
+}
+
+impl ColorPickerContext {
+    fn change(&self, color: Color) {
+        if color != *self.color.peek() {
+            self.set_color.call(color);
+        }
+    }
+}
+
+/// Which part of the color a track and its thumb edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    /// Saturation horizontally and brightness vertically.
+    Area,
+    Hue,
+    Alpha,
+}
+
+impl Channel {
+    // 拇指的位置，以百分比表示
+    fn position(&self, color: Color) -> (f64, f64) {
+        match self {
+            Channel::Area => (color.saturation * 100.0, (1.0 - color.brightness) * 100.0),
+            Channel::Hue => (color.hue / 360.0 * 100.0, 50.0),
+            Channel::Alpha => (color.alpha * 100.0, 50.0),
+        }
+    }
+
+    // x 與 y 為軌道內 0 到 1 的比例
+    fn at(&self, color: Color, x: f64, y: f64) -> Color {
+        match self {
+            Channel::Area => Color {
+                saturation: x,
+                brightness: 1.0 - y,
+                ..color
+            },
+            // 360 度與 0 度相同，停在 359 以免拇指跳回起點
+            Channel::Hue => Color {
+                hue: (x * 360.0).min(359.0),
+                ..color
+            },
+            Channel::Alpha => color.with_alpha(x),
+        }
+    }
+
+    // 鍵盤調整：Area 的 dx 是飽和度、dy 是亮度，單位為百分比
+    fn nudge(&self, color: Color, dx: f64, dy: f64) -> Color {
+        match self {
+            Channel::Area => Color::hsv(
+                color.hue,
+                color.saturation + dx / 100.0,
+                color.brightness + dy / 100.0,
+            )
+            .with_alpha(color.alpha),
+            Channel::Hue => Color {
+                hue: (color.hue + dx + dy).clamp(0.0, 359.0),
+                ..color
+            },
+            Channel::Alpha => color.with_alpha(color.alpha + (dx + dy) / 100.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TrackContext {
+    channel: Channel,
+    thumb: Signal<Option<Rc<MountedData>>>,
+}
+
+/// The root of a color picker, owning the color.
+///
+/// Pass `value` and `on_value_change` to control the color, or
+/// `default_value` to let the picker manage it. `on_value_change` fires on
+/// every movement of a drag; `on_value_commit` fires once it ends, and for
+/// every keyboard, swatch, input or eye dropper change.
+#[component]
+pub fn ColorPicker(
+    value: Option<Color>,
+    #[props(default)] default_value: Color,
+    on_value_change: Option<EventHandler<Color>>,
+    on_value_commit: Option<EventHandler<Color>>,
+    #[props(default)] disabled: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (color, set_color) = use_controllable_state(value, || default_value, on_value_change);
+    let commit = use_callback(move |color: Color| {
+        if let Some(handler) = on_value_commit {
+            handler.call(color);
+        }
+    });
+    let disabled_signal = use_synced_signal(disabled);
+    use_context_provider(|| ColorPickerContext {
+        color,
+        set_color,
+        commit,
+        disabled: disabled_signal,
+    });
+
+    let current = color();
+    let style = format!(
+        "--primitive-color-picker-hue: {}; --primitive-color-picker-color: {}; \
+         --primitive-color-picker-opaque-color: {}; --primitive-color-picker-hue-color: {};",
+        current.hue,
+        current,
+        current.with_alpha(1.0),
+        Color::hsv(current.hue, 1.0, 1.0),
+    );
+    let own_attributes = vec![
+        Attribute::new("style", style, None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 軌道共用的拖曳處理：按下時跳到指標位置，拖曳中持續更新，放開時 commit
+fn track_attributes(
+    ctx: ColorPickerContext,
+    channel: Channel,
+    mut node: Signal<Option<Rc<MountedData>>>,
+    rect: ReadOnlySignal<Option<Rect>>,
+    mut dragging: Signal<Option<i32>>,
+    thumb: Signal<Option<Rc<MountedData>>>,
+) -> Vec<Attribute> {
+    let move_to = move |event: &PointerEvent| {
+        let Some(rect) = *rect.peek() else {
+            return;
+        };
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+        let point = event.client_coordinates();
+        let x = ((point.x - rect.x) / rect.width).clamp(0.0, 1.0);
+        let y = ((point.y - rect.y) / rect.height).clamp(0.0, 1.0);
+        ctx.change(channel.at(*ctx.color.peek(), x, y));
+    };
+    let mut end = move |event: PointerEvent| {
+        if *dragging.peek() != Some(event.pointer_id()) {
+            return;
+        }
+        dragging.set(None);
+        ctx.commit.call(*ctx.color.peek());
+    };
+
+    vec![
+        Attribute::new("data-dragging", dragging().map(|_| ""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            if event.pointer_type() == "mouse"
+                && event.trigger_button() != Some(MouseButton::Primary)
+            {
+                return;
+            }
+            // 阻止預設行為以免拖曳時選取文字，因此要自己把焦點移到拇指
+            event.prevent_default();
+            #[cfg(feature = "web")]
+            crate::primitives::dom::capture_pointer(&event);
+            dragging.set(Some(event.pointer_id()));
+            move_to(&event);
+            if let Some(thumb) = thumb.peek().clone() {
+                spawn(async move {
+                    let _ = thumb.set_focus(true).await;
+                });
+            }
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            if *dragging.peek() == Some(event.pointer_id()) {
+                move_to(&event);
+            }
+        }),
+        dioxus_elements::events::onpointerup(move |event: PointerEvent| end(event)),
+        dioxus_elements::events::onpointercancel(move |event: PointerEvent| end(event)),
+    ]
+}
+
+#[component]
+fn Track(
+    channel: Channel,
+    as_child: Option<AsChild>,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ColorPickerContext>();
+    let node = use_signal(|| None);
+    let rect = use_rect(node);
+    let dragging = use_signal(|| None);
+    let thumb = use_signal(|| None);
+    use_context_provider(|| TrackContext { channel, thumb });
+
+    let mut own_attributes = track_attributes(ctx, channel, node, rect, dragging, thumb);
+    own_attributes.extend([
+        Attribute::new(
+            "style",
+            "position: relative; touch-action: none;",
+            None,
+            false,
+        ),
+        Attribute::new("data-disabled", (ctx.disabled)().then_some(""), None, false),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The saturation and brightness area. Saturation grows to the right and
+/// brightness upward; style its background from
+/// `--primitive-color-picker-hue-color`. Place a [`ColorPickerThumb`] inside.
+#[component]
+pub fn ColorPickerArea(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        Track { channel: Channel::Area, as_child, attributes, {children} }
+    }
+}
+
+/// A horizontal slider for the hue. Place a [`ColorPickerThumb`] inside.
+#[component]
+pub fn ColorPickerHueSlider(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        Track { channel: Channel::Hue, as_child, attributes, {children} }
+    }
+}
+
+/// A horizontal slider for the opacity. Style its background from
+/// `--primitive-color-picker-opaque-color`. Place a [`ColorPickerThumb`]
+/// inside.
+#[component]
+pub fn ColorPickerAlphaSlider(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        Track { channel: Channel::Alpha, as_child, attributes, {children} }
+    }
+}
+
+/// The handle of an area or slider, and the element that takes the focus.
+///
+/// The arrow keys move it by 1, or by 10 with Shift; Page Up and Page Down
+/// move it by 10, Home and End to either end. It is positioned absolutely
+/// and centered on the value, with `left` and `top` in percent.
+#[component]
+pub fn ColorPickerThumb(
+    /// Announced by screen readers, e.g. "Hue". Defaults to the channel.
+    aria_label: Option<String>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ColorPickerContext>();
+    let track = use_context::<TrackContext>();
+    let mut thumb = track.thumb;
+    let channel = track.channel;
+    let color = (ctx.color)();
+    let disabled = (ctx.disabled)();
+    let (left, top) = channel.position(color);
+
+    let percent = |value: f64| (value * 100.0).round();
+    let (label, now, max, text) = match channel {
+        Channel::Area => (
+            "Color",
+            percent(color.saturation),
+            100.0,
+            format!(
+                "Saturation {}%, Brightness {}%",
+                percent(color.saturation),
+                percent(color.brightness)
+            ),
+        ),
+        Channel::Hue => (
+            "Hue",
+            color.hue.round(),
+            360.0,
+            format!("{}°", color.hue.round()),
+        ),
+        Channel::Alpha => (
+            "Alpha",
+            percent(color.alpha),
+            100.0,
+            format!("{}%", percent(color.alpha)),
+        ),
+    };
+
+    let own_attributes = vec![
+        Attribute::new("role", "slider", None, false),
+        Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+        Attribute::new(
+            "aria-label",
+            aria_label.unwrap_or_else(|| label.to_string()),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "aria-roledescription",
+            (channel == Channel::Area).then_some("2D slider"),
+            None,
+            false,
+        ),
+        Attribute::new("aria-valuemin", 0, None, false),
+        Attribute::new("aria-valuemax", max, None, false),
+        Attribute::new("aria-valuenow", now, None, false),
+        Attribute::new("aria-valuetext", text, None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new(
+            "style",
+            format!(
+                "position: absolute; left: {left}%; top: {top}%; transform: translate(-50%, -50%);"
+            ),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            thumb.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            let step = if event.modifiers().shift() { 10.0 } else { 1.0 };
+            let color = *ctx.color.peek();
+            let next = match event.key() {
+                Key::ArrowLeft => channel.nudge(color, -step, 0.0),
+                Key::ArrowRight => channel.nudge(color, step, 0.0),
+                Key::ArrowDown => channel.nudge(color, 0.0, -step),
+                Key::ArrowUp => channel.nudge(color, 0.0, step),
+                Key::PageDown => channel.nudge(color, 0.0, -10.0),
+                Key::PageUp => channel.nudge(color, 0.0, 10.0),
+                Key::Home => channel.at(color, 0.0, 1.0),
+                Key::End => channel.at(color, 1.0, 0.0),
+                _ => return,
+            };
+            event.prevent_default();
+            ctx.change(next);
+            ctx.commit.call(next);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Shows a color as `--primitive-color-picker-swatch-color`.
+///
+/// Without `color` it shows the picker's color. With one it is a preset:
+/// pressing it selects that color, and it is `data-state="checked"` while
+/// the color is selected.
+#[component]
+pub fn ColorPickerSwatch(
+    color: Option<Color>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ColorPickerContext>();
+    let current = (ctx.color)();
+    let shown = color.unwrap_or(current);
+    let disabled = (ctx.disabled)();
+
+    let mut own_attributes = vec![
+        Attribute::new("aria-label", shown.to_hex(), None, false),
+        Attribute::new(
+            "style",
+            format!("--primitive-color-picker-swatch-color: {shown};"),
+            None,
+            false,
+        ),
+    ];
+    match color {
+        None => own_attributes.push(Attribute::new("role", "img", None, false)),
+        Some(color) => {
+            let select = move || {
+                if !*ctx.disabled.peek() {
+                    ctx.change(color);
+                    ctx.commit.call(color);
+                }
+            };
+            let selected = color.to_hex() == current.to_hex();
+            own_attributes.extend([
+                Attribute::new("role", "radio", None, false),
+                Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+                Attribute::new("aria-checked", selected, None, false),
+                Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+                Attribute::new(
+                    "data-state",
+                    if selected { "checked" } else { "unchecked" },
+                    None,
+                    false,
+                ),
+                Attribute::new("data-disabled", disabled.then_some(""), None, false),
+                dioxus_elements::events::onclick(move |_| select()),
+                dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+                    if matches!(event.key(), Key::Enter)
+                        || event.key() == Key::Character(" ".into())
+                    {
+                        event.prevent_default();
+                        select();
+                    }
+                }),
+            ]);
+        }
+    }
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A text input for the color in hex.
+///
+/// The color is applied when the input changes (on Enter or blur); text
+/// that is not a valid color is replaced by the current color.
+#[component]
+pub fn ColorPickerInput(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<ColorPickerContext>();
+    let mut draft = use_signal(|| None::<String>);
+    let hex = (ctx.color)().to_hex();
+    let text = draft().unwrap_or(hex);
+
+    let own_attributes = vec![
+        Attribute::new("type", "text", None, false),
+        Attribute::new("value", text, None, false),
+        Attribute::new("spellcheck", "false", None, false),
+        Attribute::new("autocomplete", "off", None, false),
+        Attribute::new("disabled", (ctx.disabled)(), None, false),
+        dioxus_elements::events::oninput(move |event: FormEvent| {
+            draft.set(Some(event.value()));
+        }),
+        dioxus_elements::events::onchange(move |event: FormEvent| {
+            draft.set(None);
+            let color = *ctx.color.peek();
+            // 沒有輸入 alpha 時保留目前的透明度
+            let text = event.value();
+            let Some(next) = Color::from_hex(&text) else {
+                return;
+            };
+            let digits = text.trim().trim_start_matches('#').len();
+            let next = if digits == 8 {
+                next
+            } else {
+                next.with_alpha(color.alpha)
+            };
+            ctx.change(next);
+            ctx.commit.call(next);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+/// A button that picks a color from anywhere on the screen with the
+/// browser's `EyeDropper`. It is disabled, with `data-unsupported`, where
+/// the eye dropper is not available.
+#[component]
+pub fn ColorPickerEyeDropper(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ColorPickerContext>();
+    let supported = use_hook(eye_dropper_supported);
+    let disabled = (ctx.disabled)() || !supported;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-unsupported", (!supported).then_some(""), None, false),
+        dioxus_elements::events::onclick(move |_| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            spawn(async move {
+                let Some(picked) = pick_color().await else {
+                    return;
+                };
+                let next = picked.with_alpha(ctx.color.peek().alpha);
+                ctx.change(next);
+                ctx.commit.call(next);
+            });
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+fn eye_dropper_supported() -> bool {
+    web::supported()
+}
+
+#[cfg(not(feature = "web"))]
+fn eye_dropper_supported() -> bool {
+    false
+}
+
+#[cfg(feature = "web")]
+async fn pick_color() -> Option<Color> {
+    web::pick().await.and_then(|hex| Color::from_hex(&hex))
+}
+
+#[cfg(not(feature = "web"))]
+async fn pick_color() -> Option<Color> {
+    None
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use js_sys::{Array, Function, Promise, Reflect};
+    use wasm_bindgen::JsCast;
+
+    // EyeDropper 尚未進入 web-sys 的穩定 API，透過 Reflect 呼叫
+    pub(super) fn supported() -> bool {
+        web_sys::window()
+            .and_then(|window| Reflect::has(&window, &"EyeDropper".into()).ok())
+            .unwrap_or(false)
+    }
+
+    // 使用者按 Escape 取消時 promise 會 reject，視為沒有選取
+    pub(super) async fn pick() -> Option<String> {
+        let window = web_sys::window()?;
+        let constructor = Reflect::get(&window, &"EyeDropper".into())
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let dropper = Reflect::construct(&constructor, &Array::new()).ok()?;
+        let open = Reflect::get(&dropper, &"open".into())
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let promise = open.call0(&dropper).ok()?.dyn_into::<Promise>().ok()?;
+        let result = wasm_bindgen_futures::JsFuture::from(promise).await.ok()?;
+        Reflect::get(&result, &"sRGBHex".into()).ok()?.as_string()
+    }
+}
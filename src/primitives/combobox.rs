@@ -0,0 +1,610 @@
+//! Combobox Primitive
+//!
+//! A text input with a list of suggestions filtered as the user types,
+//! following the ARIA combobox pattern: focus stays in the input while the
+//! arrow keys move a highlight through the list, announced with
+//! `aria-activedescendant`, and Enter or a click selects the highlighted
+//! suggestion. The number of matching suggestions is announced through the
+//! [`Announcer`](super::announcer::Announcer) as the user types.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Combobox { on_value_change: move |value| fruit.set(value),
+//!         ComboboxInput { placeholder: "Pick a fruit" }
+//!         ComboboxTrigger { "▾" }
+//!         ComboboxPortal {
+//!             ComboboxContent {
+//!                 ComboboxItem { value: "apple", "Apple" }
+//!                 ComboboxItem { value: "banana", "Banana" }
+//!                 ComboboxEmpty { "No fruit found" }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::announcer::use_announce;
+use super::context::create_context::use_synced_signal;
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, PositionOptions, Side};
+use super::portal::{Portal, PortalTarget};
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct ComboboxItemEntry {
+    value: String,
+    text_value: String,
+    disabled: bool,
+    id: String,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Clone, Copy)]
+struct ComboboxContext {
+    value: Memo<Option<String>>,
+    set_value: Callback<Option<String>>,
+    input_value: Memo<String>,
+    set_input_value: Callback<String>,
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    disabled: Signal<bool>,
+    filter: Signal<Callback<(String, String), bool>>,
+    // 依掛載順序排列的項目
+    items: Signal<Vec<ComboboxItemEntry>>,
+    highlighted: Signal<Option<String>>,
+    // 打開清單後還沒輸入時顯示所有項目
+    filtering: Signal<bool>,
+    input: Signal<Option<Rc<MountedData>>>,
+    trigger: Signal<Option<Rc<MountedData>>>,
+    listbox_id: Signal<String>,
+}
+
+impl ComboboxContext {
+    fn is_visible(&self, item: &ComboboxItemEntry) -> bool {
+        !(self.filtering)() || (self.filter)().call(((self.input_value)(), item.text_value.clone()))
+    }
+
+    fn visible_items(&self) -> Vec<ComboboxItemEntry> {
+        self.items
+            .read()
+            .iter()
+            .filter(|item| self.is_visible(item))
+            .cloned()
+            .collect()
+    }
+
+    fn highlight(&mut self, value: Option<String>) {
+        let node = value.as_ref().and_then(|value| {
+            self.items
+                .peek()
+                .iter()
+                .find(|item| item.value == *value)
+                .and_then(|item| item.node.clone())
+        });
+        self.highlighted.set(value);
+        if let Some(node) = node {
+            spawn(async move {
+                let _ = node.scroll_to(ScrollBehavior::Instant).await;
+            });
+        }
+    }
+
+    fn move_highlight(&mut self, step: isize) {
+        let items: Vec<_> = self
+            .visible_items()
+            .into_iter()
+            .filter(|item| !item.disabled)
+            .collect();
+        if items.is_empty() {
+            return;
+        }
+        let current = self
+            .highlighted
+            .peek()
+            .as_ref()
+            .and_then(|value| items.iter().position(|item| item.value == *value));
+        let last = items.len() as isize - 1;
+        let next = match (step, current) {
+            (isize::MIN, _) => 0,
+            (isize::MAX, _) => last,
+            (step, Some(current)) => (current as isize + step).clamp(0, last),
+            (step, None) if step > 0 => 0,
+            _ => last,
+        };
+        self.highlight(Some(items[next as usize].value.clone()));
+    }
+
+    fn select(&mut self, item: &ComboboxItemEntry) {
+        self.set_value.call(Some(item.value.clone()));
+        self.set_input_value.call(item.text_value.clone());
+        self.filtering.set(false);
+        self.set_open.call(false);
+    }
+
+    fn open_list(&mut self) {
+        if !*self.open.peek() {
+            self.filtering.set(false);
+            self.set_open.call(true);
+        }
+    }
+}
+
+fn default_filter(search: String, text: String) -> bool {
+    text.to_lowercase().contains(&search.to_lowercase())
+}
+
+fn results_message(count: usize) -> String {
+    match count {
+        0 => "No results".to_string(),
+        1 => "1 result available".to_string(),
+        count => format!("{count} results available"),
+    }
+}
+
+/// The root of a combobox, owning the selected value, the text of the input
+/// and whether the list is open. Each can be controlled with its prop and
+/// change handler, or left to the combobox.
+#[component]
+pub fn Combobox(
+    value: Option<Option<String>>,
+    #[props(default)] default_value: Option<String>,
+    on_value_change: Option<EventHandler<Option<String>>>,
+    input_value: Option<String>,
+    #[props(default)] default_input_value: String,
+    on_input_value_change: Option<EventHandler<String>>,
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Whether the item with the given text matches the input's text.
+    /// Defaults to a case-insensitive substring match.
+    filter: Option<Callback<(String, String), bool>>,
+    #[props(default)] disabled: bool,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let (input_value, set_input_value) =
+        use_controllable_state(input_value, || default_input_value, on_input_value_change);
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let disabled = use_synced_signal(disabled);
+    let default_filter =
+        use_callback(|(search, text): (String, String)| default_filter(search, text));
+    let filter = use_synced_signal(filter.unwrap_or(default_filter));
+    let listbox_id = use_id();
+    let mut ctx = use_context_provider(|| ComboboxContext {
+        value,
+        set_value,
+        input_value,
+        set_input_value,
+        open,
+        set_open,
+        disabled,
+        filter,
+        items: Signal::new(Vec::new()),
+        highlighted: Signal::new(None),
+        filtering: Signal::new(false),
+        input: Signal::new(None),
+        trigger: Signal::new(None),
+        listbox_id: Signal::new(listbox_id),
+    });
+
+    // 開啟時先標示已選取的項目，關閉時清除
+    use_effect(move || {
+        if open() {
+            let selected = value.peek().clone();
+            ctx.highlight(selected);
+        } else {
+            ctx.highlighted.set(None);
+        }
+    });
+
+    // 篩選結果改變時朗讀符合的項目數量
+    let announce = use_announce();
+    use_effect(move || {
+        if open() && (ctx.filtering)() {
+            announce.polite(results_message(ctx.visible_items().len()));
+        }
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The text input of the combobox. The list is positioned against it.
+#[component]
+pub fn ComboboxInput(#[props(extends = GlobalAttributes)] attributes: Vec<Attribute>) -> Element {
+    let mut ctx = use_context::<ComboboxContext>();
+    let open = (ctx.open)();
+    let disabled = (ctx.disabled)();
+    let highlighted_id = ctx.highlighted.read().as_ref().and_then(|value| {
+        ctx.items
+            .read()
+            .iter()
+            .find(|item| item.value == *value)
+            .map(|item| item.id.clone())
+    });
+
+    let own_attributes = vec![
+        Attribute::new("type", "text", None, false),
+        Attribute::new("role", "combobox", None, false),
+        Attribute::new("autocomplete", "off", None, false),
+        Attribute::new("aria-autocomplete", "list", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", ctx.listbox_id.read().clone(), None, false),
+        Attribute::new("aria-activedescendant", highlighted_id, None, false),
+        Attribute::new("value", (ctx.input_value)(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.input.set(Some(event.data()))
+        }),
+        dioxus_elements::events::oninput(move |event: FormEvent| {
+            ctx.set_input_value.call(event.value());
+            ctx.filtering.set(true);
+            ctx.set_open.call(true);
+            // 輸入後標示第一個符合的項目
+            let first = ctx
+                .visible_items()
+                .into_iter()
+                .find(|item| !item.disabled)
+                .map(|item| item.value);
+            ctx.highlight(first);
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            let open = *ctx.open.peek();
+            match event.key() {
+                Key::ArrowDown | Key::ArrowUp if !open => {
+                    event.prevent_default();
+                    ctx.open_list();
+                }
+                Key::ArrowDown => {
+                    event.prevent_default();
+                    ctx.move_highlight(1);
+                }
+                Key::ArrowUp => {
+                    event.prevent_default();
+                    ctx.move_highlight(-1);
+                }
+                Key::Home | Key::End if open => {
+                    event.prevent_default();
+                    ctx.move_highlight(if event.key() == Key::Home {
+                        isize::MIN
+                    } else {
+                        isize::MAX
+                    });
+                }
+                Key::Enter if open => {
+                    let highlighted = ctx.highlighted.peek().clone();
+                    let item = highlighted.and_then(|value| {
+                        ctx.items
+                            .peek()
+                            .iter()
+                            .find(|item| item.value == value && !item.disabled)
+                            .cloned()
+                    });
+                    if let Some(item) = item {
+                        event.prevent_default();
+                        ctx.select(&item);
+                    }
+                }
+                // 清單已關閉時 Escape 清除輸入
+                Key::Escape if !open => {
+                    ctx.set_input_value.call(String::new());
+                    ctx.set_value.call(None);
+                }
+                _ => {}
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| {
+                let attributes = merge_attributes(anchor, attributes.clone());
+                rsx! {
+                    input { ..attributes }
+                }
+            },
+        }
+    }
+}
+
+/// A button that toggles the list, e.g. a chevron next to the input.
+#[component]
+pub fn ComboboxTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<ComboboxContext>();
+    let open = (ctx.open)();
+    let disabled = (ctx.disabled)();
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        // 焦點留在輸入框中，按鈕不需要 Tab 停駐點
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new("aria-label", "Show suggestions", None, false),
+        Attribute::new("aria-controls", ctx.listbox_id.read().clone(), None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.trigger.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if disabled {
+                return;
+            }
+            if open {
+                ctx.set_open.call(false);
+            } else {
+                ctx.open_list();
+            }
+            let input = ctx.input.peek().clone();
+            if let Some(input) = input {
+                spawn(async move {
+                    let _ = input.set_focus(true).await;
+                });
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Renders the content into `target`, outside of the page's layout; see
+/// [`Portal`].
+#[component]
+pub fn ComboboxPortal(#[props(default)] target: PortalTarget, children: Element) -> Element {
+    rsx! {
+        Portal { target, {children} }
+    }
+}
+
+/// The list of suggestions, rendered while open and until its exit animation
+/// finishes.
+#[component]
+pub fn ComboboxContent(
+    #[props(default = Side::Bottom)] side: Side,
+    #[props(default = 4.0)] side_offset: f64,
+    #[props(default = Align::Start)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired on Escape; prevent it to keep the list open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the input and the list; prevent it
+    /// to keep the list open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ComboboxContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                ComboboxContentImpl {
+                    position,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn ComboboxContentImpl(
+    position: PositionOptions,
+    element_attributes: Vec<Attribute>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ComboboxContext>();
+    let set_open = ctx.set_open;
+
+    let own_attributes = vec![
+        Attribute::new("role", "listbox", None, false),
+        Attribute::new("id", ctx.listbox_id.read().clone(), None, false),
+        // 點擊清單時不讓輸入框失去焦點
+        dioxus_elements::events::onmousedown(move |event: MouseEvent| event.prevent_default()),
+    ];
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            DismissableLayer {
+                inside_elements: vec![ctx.input, ctx.trigger],
+                on_escape_key_down,
+                on_pointer_down_outside,
+                on_dismiss: move |_| set_open.call(false),
+                as_child: move |layer: Vec<Attribute>| {
+                    let attributes = merge_attributes(layer, attributes.clone());
+                    rsx! {
+                        div {
+                            ..attributes,
+                            {children.clone()}
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// A suggestion. Hidden while it does not match the input's text.
+#[component]
+pub fn ComboboxItem(
+    value: String,
+    /// The text matched against the input and shown in it once selected.
+    /// Defaults to `value`.
+    text_value: Option<String>,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<ComboboxContext>();
+    let id = use_id();
+    let text_value = text_value.unwrap_or_else(|| value.clone());
+    let entry = ComboboxItemEntry {
+        value: value.clone(),
+        text_value,
+        disabled,
+        id: id.clone(),
+        node: None,
+    };
+
+    // 註冊或更新項目，保留已掛載的節點
+    let registered = entry.clone();
+    use_effect(move || {
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|item| item.id == registered.id) {
+            Some(item) => {
+                let node = item.node.take();
+                *item = ComboboxItemEntry {
+                    node,
+                    ..registered.clone()
+                };
+            }
+            None => items.push(registered.clone()),
+        }
+    });
+    {
+        let id = id.clone();
+        use_drop(move || ctx.items.write().retain(|item| item.id != id));
+    }
+
+    if !ctx.is_visible(&entry) {
+        return rsx! {};
+    }
+
+    let selected = ctx.value.read().as_ref() == Some(&value);
+    let highlighted = ctx.highlighted.read().as_ref() == Some(&value);
+    let mount_id = id.clone();
+    let hover_value = value.clone();
+    let own_attributes = vec![
+        Attribute::new("role", "option", None, false),
+        Attribute::new("id", id, None, false),
+        Attribute::new("aria-selected", selected, None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new(
+            "data-state",
+            if selected { "checked" } else { "unchecked" },
+            None,
+            false,
+        ),
+        Attribute::new("data-highlighted", highlighted.then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            if let Some(item) = ctx
+                .items
+                .write()
+                .iter_mut()
+                .find(|item| item.id == mount_id)
+            {
+                item.node = Some(event.data());
+            }
+        }),
+        dioxus_elements::events::onpointermove(move |_: PointerEvent| {
+            if !disabled && *ctx.highlighted.peek() != Some(hover_value.clone()) {
+                ctx.highlight(Some(hover_value.clone()));
+            }
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !disabled {
+                ctx.select(&entry);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Rendered while no suggestion matches the input's text.
+#[component]
+pub fn ComboboxEmpty(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ComboboxContext>();
+    if !ctx.visible_items().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            role: "presentation",
+            ..attributes,
+            {children}
+        }
+    }
+}
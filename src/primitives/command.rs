@@ -0,0 +1,737 @@
+//! Command Primitive
+//!
+//! A command menu: a search input over a list of commands, filtered and
+//! ranked as the user types. Focus stays in the input while the arrow keys
+//! move a highlight through the list, and Enter runs the highlighted
+//! command. [`CommandDialog`] puts the menu in a [`Dialog`] opened with a
+//! shortcut, for an app-wide ⌘K palette.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     CommandDialog { shortcut: "mod+k",
+//!         CommandInput { placeholder: "Type a command…" }
+//!         CommandList {
+//!             CommandEmpty { "No results" }
+//!             CommandGroup { heading: "Navigation",
+//!                 CommandItem { value: "Go to settings", on_select: move |_| open_settings(),
+//!                     "Settings"
+//!                     CommandShortcut { shortcut: "mod+," }
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Nested pages show a different set of commands: push a page name with
+//! `on_pages_change`, wrap its items in a [`CommandPage`], and Backspace in
+//! the empty input goes back a page.
+
+use super::context::create_context::use_synced_signal;
+use super::dialog::{Dialog, DialogContent, DialogOverlay, DialogPortal};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hotkeys::{Hotkey, HotkeyOptions, use_hotkey_with};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Rank how well `text` matches `search`, from 0 (no match) to 1.
+///
+/// An exact match ranks highest, then a prefix, a word prefix, a substring
+/// and finally the characters of `search` appearing in order, ranked by how
+/// close together they are. Case is ignored.
+pub fn command_score(search: &str, text: &str) -> f64 {
+    let search = search.trim().to_lowercase();
+    let text = text.to_lowercase();
+    if search.is_empty() || text == search {
+        return 1.0;
+    }
+    if text.starts_with(&search) {
+        return 0.9;
+    }
+    if text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(&search))
+    {
+        return 0.8;
+    }
+    if text.contains(&search) {
+        return 0.7;
+    }
+
+    // 依序出現的字元，越集中分數越高
+    let text: Vec<char> = text.chars().collect();
+    let mut position = 0;
+    let mut first = None;
+    for wanted in search.chars().filter(|c| !c.is_whitespace()) {
+        let Some(offset) = text[position..].iter().position(|c| *c == wanted) else {
+            return 0.0;
+        };
+        first.get_or_insert(position + offset);
+        position += offset + 1;
+    }
+    let span = position - first.unwrap_or_default();
+    let length = search.chars().filter(|c| !c.is_whitespace()).count();
+    0.6 * length as f64 / span.max(1) as f64
+}
+
+#[derive(Clone)]
+struct CommandItemEntry {
+    id: usize,
+    value: String,
+    keywords: Vec<String>,
+    disabled: bool,
+    group: Option<usize>,
+    page: Option<String>,
+    element_id: String,
+    select: Callback<()>,
+    node: Option<Rc<MountedData>>,
+}
+
+// 排序後的項目與它們的名次
+struct Ranked {
+    entry: CommandItemEntry,
+    rank: usize,
+    group_rank: usize,
+}
+
+#[derive(Clone, Copy)]
+struct CommandContext {
+    search: Memo<String>,
+    set_search: Callback<String>,
+    pages: Memo<Vec<String>>,
+    set_pages: Callback<Vec<String>>,
+    should_filter: Signal<bool>,
+    filter: Signal<Option<Callback<(String, String, Vec<String>), f64>>>,
+    loop_focus: Signal<bool>,
+    label: Signal<Option<String>>,
+    items: Signal<Vec<CommandItemEntry>>,
+    highlighted: Signal<Option<usize>>,
+    list_id: Signal<String>,
+}
+
+impl CommandContext {
+    fn score(&self, entry: &CommandItemEntry) -> f64 {
+        if entry.page.as_ref() != self.pages.read().last() {
+            return 0.0;
+        }
+        let search = (self.search)();
+        if !(self.should_filter)() || search.trim().is_empty() {
+            return 1.0;
+        }
+        if let Some(filter) = (self.filter)() {
+            return filter.call((search, entry.value.clone(), entry.keywords.clone()));
+        }
+        // 關鍵字的分數稍低於名稱本身
+        let keywords = entry
+            .keywords
+            .iter()
+            .map(|keyword| command_score(&search, keyword) * 0.95);
+        std::iter::once(command_score(&search, &entry.value))
+            .chain(keywords)
+            .fold(0.0, f64::max)
+    }
+
+    /// The visible items in display order: by score, with each group placed
+    /// at its best item.
+    fn ranked(&self) -> Vec<Ranked> {
+        let items = self.items.read().clone();
+        #[cfg(feature = "web")]
+        let items = {
+            let mut items = items;
+            items.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => super::dom::document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            items
+        };
+        let mut scored: Vec<(CommandItemEntry, f64)> = items
+            .into_iter()
+            .map(|entry| {
+                let score = self.score(&entry);
+                (entry, score)
+            })
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        // sort_by 是穩定排序，同分時保留文件順序
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let group_rank = |group: Option<usize>, rank: usize| {
+            group
+                .and_then(|group| {
+                    scored
+                        .iter()
+                        .position(|(entry, _)| entry.group == Some(group))
+                })
+                .unwrap_or(rank)
+        };
+        let mut ranked: Vec<Ranked> = scored
+            .iter()
+            .enumerate()
+            .map(|(rank, (entry, _))| Ranked {
+                entry: entry.clone(),
+                rank,
+                group_rank: group_rank(entry.group, rank),
+            })
+            .collect();
+        ranked.sort_by_key(|ranked| (ranked.group_rank, ranked.rank));
+        ranked
+    }
+
+    fn selectable(&self) -> Vec<CommandItemEntry> {
+        self.ranked()
+            .into_iter()
+            .map(|ranked| ranked.entry)
+            .filter(|entry| !entry.disabled)
+            .collect()
+    }
+
+    fn highlight(&mut self, id: Option<usize>, scroll: bool) {
+        self.highlighted.set(id);
+        let node = id.and_then(|id| {
+            self.items
+                .peek()
+                .iter()
+                .find(|entry| entry.id == id)
+                .and_then(|entry| entry.node.clone())
+        });
+        if let (true, Some(node)) = (scroll, node) {
+            spawn(async move {
+                let _ = node.scroll_to(ScrollBehavior::Instant).await;
+            });
+        }
+    }
+
+    fn move_highlight(&mut self, step: isize) {
+        let items = self.selectable();
+        if items.is_empty() {
+            return;
+        }
+        let current = self
+            .highlighted
+            .peek()
+            .and_then(|id| items.iter().position(|entry| entry.id == id));
+        let last = items.len() as isize - 1;
+        let next = match (step, current) {
+            (isize::MIN, _) => 0,
+            (isize::MAX, _) => last,
+            (step, Some(current)) if *self.loop_focus.peek() => {
+                (current as isize + step).rem_euclid(last + 1)
+            }
+            (step, Some(current)) => (current as isize + step).clamp(0, last),
+            (step, None) if step > 0 => 0,
+            _ => last,
+        };
+        self.highlight(Some(items[next as usize].id), true);
+    }
+}
+
+/// The root of a command menu, owning the search text and the page stack.
+///
+/// Pass `search` and `on_search_change` to control the search, and `pages`
+/// and `on_pages_change` to control the pages; the last page is the one
+/// shown. `filter` replaces [`command_score`] and is called with the
+/// search, an item's value and its keywords; items scoring 0 are hidden.
+#[component]
+pub fn Command(
+    search: Option<String>,
+    #[props(default)] default_search: String,
+    on_search_change: Option<EventHandler<String>>,
+    pages: Option<Vec<String>>,
+    #[props(default)] default_pages: Vec<String>,
+    on_pages_change: Option<EventHandler<Vec<String>>>,
+    /// Set to `false` to filter the items yourself, e.g. from a server.
+    #[props(default = true)]
+    should_filter: bool,
+    filter: Option<Callback<(String, String, Vec<String>), f64>>,
+    /// Wrap the highlight around from the last item to the first.
+    #[props(default)]
+    loop_focus: bool,
+    /// The accessible name of the input and the list.
+    label: Option<String>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (search, set_search) = use_controllable_state(search, || default_search, on_search_change);
+    let (pages, set_pages) = use_controllable_state(pages, || default_pages, on_pages_change);
+    let list_id = use_id();
+    let should_filter = use_synced_signal(should_filter);
+    let filter = use_synced_signal(filter);
+    let loop_focus = use_synced_signal(loop_focus);
+    let label = use_synced_signal(label);
+    let mut ctx = use_context_provider(|| CommandContext {
+        search,
+        set_search,
+        pages,
+        set_pages,
+        should_filter,
+        filter,
+        loop_focus,
+        label,
+        items: Signal::new(Vec::new()),
+        highlighted: Signal::new(None),
+        list_id: Signal::new(list_id),
+    });
+
+    // 搜尋或頁面改變、或高亮的項目被隱藏時，高亮第一個項目
+    let mut previous = use_signal(|| (search.peek().clone(), pages.peek().clone()));
+    use_effect(move || {
+        let items = ctx.selectable();
+        let query = ((ctx.search)(), (ctx.pages)());
+        let changed = *previous.peek() != query;
+        let current = *ctx.highlighted.peek();
+        let visible = current.is_some_and(|id| items.iter().any(|entry| entry.id == id));
+        if changed || !visible {
+            previous.set(query);
+            ctx.highlight(items.first().map(|entry| entry.id), changed);
+        }
+    });
+
+    let own_attributes = vec![Attribute::new(
+        "data-page",
+        pages.read().last().cloned(),
+        None,
+        false,
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The search input, with `role="combobox"`.
+///
+/// The arrow keys, Home and End move the highlight, Enter runs the
+/// highlighted item, and Backspace in the empty input goes back a page.
+#[component]
+pub fn CommandInput(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<CommandContext>();
+    let highlighted = (ctx.highlighted)().and_then(|id| {
+        ctx.items
+            .read()
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.element_id.clone())
+    });
+
+    let own_attributes = vec![
+        Attribute::new("type", "text", None, false),
+        Attribute::new("role", "combobox", None, false),
+        Attribute::new("autocomplete", "off", None, false),
+        Attribute::new("autocorrect", "off", None, false),
+        Attribute::new("spellcheck", "false", None, false),
+        Attribute::new("aria-expanded", "true", None, false),
+        Attribute::new("aria-autocomplete", "list", None, false),
+        Attribute::new("aria-controls", (ctx.list_id)(), None, false),
+        Attribute::new("aria-activedescendant", highlighted, None, false),
+        Attribute::new("aria-label", (ctx.label)(), None, false),
+        Attribute::new("value", (ctx.search)(), None, false),
+        dioxus_elements::events::oninput(move |event: FormEvent| {
+            ctx.set_search.call(event.value());
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            match event.key() {
+                Key::ArrowDown => ctx.move_highlight(1),
+                Key::ArrowUp => ctx.move_highlight(-1),
+                Key::Home => ctx.move_highlight(isize::MIN),
+                Key::End => ctx.move_highlight(isize::MAX),
+                Key::Enter => {
+                    let id = *ctx.highlighted.peek();
+                    let entry = id.and_then(|id| {
+                        ctx.items
+                            .peek()
+                            .iter()
+                            .find(|entry| entry.id == id && !entry.disabled)
+                            .cloned()
+                    });
+                    if let Some(entry) = entry {
+                        entry.select.call(());
+                    }
+                }
+                Key::Backspace if ctx.search.peek().is_empty() && !ctx.pages.peek().is_empty() => {
+                    let mut pages = ctx.pages.peek().clone();
+                    pages.pop();
+                    ctx.set_pages.call(pages);
+                }
+                _ => return,
+            }
+            event.prevent_default();
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+/// The list of commands, with `role="listbox"`.
+///
+/// While searching, items and groups are reordered by rank with the CSS
+/// `order` property, so the list is a flex column.
+#[component]
+pub fn CommandList(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CommandContext>();
+
+    let own_attributes = vec![
+        Attribute::new("id", (ctx.list_id)(), None, false),
+        Attribute::new("role", "listbox", None, false),
+        Attribute::new("aria-label", (ctx.label)(), None, false),
+        Attribute::new(
+            "style",
+            "display: flex; flex-direction: column;",
+            None,
+            false,
+        ),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct GroupContext {
+    id: usize,
+}
+
+#[derive(Clone, Copy)]
+struct PageContext {
+    page: Signal<String>,
+}
+
+/// A group of items under an optional heading. It is hidden while none of
+/// its items are.
+#[component]
+pub fn CommandGroup(
+    heading: Option<String>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CommandContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let heading_id = use_id();
+    use_context_provider(|| GroupContext { id });
+
+    let ranked = ctx.ranked();
+    let order = ranked
+        .iter()
+        .find(|ranked| ranked.entry.group == Some(id))
+        .map(|ranked| ranked.group_rank);
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new(
+            "aria-labelledby",
+            heading.is_some().then(|| heading_id.clone()),
+            None,
+            false,
+        ),
+        Attribute::new("hidden", order.is_none(), None, false),
+        Attribute::new(
+            "style",
+            format!(
+                "display: flex; flex-direction: column; order: {};",
+                order.unwrap_or_default()
+            ),
+            None,
+            false,
+        ),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+    let content = rsx! {
+        if let Some(heading) = heading {
+            div { id: heading_id, "data-command-group-heading": "", aria_hidden: "true", {heading} }
+        }
+        {children}
+    };
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {content}
+        }
+    }
+}
+
+/// The items of a nested page, shown while `page` is the last of the
+/// root's pages. Items outside any page show while there are no pages.
+#[component]
+pub fn CommandPage(page: String, children: Element) -> Element {
+    let ctx = use_context::<CommandContext>();
+    let page_signal = use_synced_signal(page.clone());
+    use_context_provider(|| PageContext { page: page_signal });
+
+    if ctx.pages.read().last() != Some(&page) {
+        return rsx! {};
+    }
+    rsx! { {children} }
+}
+
+#[derive(Clone, Copy)]
+struct ItemContext {
+    select: Callback<()>,
+    visible: Memo<bool>,
+    disabled: Signal<bool>,
+}
+
+/// A command. Pressing it, or Enter while it is highlighted, calls
+/// `on_select` with its value.
+#[component]
+pub fn CommandItem(
+    /// The text the search is matched against, and passed to `on_select`.
+    value: String,
+    /// More text to match, e.g. synonyms.
+    #[props(default)]
+    keywords: Vec<String>,
+    #[props(default)] disabled: bool,
+    on_select: Option<EventHandler<String>>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<CommandContext>();
+    let group = try_use_context::<GroupContext>().map(|group| group.id);
+    let page = try_use_context::<PageContext>().map(|page| page.page);
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let element_id = use_id();
+    let entry_element_id = element_id.clone();
+    let mut node = use_signal(|| None);
+    let value_signal = use_synced_signal(value.clone());
+    let keywords = use_synced_signal(keywords);
+    let disabled_signal = use_synced_signal(disabled);
+    let select = use_callback(move |()| {
+        if let Some(on_select) = on_select {
+            on_select.call(value_signal.peek().clone());
+        }
+    });
+
+    use_effect(move || {
+        let entry = CommandItemEntry {
+            id,
+            value: value_signal(),
+            keywords: keywords(),
+            disabled: disabled_signal(),
+            group,
+            page: page.map(|page| page()),
+            element_id: entry_element_id.clone(),
+            select,
+            node: node(),
+        };
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|other| other.id == id) {
+            Some(existing) => *existing = entry,
+            None => items.push(entry),
+        }
+    });
+    use_drop(move || {
+        ctx.items.write().retain(|entry| entry.id != id);
+    });
+
+    let rank = use_memo(move || {
+        ctx.ranked()
+            .iter()
+            .find(|ranked| ranked.entry.id == id)
+            .map(|ranked| ranked.rank)
+    });
+    let visible = use_memo(move || rank().is_some());
+    use_context_provider(|| ItemContext {
+        select,
+        visible,
+        disabled: disabled_signal,
+    });
+
+    let highlighted = (ctx.highlighted)() == Some(id);
+
+    let own_attributes = vec![
+        Attribute::new("id", element_id, None, false),
+        Attribute::new("role", "option", None, false),
+        Attribute::new("aria-selected", highlighted, None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("data-selected", highlighted.then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-value", value, None, false),
+        Attribute::new("hidden", !visible(), None, false),
+        Attribute::new(
+            "style",
+            format!("order: {};", rank().unwrap_or_default()),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onpointermove(move |_| {
+            if !*disabled_signal.peek() && *ctx.highlighted.peek() != Some(id) {
+                ctx.highlight(Some(id), false);
+            }
+        }),
+        // 不讓點擊搶走輸入框的焦點
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| event.prevent_default()),
+        dioxus_elements::events::onclick(move |_| {
+            if !*disabled_signal.peek() {
+                select.call(());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Rendered while no item is visible.
+#[component]
+pub fn CommandEmpty(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<CommandContext>();
+    if !ctx.ranked().is_empty() {
+        return rsx! {};
+    }
+
+    let own_attributes = vec![Attribute::new("role", "presentation", None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Shows the keyboard shortcut of its [`CommandItem`], e.g. `⌘,`, and runs
+/// the item when the shortcut is pressed while the item is visible.
+///
+/// Needs a [`HotkeyProvider`](super::hotkeys::HotkeyProvider) to run.
+///
+/// # Panics
+///
+/// Panics if `shortcut` is not a valid shortcut; see [`Hotkey::parse`].
+#[component]
+pub fn CommandShortcut(
+    shortcut: String,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let item = use_context::<ItemContext>();
+    let enabled = (item.visible)() && !(item.disabled)();
+    let options = HotkeyOptions {
+        enabled,
+        allow_in_editable: true,
+        prevent_default: true,
+    };
+    use_hotkey_with(&shortcut, options, move || item.select.call(()));
+    let label = Hotkey::parse(&shortcut)
+        .map(|hotkey| hotkey.to_string())
+        .unwrap_or_default();
+
+    rsx! {
+        kbd {
+            aria_hidden: "true",
+            ..attributes,
+            {label}
+        }
+    }
+}
+
+/// A [`Command`] menu in a modal [`Dialog`], toggled with `shortcut`.
+///
+/// The children are the menu's parts; the dialog's overlay and content can
+/// be styled with `overlay_class` and `class`. Selecting an item does not
+/// close the dialog on its own: close it from `on_select` with
+/// `on_open_change`.
+#[component]
+pub fn CommandDialog(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Toggles the dialog; needs a
+    /// [`HotkeyProvider`](super::hotkeys::HotkeyProvider).
+    #[props(default = String::from("mod+k"))]
+    shortcut: String,
+    overlay_class: Option<String>,
+    label: Option<String>,
+    #[props(default = true)] should_filter: bool,
+    filter: Option<Callback<(String, String, Vec<String>), f64>>,
+    #[props(default)] loop_focus: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let options = HotkeyOptions {
+        enabled: true,
+        allow_in_editable: true,
+        prevent_default: true,
+    };
+    use_hotkey_with(&shortcut, options, move || {
+        let next = !*open.peek();
+        set_open.call(next);
+    });
+
+    rsx! {
+        Dialog {
+            open: open(),
+            on_open_change: move |next| set_open.call(next),
+            DialogPortal {
+                DialogOverlay { class: overlay_class }
+                DialogContent {
+                    aria_label: label.clone(),
+                    ..attributes,
+                    // 每次打開都是一個新的 Command，搜尋從空白開始
+                    Command { label, should_filter, filter, loop_focus, {children} }
+                }
+            }
+        }
+    }
+}
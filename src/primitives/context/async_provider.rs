@@ -0,0 +1,74 @@
+use dioxus::prelude::*;
+
+/// The state of an asynchronously provided context value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextState<T, E> {
+    /// The resource has not resolved yet.
+    Loading,
+    /// The resource resolved successfully.
+    Ready(T),
+    /// The resource resolved with an error.
+    Error(E),
+}
+
+impl<T, E> ContextState<T, E> {
+    pub fn is_loading(&self) -> bool {
+        matches!(self, ContextState::Loading)
+    }
+
+    /// The resolved value, if the resource finished successfully.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            ContextState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Provide the output of a [`use_resource`] to descendants as a [`ContextState`].
+///
+/// Pass the resource's value signal (`resource.value()`); consumers read it
+/// with [`use_async_context`] and rerender when the resource resolves or reruns.
+///
+/// ```rust,ignore
+/// let session = use_resource(fetch_session);
+///
+/// rsx! {
+///     AsyncProvider { resource: session.value(), App {} }
+/// }
+/// ```
+#[component]
+pub fn AsyncProvider<T: 'static + Clone + PartialEq, E: 'static + Clone + PartialEq>(
+    resource: ReadOnlySignal<Option<Result<T, E>>>,
+    children: Element,
+) -> Element {
+    // resource 重新解析時，memo 會以新的狀態重新提供給使用者
+    let state = use_memo(move || match &*resource.read() {
+        None => ContextState::Loading,
+        Some(Ok(value)) => ContextState::Ready(value.clone()),
+        Some(Err(err)) => ContextState::Error(err.clone()),
+    });
+
+    use_context_provider(|| AsyncContext(state));
+
+    rsx! { {children} }
+}
+
+/// Read the state provided by the nearest [`AsyncProvider`] for `Result<T, E>`.
+///
+/// Returns `None` when there is no such provider above the caller.
+pub fn use_async_context<T: 'static + Clone + PartialEq, E: 'static + Clone + PartialEq>()
+-> Option<ContextState<T, E>> {
+    try_use_context::<AsyncContext<T, E>>().map(|AsyncContext(state)| state.read().clone())
+}
+
+// 包裝 Memo，避免與 create_context 提供的 Memo<T> 衝突
+struct AsyncContext<T: 'static, E: 'static>(Memo<ContextState<T, E>>);
+
+impl<T: 'static, E: 'static> Clone for AsyncContext<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, E: 'static> Copy for AsyncContext<T, E> {}
@@ -0,0 +1,80 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// A set of contexts captured at a call site, re-provided by [`ContextBridge`].
+///
+/// Build one with the [`bridge!`](crate::bridge) macro or by chaining
+/// [`with`](Self::with) / [`with_context`](Self::with_context) while
+/// rendering the component that owns the contexts.
+#[derive(Clone, Default)]
+pub struct BridgedContexts {
+    providers: Vec<Rc<dyn Fn()>>,
+}
+
+impl BridgedContexts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the value of type `T` provided by
+    /// [`create_context`](super::create_context::create_context), if any.
+    pub fn with<T: 'static + Clone + PartialEq>(self) -> Self {
+        self.with_context::<Memo<T>>()
+    }
+
+    /// Capture the nearest raw Dioxus context of type `C`, if any.
+    pub fn with_context<C: 'static + Clone>(mut self) -> Self {
+        if let Some(value) = try_consume_context::<C>() {
+            self.providers.push(Rc::new(move || {
+                provide_context(value.clone());
+            }));
+        }
+        self
+    }
+}
+
+impl PartialEq for BridgedContexts {
+    fn eq(&self, other: &Self) -> bool {
+        self.providers.len() == other.providers.len()
+            && self
+                .providers
+                .iter()
+                .zip(&other.providers)
+                .all(|(a, b)| Rc::ptr_eq(a, b))
+    }
+}
+
+/// Re-provide contexts captured elsewhere to its children.
+///
+/// Use it where content is rendered under a different part of the tree, such
+/// as inside a portal host, so the content still sees the contexts of the
+/// place it was declared. Contexts are provided once, on mount; signal-backed
+/// contexts (like those from `create_context`) keep updating through their
+/// handles.
+#[component]
+pub fn ContextBridge(contexts: BridgedContexts, children: Element) -> Element {
+    use_hook(|| {
+        for provide in &contexts.providers {
+            provide();
+        }
+    });
+
+    rsx! { {children} }
+}
+
+/// Capture the listed context value types at the call site.
+///
+/// ```rust,ignore
+/// let contexts = bridge![ThemeCtx, DialogCtx];
+///
+/// rsx! {
+///     ContextBridge { contexts, {children} }
+/// }
+/// ```
+#[macro_export]
+macro_rules! bridge {
+    ($($ty:ty),* $(,)?) => {
+        $crate::primitives::context::context_bridge::BridgedContexts::new()
+            $(.with::<$ty>())*
+    };
+}
@@ -1,10 +1,14 @@
+use super::error::ContextError;
 use dioxus::prelude::*;
 use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub fn create_context<T: 'static + Clone + PartialEq>(
     root_component_name: &str,
@@ -12,77 +16,295 @@ pub fn create_context<T: 'static + Clone + PartialEq>(
 ) -> (
     impl Fn(T, Element) -> Element + 'static,
     impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
 ) {
-    let root_component_name = root_component_name.to_string();
+    build_context(
+        root_component_name,
+        Rc::new(move || default_context.clone()),
+    )
+}
 
-    // Provider component
-    #[component]
-    fn Provider<T: 'static + Clone + PartialEq>(value: T, children: Element) -> Element {
-        // Create a signal for the context value
-        let context_signal = use_signal(|| value.clone());
+/// Like [`create_context`], but the default is built lazily by `default_fn`
+/// only when a consumer has no provider above it.
+pub fn create_context_with_default<T: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+    default_fn: impl Fn() -> T + 'static,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+) {
+    build_context(root_component_name, Rc::new(move || Some(default_fn())))
+}
 
-        // Memoize the context value to avoid unnecessary rerenders
-        // Similar to React.useMemo with Object.values dependency
-        let memoized_value = use_memo(move || context_signal.read().clone());
+/// Like [`create_context`], but the provider is a real component that can be
+/// used directly in `rsx!`:
+///
+/// ```rust,ignore
+/// let (ThemeProvider, use_theme, _) = create_context_component("ThemeProvider", None);
+///
+/// rsx! {
+///     ThemeProvider { value: theme, Toolbar {} }
+/// }
+/// ```
+pub fn create_context_component<T: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+    default_context: Option<T>,
+) -> (
+    fn(ProviderProps<T>) -> Element,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+) {
+    let (_, use_ctx, try_use_ctx) = create_context(root_component_name, default_context);
 
-        // Provide the context
-        use_context_provider(|| memoized_value.clone());
+    (Provider::<T>, use_ctx, try_use_ctx)
+}
 
-        // Render children
-        rsx! { {children} }
-    }
+/// Provides `value` to its children, as consumed by the hooks returned from
+/// [`create_context`] and its variants.
+///
+/// `name` only labels the provider in the `debug` registry; when omitted the
+/// name registered for `T` is used.
+#[component]
+pub fn Provider<T: 'static + Clone + PartialEq>(
+    value: T,
+    #[props(default)] name: String,
+    children: Element,
+) -> Element {
+    // Provide the context
+    let memoized_value = use_provide_context_value(value);
+
+    // 在 debug 模式下記錄 Provider 的掛載與更新
+    #[cfg(feature = "debug")]
+    super::registry::use_context_record(&name, memoized_value);
+    #[cfg(not(feature = "debug"))]
+    let _ = (name, memoized_value);
+
+    // Render children
+    rsx! { {children} }
+}
+
+// create_context 系列函數的共同實作，預設值由 default_context 延遲產生
+fn build_context<T: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+    default_context: Rc<dyn Fn() -> Option<T>>,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+) {
+    let root_component_name = root_component_name.to_string();
+
+    #[cfg(feature = "debug")]
+    super::registry::register_context::<T>(&root_component_name);
 
     // Wrapper function for the provider component
+    let provider_name = root_component_name.clone();
     let provider_fn = move |value: T, children: Element| -> Element {
         rsx! {
             Provider {
                 value: value.clone(),
+                name: provider_name.clone(),
                 children: children
             }
         }
     };
 
-    // Consumer hook
-    let use_ctx = move |consumer_name: &str| -> T {
-        // 使用擁有的字符串而不是引用
-        let context = use_context::<Option<T>>();
+    // Non-panicking consumer hook
+    let try_use_ctx = move |consumer_name: &str| -> Result<T, ContextError> {
+        try_use_context_value(consumer_name, &root_component_name, || default_context())
+    };
 
-        match context {
-            Some(ctx) => ctx,
-            None => match &default_context {
-                Some(default) => default.clone(),
-                None => panic!(
-                    "`{}` must be used within `{}`",
-                    consumer_name, root_component_name
-                ),
-            },
+    // Consumer hook
+    let use_ctx = {
+        let try_use_ctx = try_use_ctx.clone();
+        move |consumer_name: &str| -> T {
+            try_use_ctx(consumer_name).unwrap_or_else(|err| panic!("{}", err))
         }
     };
 
-    (provider_fn, use_ctx)
+    (provider_fn, use_ctx, try_use_ctx)
+}
+
+/// Provide `value` to all descendants, keeping it in sync with the value
+/// passed on later renders.
+///
+/// This is the hook behind the providers returned by [`create_context`]; it
+/// is public so generated providers (see `#[derive(ContextValue)]`) can share it.
+pub fn use_provide_context_value<T: 'static + Clone + PartialEq>(value: T) -> Memo<T> {
+    let context_signal = use_synced_signal(value);
+
+    // Memoize the context value to avoid unnecessary rerenders
+    // Similar to React.useMemo with Object.values dependency
+    let memoized_value = use_memo(move || context_signal.read().clone());
+
+    // 在 debug 模式下記錄每一次的值變化
+    #[cfg(feature = "debug")]
+    super::history::use_record_history(context_signal);
+
+    use_context_provider(|| memoized_value)
+}
+
+// Create a signal for the context value and keep it in sync with the `value`
+// prop. `use_reactive!` reruns the effect whenever the prop changes between
+// renders, and the `PartialEq` check avoids notifying consumers when nothing
+// changed.
+pub(crate) fn use_synced_signal<T: 'static + Clone + PartialEq>(value: T) -> Signal<T> {
+    let mut context_signal = use_signal(|| value.clone());
+
+    use_effect(use_reactive!(|(value,)| {
+        if *context_signal.peek() != value {
+            context_signal.set(value);
+        }
+    }));
+
+    context_signal
+}
+
+/// Read the nearest value provided by [`use_provide_context_value`].
+///
+/// `default_context` is only called when no provider exists above the
+/// caller; if it returns `None` a [`ContextError::MissingProvider`] is returned.
+pub fn try_use_context_value<T: 'static + Clone + PartialEq>(
+    consumer_name: &str,
+    root_component_name: &str,
+    default_context: impl FnOnce() -> Option<T>,
+) -> Result<T, ContextError> {
+    // Provider 提供的是 Memo<T>，讀取時會訂閱後續的值變化
+    let context = try_use_context::<Memo<T>>();
+
+    match context {
+        Some(ctx) => Ok(ctx.read().clone()),
+        None => default_context().ok_or_else(|| ContextError::MissingProvider {
+            consumer: consumer_name.to_string(),
+            root: root_component_name.to_string(),
+        }),
+    }
 }
 
 // 定義 Scope 類型
 pub type ScopeContexts = Vec<Rc<dyn Any>>;
-pub type Scope = Option<HashMap<String, ScopeContexts>>;
+pub type Scope = Option<HashMap<ScopeKey, ScopeContexts>>;
 
 // ScopeHook 類型 - 使用 Box<dyn Fn> 而不是 fn 指針
-pub type ScopeHook = Box<dyn Fn(Scope) -> HashMap<String, Scope>>;
+pub type ScopeHook = Box<dyn Fn(Scope) -> HashMap<ScopeKey, Scope>>;
 pub type ScopeHookFactory = Arc<dyn Fn() -> ScopeHook>;
 
+/// Identifies the scope created by one [`create_context_scope`] call.
+///
+/// Keys compare by a unique id, so two scopes with the same name never
+/// collide; the name is only kept for debugging.
+#[derive(Debug, Clone)]
+pub struct ScopeKey {
+    id: usize,
+    name: Rc<str>,
+}
+
+impl ScopeKey {
+    fn new(name: &str) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        ScopeKey {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name: Rc::from(name),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl PartialEq for ScopeKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for ScopeKey {}
+
+impl Hash for ScopeKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Display for ScopeKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.name, self.id)
+    }
+}
+
+/// Downcast one entry of [`ScopeContexts`] to `T`.
+pub fn downcast_scope_context<T: 'static>(context: &Rc<dyn Any>) -> Option<&T> {
+    (**context).downcast_ref::<T>()
+}
+
+/// Look up the entry at `index` of the scope `key`, downcast to `T`.
+pub fn scope_context<'a, T: 'static>(
+    scope: &'a Scope,
+    key: &ScopeKey,
+    index: usize,
+) -> Option<&'a T> {
+    scope
+        .as_ref()
+        .and_then(|scope| scope.get(key))
+        .and_then(|contexts| contexts.get(index))
+        .and_then(downcast_scope_context::<T>)
+}
+
 // Context 創建函數的返回類型
 pub type ContextPair<T> = (ContextProvider<T>, ContextConsumer<T>);
 
+// Scoped context 的唯一識別碼，Scope 中的每個項目都是一個 ContextId
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContextId(usize);
+
+impl ContextId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        ContextId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+// 樹中已提供的 scoped context 值，子 Provider 會複製父層的表後加入自己的值
+#[derive(Clone, Default)]
+struct ScopedValues(Rc<HashMap<ContextId, Rc<dyn Any>>>);
+
+// 優先使用傳入 Scope 中的 context，否則退回到 base context
+fn resolve_context_id(
+    scope: &Scope,
+    scope_key: &ScopeKey,
+    index: usize,
+    base_id: ContextId,
+) -> ContextId {
+    scope_context::<ContextId>(scope, scope_key, index)
+        .copied()
+        .unwrap_or(base_id)
+}
+
 pub struct ContextProvider<T: 'static + Clone + PartialEq> {
-    scope_name: String,
+    scope_key: ScopeKey,
     index: usize,
+    base_id: ContextId,
     _phantom: PhantomData<T>,
 }
 
 impl<T: 'static + Clone + PartialEq> ContextProvider<T> {
     pub fn render(&self, value: T, scope: Scope, children: Element) -> Element {
-        // 提供上下文值
-        use_context_provider(|| value);
+        let id = resolve_context_id(&scope, &self.scope_key, self.index, self.base_id);
+
+        // 與 create_context 相同，讓 value 的變化能通知使用者
+        let context_signal = use_synced_signal(value);
+        let memoized_value = use_memo(move || context_signal.read().clone());
+
+        // 提供上下文值：複製父層的值表並登記自己的值
+        use_context_provider(move || {
+            let mut values = try_consume_context::<ScopedValues>()
+                .map(|values| (*values.0).clone())
+                .unwrap_or_default();
+            values.insert(id, Rc::new(memoized_value) as Rc<dyn Any>);
+            ScopedValues(Rc::new(values))
+        });
 
         // 返回子元素
         rsx! { {children} }
@@ -91,25 +313,39 @@ impl<T: 'static + Clone + PartialEq> ContextProvider<T> {
 
 // Context Consumer 封裝
 pub struct ContextConsumer<T: 'static + Clone + PartialEq> {
-    scope_name: String,
+    scope_key: ScopeKey,
     index: usize,
+    base_id: ContextId,
     default_context: Option<T>,
     root_name: String,
 }
 
 impl<T: 'static + Clone + PartialEq> ContextConsumer<T> {
     pub fn consume(&self, consumer_name: &str, scope: Scope) -> T {
+        self.try_consume(consumer_name, scope)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_consume(&self, consumer_name: &str, scope: Scope) -> Result<T, ContextError> {
+        let id = resolve_context_id(&scope, &self.scope_key, self.index, self.base_id);
+
         // 獲取上下文
-        let context = use_context::<Option<T>>();
+        let context = try_use_context::<ScopedValues>().and_then(|values| {
+            values
+                .0
+                .get(&id)
+                .and_then(|value| (**value).downcast_ref::<Memo<T>>())
+                .copied()
+        });
 
         match context {
-            Some(ctx) => ctx,
+            Some(ctx) => Ok(ctx.read().clone()),
             None => match &self.default_context {
-                Some(default) => default.clone(),
-                None => panic!(
-                    "`{}` must be used within `{}`",
-                    consumer_name, self.root_name
-                ),
+                Some(default) => Ok(default.clone()),
+                None => Err(ContextError::MissingProvider {
+                    consumer: consumer_name.to_string(),
+                    root: self.root_name.clone(),
+                }),
             },
         }
     }
@@ -117,11 +353,16 @@ impl<T: 'static + Clone + PartialEq> ContextConsumer<T> {
 
 // Context Creator
 pub struct ContextCreator {
-    scope_name: String,
-    contexts: Rc<RefCell<Vec<Option<Rc<dyn Any>>>>>,
+    scope_key: ScopeKey,
+    contexts: Rc<RefCell<Vec<ContextId>>>,
 }
 
 impl ContextCreator {
+    /// The key this creator's scope is stored under in a [`Scope`].
+    pub fn scope_key(&self) -> &ScopeKey {
+        &self.scope_key
+    }
+
     pub fn create<T: 'static + Clone + PartialEq>(
         &self,
         root_name: &str,
@@ -131,24 +372,23 @@ impl ContextCreator {
         let mut contexts_mut = self.contexts.borrow_mut();
         let index = contexts_mut.len();
 
-        // 存儲默認上下文
-        contexts_mut.push(
-            default_context
-                .clone()
-                .map(|ctx| Rc::new(ctx) as Rc<dyn Any>),
-        );
+        // 存儲 base context，沒有傳入 Scope 時使用
+        let base_id = ContextId::next();
+        contexts_mut.push(base_id);
 
         // 創建 Provider
         let provider = ContextProvider {
-            scope_name: self.scope_name.clone(),
+            scope_key: self.scope_key.clone(),
             index,
+            base_id,
             _phantom: PhantomData,
         };
 
         // 創建 Consumer
         let consumer = ContextConsumer {
-            scope_name: self.scope_name.clone(),
+            scope_key: self.scope_key.clone(),
             index,
+            base_id,
             default_context,
             root_name,
         };
@@ -161,38 +401,44 @@ pub fn create_context_scope(
     scope_name: &str,
     deps: Vec<ScopeHookFactory>,
 ) -> (ContextCreator, ScopeHookFactory) {
-    // 轉換為擁有的字符串
-    let scope_name = scope_name.to_string();
-    // 存儲默認上下文
+    // 每次調用都產生唯一的 ScopeKey
+    let scope_key = ScopeKey::new(scope_name);
+    // 存儲 base contexts
     let contexts = Rc::new(RefCell::new(Vec::new()));
 
     // 創建上下文創建器
     let creator = ContextCreator {
-        scope_name: scope_name.clone(),
+        scope_key: scope_key.clone(),
         contexts: contexts.clone(),
     };
 
     // 創建 ScopeHook 工廠函數
+    // 每次調用工廠都會為每個 context 產生新的 ContextId，讓組合此 scope 的
+    // primitive 與直接使用它的 primitive 互不干擾，因此工廠應只調用一次
     let scope_hook_factory: ScopeHookFactory = Arc::new(move || {
-        let scope_name = scope_name.clone();
-        let contexts = contexts.clone();
+        let scope_key = scope_key.clone();
+        let scope_contexts: ScopeContexts = contexts
+            .borrow()
+            .iter()
+            .map(|_| Rc::new(ContextId::next()) as Rc<dyn Any>)
+            .collect();
 
         Box::new(move |scope: Scope| {
             let mut result = HashMap::new();
 
-            // 獲取上下文
-            let contexts_vec = contexts
-                .borrow()
-                .iter()
-                .map(|ctx| ctx.clone().unwrap_or_else(|| Rc::new(())))
-                .collect();
+            // 獲取上下文，外部傳入的 scope 優先
+            let contexts_vec = scope
+                .as_ref()
+                .and_then(|scope| scope.get(&scope_key))
+                .cloned()
+                .unwrap_or_else(|| scope_contexts.clone());
 
             // 創建新的範圍
-            let mut new_scope = scope.unwrap_or_else(|| HashMap::new());
-            new_scope.insert(scope_name.clone(), contexts_vec);
+            let mut new_scope = scope.unwrap_or_default();
+            new_scope.insert(scope_key.clone(), contexts_vec);
 
             // 返回範圍
-            result.insert(format!("__scope{}", scope_name), Some(new_scope));
+            result.insert(scope_key.clone(), Some(new_scope));
             result
         })
     });
@@ -212,6 +458,10 @@ pub fn create_context_scope(
 }
 
 // Compose multiple context scopes
+//
+// 每個 hook 都只返回一個以自己的 ScopeKey 為鍵的項目。依照工廠的順序把所有
+// 項目中的 scope 合併成一個表，並放在第一個（base）scope 的鍵之下，結果不依賴
+// HashMap 的迭代順序。
 pub fn compose_context_scopes(factories: Vec<ScopeHookFactory>) -> ScopeHookFactory {
     if factories.len() == 1 {
         // 如果只有一個工廠，直接返回它
@@ -225,22 +475,128 @@ pub fn compose_context_scopes(factories: Vec<ScopeHookFactory>) -> ScopeHookFact
 
         // 返回一個新的 hook，組合所有 hooks 的結果
         Box::new(move |scope: Scope| {
-            let next_scopes = hooks.iter().fold(HashMap::new(), |acc, hook| {
-                // 對每個 hook 調用並合併結果
-                let scope_props = hook(scope.clone());
-                acc.into_iter().chain(scope_props.into_iter()).collect()
-            });
-
-            // 從合併結果中提取基本範圍
-            // 這裡簡化處理，實際情況可能需要更精確的邏輯
-            if let Some(first_scope) = next_scopes.values().next() {
-                HashMap::from([(format!("__scope{}", "baseScope"), first_scope.clone())])
-            } else {
-                HashMap::new()
+            let mut base_key = None;
+            let mut next_scopes: HashMap<ScopeKey, ScopeContexts> = HashMap::new();
+
+            for hook in &hooks {
+                for (key, current_scope) in hook(scope.clone()) {
+                    // 第一個 hook 的鍵就是 base scope 的鍵
+                    base_key.get_or_insert(key);
+                    if let Some(current_scope) = current_scope {
+                        next_scopes.extend(current_scope);
+                    }
+                }
+            }
+
+            match base_key {
+                Some(key) => HashMap::from([(key, Some(next_scopes))]),
+                None => HashMap::new(),
             }
         })
     })
 }
 
 // Export types
-pub type CreateScope = fn() -> Box<dyn Fn(Scope) -> HashMap<String, Scope>>;
+pub type CreateScope = fn() -> ScopeHook;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 建立含有 `len` 個 context 的 scope
+    fn scope_with(
+        name: &str,
+        len: usize,
+        deps: Vec<ScopeHookFactory>,
+    ) -> (ScopeKey, ScopeHookFactory) {
+        let (creator, factory) = create_context_scope(name, deps);
+        for index in 0..len {
+            let _ = creator.create::<usize>(&format!("{name}{index}"), None);
+        }
+        (creator.scope_key().clone(), factory)
+    }
+
+    fn scope_len(scope: &Scope, key: &ScopeKey) -> Option<usize> {
+        scope
+            .as_ref()
+            .and_then(|scope| scope.get(key))
+            .map(Vec::len)
+    }
+
+    #[test]
+    fn composes_three_scopes_under_the_base_key() {
+        let (a, scope_a) = scope_with("A", 1, Vec::new());
+        let (b, scope_b) = scope_with("B", 2, Vec::new());
+        let (c, scope_c) = scope_with("C", 3, Vec::new());
+
+        let hook = compose_context_scopes(vec![scope_a, scope_b, scope_c])();
+        let result = hook(None);
+
+        assert_eq!(result.len(), 1);
+        let scope = result
+            .get(&a)
+            .expect("the base key holds the composed scope");
+        assert_eq!(scope_len(scope, &a), Some(1));
+        assert_eq!(scope_len(scope, &b), Some(2));
+        assert_eq!(scope_len(scope, &c), Some(3));
+        for (key, len) in [(&a, 1), (&b, 2), (&c, 3)] {
+            for index in 0..len {
+                assert!(scope_context::<ContextId>(scope, key, index).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn base_key_is_deterministic() {
+        let (a, scope_a) = scope_with("A", 1, Vec::new());
+        let (_, scope_b) = scope_with("B", 1, Vec::new());
+        let (_, scope_c) = scope_with("C", 1, Vec::new());
+        let (_, scope_d) = scope_with("D", 1, Vec::new());
+
+        let composed = compose_context_scopes(vec![scope_a, scope_b, scope_c, scope_d]);
+        for _ in 0..16 {
+            let result = composed()(None);
+            let keys: Vec<&ScopeKey> = result.keys().collect();
+            assert_eq!(keys, vec![&a]);
+        }
+    }
+
+    #[test]
+    fn keeps_the_ids_of_each_scope() {
+        let (a, scope_a) = scope_with("A", 1, Vec::new());
+        let (b, scope_b) = scope_with("B", 1, Vec::new());
+        let (c, scope_c) = scope_with("C", 1, Vec::new());
+
+        let composed = compose_context_scopes(vec![scope_a, scope_b, scope_c]);
+        let hook = composed();
+        let first = hook(None);
+        let second = hook(None);
+        // 另一次調用工廠會產生新的 ContextId，但外部傳入的 scope 優先
+        let nested = composed()(first[&a].clone());
+
+        let id = |result: &HashMap<ScopeKey, Scope>, key: &ScopeKey| {
+            scope_context::<ContextId>(&result[&a], key, 0).copied()
+        };
+        for key in [&a, &b, &c] {
+            assert!(id(&first, key).is_some());
+            assert_eq!(id(&first, key), id(&second, key));
+            assert_eq!(id(&first, key), id(&nested, key));
+        }
+    }
+
+    #[test]
+    fn scope_dependencies_are_composed_under_the_dependent_key() {
+        let (b, scope_b) = scope_with("B", 1, Vec::new());
+        let (c, scope_c) = scope_with("C", 2, Vec::new());
+        let (d, scope_d) = scope_with("D", 1, Vec::new());
+        let (a, scope_a) = scope_with("A", 1, vec![scope_b, scope_c, scope_d]);
+
+        let result = scope_a()(None);
+
+        assert_eq!(result.keys().collect::<Vec<_>>(), vec![&a]);
+        let scope = &result[&a];
+        for (key, len) in [(&a, 1), (&b, 1), (&c, 2), (&d, 1)] {
+            assert_eq!(scope_len(scope, key), Some(len));
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::create_context::create_context;
+use super::error::ContextError;
+use dioxus::prelude::*;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// Like [`create_context`], but values are compared with `eq` instead of
+/// requiring `T: PartialEq`.
+///
+/// Consumers are only notified when `eq(old, new)` returns `false`, so
+/// contexts holding closures or trait objects can still be memoized, e.g. by
+/// comparing an id field or `Rc::ptr_eq`.
+pub fn create_context_with_eq<T: 'static + Clone>(
+    root_component_name: &str,
+    default_context: Option<T>,
+    eq: fn(&T, &T) -> bool,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+) {
+    let (provider, _, inner_try_use_ctx) = create_context(
+        root_component_name,
+        default_context.map(|value| EqBy { value, eq }),
+    );
+    let inner_try_use_ctx = Rc::new(inner_try_use_ctx);
+
+    // Wrapper function for the provider component
+    let provider_fn =
+        move |value: T, children: Element| -> Element { provider(EqBy { value, eq }, children) };
+
+    // Non-panicking consumer hook
+    let try_use_ctx = {
+        let inner_try_use_ctx = inner_try_use_ctx.clone();
+        move |consumer_name: &str| -> Result<T, ContextError> {
+            inner_try_use_ctx(consumer_name).map(|ctx| ctx.value)
+        }
+    };
+
+    // Consumer hook
+    let use_ctx = move |consumer_name: &str| -> T {
+        inner_try_use_ctx(consumer_name)
+            .map(|ctx| ctx.value)
+            .unwrap_or_else(|err| panic!("{}", err))
+    };
+
+    (provider_fn, use_ctx, try_use_ctx)
+}
+
+/// A wrapper whose values always compare equal.
+///
+/// Use it for context values that cannot implement `PartialEq` (callbacks,
+/// trait objects) and that should never by themselves cause consumers to
+/// rerender.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysEqual<T>(pub T);
+
+impl<T> PartialEq for AlwaysEqual<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Deref for AlwaysEqual<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for AlwaysEqual<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for AlwaysEqual<T> {
+    fn from(value: T) -> Self {
+        AlwaysEqual(value)
+    }
+}
+
+// 以自訂的比較函數實作 PartialEq，讓值能放進 Memo 中
+#[derive(Clone)]
+struct EqBy<T> {
+    value: T,
+    eq: fn(&T, &T) -> bool,
+}
+
+impl<T> PartialEq for EqBy<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.eq)(&self.value, &other.value)
+    }
+}
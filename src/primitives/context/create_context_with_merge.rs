@@ -0,0 +1,80 @@
+use super::create_context::{try_use_context_value, use_synced_signal};
+use super::error::ContextError;
+use dioxus::prelude::*;
+
+/// Like [`create_context`](super::create_context::create_context), but a
+/// nested provider combines its value with the nearest ancestor provider's
+/// instead of shadowing it.
+///
+/// `merge(parent, child)` builds the value seen below the nested provider; it
+/// reruns whenever either value changes. A provider without an ancestor
+/// provides its own value unchanged.
+///
+/// ```rust,ignore
+/// // A nested `TooltipProvider` only overrides the delay.
+/// let (tooltip_provider, use_tooltip, _) = create_context_with_merge(
+///     "TooltipProvider",
+///     Some(TooltipConfig::default()),
+///     |parent, child| TooltipConfig { delay_duration: child.delay_duration, ..parent.clone() },
+/// );
+/// ```
+pub fn create_context_with_merge<T: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+    default_context: Option<T>,
+    merge: fn(&T, &T) -> T,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+) {
+    let root_component_name = root_component_name.to_string();
+
+    // Provider component
+    #[component]
+    fn MergeProvider<T: 'static + Clone + PartialEq>(
+        value: T,
+        merge: fn(&T, &T) -> T,
+        children: Element,
+    ) -> Element {
+        // 在提供自己的值之前取得祖先 Provider 的值
+        let parent = use_hook(try_consume_context::<Memo<T>>);
+        let context_signal = use_synced_signal(value);
+
+        let merged_value = use_memo(move || match parent {
+            Some(parent) => merge(&parent.read(), &context_signal.read()),
+            None => context_signal.read().clone(),
+        });
+
+        use_context_provider(|| merged_value);
+
+        rsx! { {children} }
+    }
+
+    // Wrapper function for the provider component
+    let provider_fn = move |value: T, children: Element| -> Element {
+        rsx! {
+            MergeProvider {
+                value: value.clone(),
+                merge: merge,
+                children: children
+            }
+        }
+    };
+
+    // Non-panicking consumer hook
+    let try_use_ctx = move |consumer_name: &str| -> Result<T, ContextError> {
+        try_use_context_value(consumer_name, &root_component_name, || {
+            default_context.clone()
+        })
+    };
+
+    // Consumer hook
+    let use_ctx = {
+        let try_use_ctx = try_use_ctx.clone();
+        move |consumer_name: &str| -> T {
+            try_use_ctx(consumer_name).unwrap_or_else(|err| panic!("{}", err))
+        }
+    };
+
+    (provider_fn, use_ctx, try_use_ctx)
+}
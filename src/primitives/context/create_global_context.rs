@@ -0,0 +1,62 @@
+use dioxus::prelude::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+// 依型別註冊的初始化函數
+fn initializers() -> &'static Mutex<HashMap<TypeId, Box<dyn Any + Send>>> {
+    static INITIALIZERS: OnceLock<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = OnceLock::new();
+    INITIALIZERS.get_or_init(Default::default)
+}
+
+/// Register a context of type `T` that lives at the app root.
+///
+/// No provider has to be rendered: the first [`use_global::<T>`](use_global)
+/// call builds the value with `init` and provides it from the root scope,
+/// where every later call finds it. Call this before launching the app (or
+/// at least before the first `use_global::<T>()`).
+pub fn create_global_context<T: 'static>(init: fn() -> T) {
+    initializers()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(TypeId::of::<T>(), Box::new(init));
+}
+
+/// Read the root context registered with [`create_global_context`].
+///
+/// Panics if no initializer was registered for `T`.
+pub fn use_global<T: 'static>() -> Signal<T> {
+    use_hook(|| {
+        if let Some(GlobalContext(signal)) = try_consume_context::<GlobalContext<T>>() {
+            return signal;
+        }
+
+        let init = initializers()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(&TypeId::of::<T>())
+            .and_then(|init| init.downcast_ref::<fn() -> T>())
+            .copied()
+            .unwrap_or_else(|| {
+                panic!(
+                    "`use_global::<{}>` requires `create_global_context` to be called first",
+                    std::any::type_name::<T>()
+                )
+            });
+
+        // 在 root scope 建立 signal，避免第一個使用者卸載時被回收
+        let signal = Signal::new_in_scope(init(), ScopeId::ROOT);
+        provide_root_context(GlobalContext(signal)).0
+    })
+}
+
+// 包裝 Signal，避免與其他直接提供 Signal<T> 的上下文衝突
+struct GlobalContext<T: 'static>(Signal<T>);
+
+impl<T: 'static> Clone for GlobalContext<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for GlobalContext<T> {}
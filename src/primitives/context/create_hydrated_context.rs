@@ -0,0 +1,87 @@
+//! Contexts whose initial value survives server-side rendering.
+//!
+//! Only compiled with the `fullstack` feature.
+
+use super::create_context::try_use_context_value;
+use super::error::ContextError;
+use dioxus::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Like [`create_context`](super::create_context::create_context), but the
+/// provider's initial value is serialized into the server-rendered page and
+/// hydrated on the client.
+///
+/// Consumers render the server's value during hydration, so there is no
+/// flash of the default state; after that the provider follows its `value`
+/// prop like any other provider.
+pub fn create_hydrated_context<T>(
+    root_component_name: &str,
+    default_context: Option<T>,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> T + 'static,
+    impl Fn(&str) -> Result<T, ContextError> + 'static,
+)
+where
+    T: 'static + Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    let root_component_name = root_component_name.to_string();
+
+    // Provider component
+    #[component]
+    fn HydratedProvider<T: 'static + Clone + PartialEq + Serialize + DeserializeOwned>(
+        value: T,
+        children: Element,
+    ) -> Element {
+        // 伺服器渲染時序列化 value，客戶端 hydration 時讀回相同的值
+        let initial = use_server_cached({
+            let value = value.clone();
+            move || value.clone()
+        });
+        let mut context_signal = use_signal(|| initial);
+
+        // 掛載時的 prop 已被伺服器的值取代，只同步之後的變化
+        let last_value = use_hook(|| Rc::new(RefCell::new(value.clone())));
+        use_effect(use_reactive!(|(value,)| {
+            if *last_value.borrow() != value {
+                last_value.replace(value.clone());
+                context_signal.set(value);
+            }
+        }));
+
+        let memoized_value = use_memo(move || context_signal.read().clone());
+        use_context_provider(|| memoized_value);
+
+        rsx! { {children} }
+    }
+
+    // Wrapper function for the provider component
+    let provider_fn = move |value: T, children: Element| -> Element {
+        rsx! {
+            HydratedProvider {
+                value: value.clone(),
+                children: children
+            }
+        }
+    };
+
+    // Non-panicking consumer hook
+    let try_use_ctx = move |consumer_name: &str| -> Result<T, ContextError> {
+        try_use_context_value(consumer_name, &root_component_name, || {
+            default_context.clone()
+        })
+    };
+
+    // Consumer hook
+    let use_ctx = {
+        let try_use_ctx = try_use_ctx.clone();
+        move |consumer_name: &str| -> T {
+            try_use_ctx(consumer_name).unwrap_or_else(|err| panic!("{}", err))
+        }
+    };
+
+    (provider_fn, use_ctx, try_use_ctx)
+}
@@ -0,0 +1,102 @@
+//! Writable contexts persisted to web storage.
+//!
+//! Only compiled with the `web` feature.
+
+use super::create_writable_context::{WritableContext, create_writable_context};
+use super::error::ContextError;
+use dioxus::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Which web storage area a persistent context is saved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageKind {
+    /// `window.localStorage`, shared across tabs and sessions.
+    #[default]
+    Local,
+    /// `window.sessionStorage`, cleared when the tab closes.
+    Session,
+}
+
+/// Create a writable context whose value is loaded from and saved to web storage.
+///
+/// The provider reads `storage_key` on mount, falling back to its `value`
+/// when nothing (or nothing deserializable) is stored, and writes the value
+/// back as JSON every time a consumer changes it.
+///
+/// Returns `(provider, use_ctx, try_use_ctx)` mirroring
+/// [`create_writable_context`].
+pub fn create_persistent_context<T>(
+    root_component_name: &str,
+    storage_key: &str,
+    storage: StorageKind,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> Signal<T> + 'static,
+    impl Fn(&str) -> Result<Signal<T>, ContextError> + 'static,
+)
+where
+    T: 'static + Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    let storage_key = storage_key.to_string();
+
+    // Provider component
+    #[component]
+    fn PersistentProvider<T: 'static + Clone + PartialEq + Serialize + DeserializeOwned>(
+        value: T,
+        storage_key: String,
+        storage: StorageKind,
+        children: Element,
+    ) -> Element {
+        // 掛載時從 storage 讀取初始值
+        let signal = use_signal(|| load(storage, &storage_key).unwrap_or_else(|| value.clone()));
+
+        // 值改變時寫回 storage
+        use_effect(use_reactive!(|(storage_key,)| {
+            save(storage, &storage_key, &*signal.read());
+        }));
+
+        use_context_provider(|| WritableContext(signal));
+
+        rsx! { {children} }
+    }
+
+    // Wrapper function for the provider component
+    let provider_fn = move |value: T, children: Element| -> Element {
+        rsx! {
+            PersistentProvider {
+                value: value.clone(),
+                storage_key: storage_key.clone(),
+                storage: storage,
+                children: children
+            }
+        }
+    };
+
+    // 使用者讀取的與 create_writable_context 相同
+    let (_, use_ctx, try_use_ctx) = create_writable_context::<T>(root_component_name);
+
+    (provider_fn, use_ctx, try_use_ctx)
+}
+
+pub(crate) fn web_storage(storage: StorageKind) -> Option<web_sys::Storage> {
+    let window = web_sys::window()?;
+    let storage = match storage {
+        StorageKind::Local => window.local_storage(),
+        StorageKind::Session => window.session_storage(),
+    };
+    storage.ok().flatten()
+}
+
+pub(crate) fn load<T: DeserializeOwned>(storage: StorageKind, key: &str) -> Option<T> {
+    let raw = web_storage(storage)?.get_item(key).ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+pub(crate) fn save<T: Serialize>(storage: StorageKind, key: &str, value: &T) {
+    let (Some(storage), Ok(raw)) = (web_storage(storage), serde_json::to_string(value)) else {
+        return;
+    };
+    // storage 已滿或被停用時忽略錯誤
+    let _ = storage.set_item(key, &raw);
+}
@@ -0,0 +1,100 @@
+use super::error::ContextError;
+use dioxus::prelude::*;
+
+/// Create a context whose state is changed by dispatching actions through
+/// `reducer`, instead of writing to a signal ad hoc.
+///
+/// Returns `(provider, use_state, use_dispatch)`:
+///
+/// - `provider(initial_state, children)` owns the state;
+/// - `use_state(consumer_name)` reads the current state and rerenders the
+///   consumer when it changes;
+/// - `use_dispatch(consumer_name)` returns a [`Callback`] whose identity is
+///   stable across renders, so reading it never causes a rerender.
+///
+/// The provider passes `reducer` as a component prop, so actions must be
+/// `Clone + PartialEq` as well.
+pub fn create_reducer_context<S: 'static + Clone + PartialEq, A: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+    reducer: fn(&S, A) -> S,
+) -> (
+    impl Fn(S, Element) -> Element + 'static,
+    impl Fn(&str) -> S + 'static,
+    impl Fn(&str) -> Callback<A> + 'static,
+) {
+    let root_component_name = root_component_name.to_string();
+
+    // Provider component
+    #[component]
+    fn ReducerProvider<S: 'static + Clone + PartialEq, A: 'static + Clone + PartialEq>(
+        initial_state: S,
+        reducer: fn(&S, A) -> S,
+        children: Element,
+    ) -> Element {
+        let mut state = use_signal(|| initial_state.clone());
+
+        // use_callback 會在每次渲染時更新內部閉包，但 Callback 本身保持不變
+        let dispatch = use_callback(move |action: A| {
+            let next = reducer(&state.peek(), action);
+            if *state.peek() != next {
+                state.set(next);
+            }
+        });
+
+        use_context_provider(|| ReducerContext {
+            state: ReadOnlySignal::new(state),
+            dispatch,
+        });
+
+        rsx! { {children} }
+    }
+
+    // Wrapper function for the provider component
+    let provider_fn = move |initial_state: S, children: Element| -> Element {
+        rsx! {
+            ReducerProvider {
+                initial_state: initial_state.clone(),
+                reducer: reducer,
+                children: children
+            }
+        }
+    };
+
+    let use_reducer_context = move |consumer_name: &str| -> ReducerContext<S, A> {
+        try_use_context::<ReducerContext<S, A>>().unwrap_or_else(|| {
+            panic!(
+                "{}",
+                ContextError::MissingProvider {
+                    consumer: consumer_name.to_string(),
+                    root: root_component_name.clone(),
+                }
+            )
+        })
+    };
+
+    // State hook
+    let use_state = {
+        let use_reducer_context = use_reducer_context.clone();
+        move |consumer_name: &str| -> S { use_reducer_context(consumer_name).state.read().clone() }
+    };
+
+    // Dispatch hook
+    let use_dispatch =
+        move |consumer_name: &str| -> Callback<A> { use_reducer_context(consumer_name).dispatch };
+
+    (provider_fn, use_state, use_dispatch)
+}
+
+// Provider 提供的狀態與 dispatch
+struct ReducerContext<S: 'static, A: 'static> {
+    state: ReadOnlySignal<S>,
+    dispatch: Callback<A>,
+}
+
+impl<S: 'static, A: 'static> Clone for ReducerContext<S, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: 'static, A: 'static> Copy for ReducerContext<S, A> {}
@@ -0,0 +1,25 @@
+use super::create_context_with_eq::create_context_with_eq;
+use super::error::ContextError;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Create a context for large values that are shared through an [`Rc`]
+/// instead of being cloned on every read.
+///
+/// `T` needs neither `Clone` nor `PartialEq`: consumers receive the provider's
+/// `Rc<T>`, and are notified only when the provider is given a different `Rc`
+/// (compared with [`Rc::ptr_eq`]). To publish a change, build a new `Rc`.
+pub fn create_shared_context<T: 'static>(
+    root_component_name: &str,
+    default_context: Option<T>,
+) -> (
+    impl Fn(Rc<T>, Element) -> Element + 'static,
+    impl Fn(&str) -> Rc<T> + 'static,
+    impl Fn(&str) -> Result<Rc<T>, ContextError> + 'static,
+) {
+    create_context_with_eq(
+        root_component_name,
+        default_context.map(Rc::new),
+        Rc::ptr_eq,
+    )
+}
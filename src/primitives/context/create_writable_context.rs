@@ -0,0 +1,73 @@
+use super::error::ContextError;
+use dioxus::prelude::*;
+
+/// Create a context whose consumers receive a writable [`Signal<T>`].
+///
+/// The provider seeds the signal with its `value` on first render; after that
+/// the signal is owned by the provider and any consumer may write to it, so
+/// child parts can update shared state without callbacks being drilled down.
+///
+/// Returns `(provider, use_ctx, try_use_ctx)` mirroring
+/// [`create_context`](super::create_context::create_context).
+pub fn create_writable_context<T: 'static + Clone + PartialEq>(
+    root_component_name: &str,
+) -> (
+    impl Fn(T, Element) -> Element + 'static,
+    impl Fn(&str) -> Signal<T> + 'static,
+    impl Fn(&str) -> Result<Signal<T>, ContextError> + 'static,
+) {
+    let root_component_name = root_component_name.to_string();
+
+    // Provider component
+    #[component]
+    fn WritableProvider<T: 'static + Clone + PartialEq>(value: T, children: Element) -> Element {
+        // 只在第一次渲染時使用初始值，之後由 signal 持有狀態
+        let signal = use_signal(|| value.clone());
+
+        use_context_provider(|| WritableContext(signal));
+
+        rsx! { {children} }
+    }
+
+    // Wrapper function for the provider component
+    let provider_fn = move |value: T, children: Element| -> Element {
+        rsx! {
+            WritableProvider {
+                value: value.clone(),
+                children: children
+            }
+        }
+    };
+
+    // Non-panicking consumer hook
+    let try_use_ctx = move |consumer_name: &str| -> Result<Signal<T>, ContextError> {
+        match try_use_context::<WritableContext<T>>() {
+            Some(WritableContext(signal)) => Ok(signal),
+            None => Err(ContextError::MissingProvider {
+                consumer: consumer_name.to_string(),
+                root: root_component_name.clone(),
+            }),
+        }
+    };
+
+    // Consumer hook
+    let use_ctx = {
+        let try_use_ctx = try_use_ctx.clone();
+        move |consumer_name: &str| -> Signal<T> {
+            try_use_ctx(consumer_name).unwrap_or_else(|err| panic!("{}", err))
+        }
+    };
+
+    (provider_fn, use_ctx, try_use_ctx)
+}
+
+// 包裝 Signal，避免與其他直接提供 Signal<T> 的上下文衝突
+pub(crate) struct WritableContext<T: 'static>(pub(crate) Signal<T>);
+
+impl<T: 'static> Clone for WritableContext<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for WritableContext<T> {}
@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors returned by the non-panicking context consumers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextError {
+    /// The consumer was rendered outside of its provider and the context has
+    /// no default value to fall back to.
+    MissingProvider { consumer: String, root: String },
+    /// Several contexts resolved together (see [`use_contexts!`](crate::use_contexts))
+    /// were missing their providers.
+    MissingProviders {
+        consumer: String,
+        roots: Vec<String>,
+    },
+}
+
+impl ContextError {
+    /// Merge several missing provider errors into one diagnostic.
+    ///
+    /// A single error is returned unchanged.
+    pub fn combine(errors: Vec<ContextError>) -> Option<ContextError> {
+        if errors.len() <= 1 {
+            return errors.into_iter().next();
+        }
+
+        let mut consumer = String::new();
+        let mut roots = Vec::new();
+
+        for error in errors {
+            let (error_consumer, error_roots) = match error {
+                ContextError::MissingProvider { consumer, root } => (consumer, vec![root]),
+                ContextError::MissingProviders { consumer, roots } => (consumer, roots),
+            };
+
+            if consumer.is_empty() {
+                consumer = error_consumer;
+            }
+            roots.extend(error_roots);
+        }
+
+        Some(ContextError::MissingProviders { consumer, roots })
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextError::MissingProvider { consumer, root } => {
+                write!(f, "`{}` must be used within `{}`", consumer, root)
+            }
+            ContextError::MissingProviders { consumer, roots } => {
+                let roots = roots
+                    .iter()
+                    .map(|root| format!("`{}`", root))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "`{}` must be used within {}", consumer, roots)
+            }
+        }
+    }
+}
+
+impl Error for ContextError {}
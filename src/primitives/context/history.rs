@@ -0,0 +1,95 @@
+//! Value history of provided contexts.
+//!
+//! Only compiled with the `debug` feature. Every provider created through
+//! [`create_context`](super::create_context::create_context) records its
+//! values into a bounded history that descendants can read and replay, which
+//! makes it possible to step through state-machine transitions of overlays.
+
+use dioxus::prelude::*;
+use std::collections::VecDeque;
+
+/// How many values are kept per provider.
+pub const HISTORY_LIMIT: usize = 100;
+
+/// The recorded values of the nearest provider for `T`.
+pub struct ContextHistory<T: 'static> {
+    entries: Signal<VecDeque<T>>,
+    value: Signal<T>,
+    replaying: CopyValue<bool>,
+}
+
+impl<T: 'static + Clone + PartialEq> ContextHistory<T> {
+    /// The recorded values, oldest first.
+    pub fn entries(&self) -> Vec<T> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    /// Provide the recorded value at `index` again, without recording it as
+    /// a new transition. The provider's next `value` change overrides it.
+    ///
+    /// Returns `false` if `index` is out of range.
+    pub fn replay(&self, index: usize) -> bool {
+        let Some(entry) = self.entries.peek().get(index).cloned() else {
+            return false;
+        };
+
+        let mut value = self.value;
+        let mut replaying = self.replaying;
+        replaying.set(true);
+        value.set(entry);
+        true
+    }
+}
+
+impl<T: 'static> Clone for ContextHistory<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for ContextHistory<T> {}
+
+/// Read the history of the nearest provider for `T`.
+///
+/// Panics if no provider for `T` exists above the calling component.
+pub fn use_context_history<T: 'static + Clone + PartialEq>() -> ContextHistory<T> {
+    use_context::<ContextHistory<T>>()
+}
+
+// 在 Provider 中記錄 signal 的每一次變化
+pub(crate) fn use_record_history<T: 'static + Clone + PartialEq>(value: Signal<T>) {
+    let mut entries = use_signal(VecDeque::new);
+    let mut replaying = use_hook(|| CopyValue::new(false));
+
+    use_effect(move || {
+        let next = value.read().clone();
+
+        // replay 設定的值不算新的轉換
+        if *replaying.peek() {
+            replaying.set(false);
+            return;
+        }
+
+        let mut entries = entries.write();
+        if entries.back() != Some(&next) {
+            if entries.len() == HISTORY_LIMIT {
+                entries.pop_front();
+            }
+            entries.push_back(next);
+        }
+    });
+
+    use_context_provider(|| ContextHistory {
+        entries,
+        value,
+        replaying,
+    });
+}
@@ -5,3 +5,25 @@
 //! pass props down manually at every level.
 
 pub mod create_context;
+pub mod create_writable_context;
+pub mod error;
+pub mod use_context_selector;
+pub mod create_context_with_eq;
+pub mod create_reducer_context;
+#[cfg(feature = "debug")]
+pub mod registry;
+pub mod async_provider;
+pub mod use_context_effect;
+pub mod context_bridge;
+pub mod use_contexts;
+#[cfg(feature = "web")]
+pub mod create_persistent_context;
+pub mod create_global_context;
+pub mod use_context_memo;
+pub mod create_shared_context;
+#[cfg(feature = "debug")]
+pub mod history;
+pub mod use_optional_context;
+pub mod create_context_with_merge;
+#[cfg(feature = "fullstack")]
+pub mod create_hydrated_context;
@@ -0,0 +1,170 @@
+//! Dev-mode registry of created contexts.
+//!
+//! Only compiled with the `debug` feature. Every context created through
+//! [`create_context`](super::create_context::create_context) registers itself
+//! here, and its providers record mounts and value updates, so
+//! [`ContextDevtools`] can show which providers are live when debugging
+//! "must be used within" panics or stale values.
+
+use dioxus::prelude::*;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// A snapshot of one registered context.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextRecord {
+    /// The root component name passed to `create_context`.
+    pub name: String,
+    /// The Rust type of the context value.
+    pub type_name: &'static str,
+    /// How many providers for this context are currently mounted.
+    pub mount_count: usize,
+    /// When a provider last published a value, in milliseconds since the Unix epoch.
+    pub last_update: Option<f64>,
+}
+
+// 以名稱與值的型別為鍵，同名但型別不同的 context 各自記錄
+type RecordKey = (String, TypeId);
+
+thread_local! {
+    static REGISTRY: RefCell<BTreeMap<RecordKey, ContextRecord>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+// 每次記錄變化時遞增，讓 ContextDevtools 重新渲染
+static REVISION: GlobalSignal<u64> = Signal::global(|| 0);
+
+/// All registered contexts, ordered by name.
+///
+/// Contexts sharing a root name but not a value type are listed separately.
+pub fn registered_contexts() -> Vec<ContextRecord> {
+    REGISTRY.with(|registry| registry.borrow().values().cloned().collect())
+}
+
+pub(crate) fn register_context<T: 'static>(name: &str) {
+    REGISTRY.with(|registry| {
+        registry
+            .borrow_mut()
+            .entry((name.to_string(), TypeId::of::<T>()))
+            .or_insert_with(|| ContextRecord {
+                name: name.to_string(),
+                type_name: std::any::type_name::<T>(),
+                mount_count: 0,
+                last_update: None,
+            });
+    });
+}
+
+// 沒有指定名稱的 Provider 使用為 T 註冊的第一個名稱
+fn name_for<T: 'static>() -> String {
+    let type_id = TypeId::of::<T>();
+    REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .keys()
+            .find(|(_, other)| *other == type_id)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| std::any::type_name::<T>().to_string())
+    })
+}
+
+// 在 Provider 中記錄掛載、更新與卸載
+pub(crate) fn use_context_record<T: 'static + Clone + PartialEq>(name: &str, value: Memo<T>) {
+    let name = use_hook(|| {
+        let name = if name.is_empty() {
+            name_for::<T>()
+        } else {
+            name.to_string()
+        };
+        register_context::<T>(&name);
+        Rc::new(name)
+    });
+
+    use_effect({
+        let name = name.clone();
+        let mut mounted = false;
+        move || {
+            // 訂閱值的變化
+            value.read();
+
+            update_record::<T>(&name, |record| {
+                if !mounted {
+                    record.mount_count += 1;
+                }
+                record.last_update = Some(now_ms());
+            });
+            mounted = true;
+        }
+    });
+
+    use_drop(move || {
+        update_record::<T>(&name, |record| {
+            record.mount_count = record.mount_count.saturating_sub(1);
+        });
+    });
+}
+
+fn update_record<T: 'static>(name: &str, update: impl FnOnce(&mut ContextRecord)) {
+    REGISTRY.with(|registry| {
+        let key = (name.to_string(), TypeId::of::<T>());
+        if let Some(record) = registry.borrow_mut().get_mut(&key) {
+            update(record);
+        }
+    });
+    *REVISION.write() += 1;
+}
+
+fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .unwrap_or_default()
+    }
+}
+
+/// Renders the context registry as a table.
+#[component]
+pub fn ContextDevtools() -> Element {
+    // 訂閱註冊表的變化
+    REVISION.read();
+    let records = registered_contexts();
+
+    rsx! {
+        table { "data-primitive-devtools": "",
+            thead {
+                tr {
+                    th { "Context" }
+                    th { "Type" }
+                    th { "Mounted providers" }
+                    th { "Last update" }
+                }
+            }
+            tbody {
+                for record in records {
+                    tr {
+                        key: "{record.name}-{record.type_name}",
+                        "data-mounted": record.mount_count > 0,
+                        td { "{record.name}" }
+                        td { "{record.type_name}" }
+                        td { "{record.mount_count}" }
+                        td {
+                            {
+                                record
+                                    .last_update
+                                    .map(|timestamp| format!("{timestamp:.0}"))
+                                    .unwrap_or_else(|| "never".to_string())
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
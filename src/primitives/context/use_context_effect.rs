@@ -0,0 +1,31 @@
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Run `on_change(old, new)` whenever the context provided by
+/// [`create_context`](super::create_context::create_context) changes.
+///
+/// The subscription lives in an effect, so the calling component itself is
+/// not rerendered by context changes. The callback does not fire for the
+/// initial value.
+///
+/// Panics if no provider for `T` exists above the calling component.
+pub fn use_context_effect<T: 'static + Clone + PartialEq>(
+    mut on_change: impl FnMut(&T, &T) + 'static,
+) {
+    let context = use_context::<Memo<T>>();
+
+    // 保存上一次的值，用來與新值比較
+    let previous = use_hook(|| Rc::new(RefCell::new(None::<T>)));
+
+    use_effect(move || {
+        let next = context.read().clone();
+        let old = previous.borrow_mut().replace(next.clone());
+
+        if let Some(old) = old
+            && old != next
+        {
+            on_change(&old, &next);
+        }
+    });
+}
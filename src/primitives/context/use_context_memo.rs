@@ -0,0 +1,38 @@
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Build a context value that is only recreated when `deps` change, like
+/// React's `useMemo` with a dependency array.
+///
+/// Use it for provided values that are structs of callbacks: rebuilding the
+/// struct on every render would make the provider see a new value each time,
+/// while with explicit dependencies consumers are only notified when something
+/// they care about actually changed.
+///
+/// ```rust,ignore
+/// let value = use_context_memo((open(), disabled), move || MenuContext {
+///     open: open(),
+///     disabled,
+///     on_open_change: AlwaysEqual(on_open_change),
+/// });
+///
+/// rsx! { {menu_provider(value, children)} }
+/// ```
+pub fn use_context_memo<D, T>(deps: D, value_fn: impl FnOnce() -> T) -> T
+where
+    D: 'static + PartialEq,
+    T: 'static + Clone,
+{
+    let cache = use_hook(|| Rc::new(RefCell::new(None::<(D, T)>)));
+    let mut cache = cache.borrow_mut();
+
+    match &*cache {
+        Some((cached_deps, value)) if *cached_deps == deps => value.clone(),
+        _ => {
+            let value = value_fn();
+            *cache = Some((deps, value.clone()));
+            value
+        }
+    }
+}
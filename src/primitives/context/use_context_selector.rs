@@ -0,0 +1,21 @@
+use dioxus::prelude::*;
+
+/// Consume a slice of a context provided by [`create_context`](super::create_context::create_context).
+///
+/// The selector runs inside a memo that is subscribed to the whole context,
+/// but the calling component only subscribes to the selected value, so it
+/// rerenders only when the slice changes (compared with `PartialEq`).
+///
+/// Panics if no provider for `T` exists above the calling component.
+pub fn use_context_selector<T, U>(selector: impl Fn(&T) -> U + 'static) -> U
+where
+    T: 'static + Clone + PartialEq,
+    U: 'static + Clone + PartialEq,
+{
+    let context = use_context::<Memo<T>>();
+
+    // 只有選取的值改變時才會通知訂閱者
+    let selected = use_memo(move || selector(&context.read()));
+
+    selected.read().clone()
+}
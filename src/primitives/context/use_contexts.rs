@@ -0,0 +1,67 @@
+use super::error::ContextError;
+
+/// A tuple of context lookups that can be resolved together.
+///
+/// Implemented for tuples of up to eight `Result<T, ContextError>`; resolving
+/// fails with one [`ContextError`] listing every missing provider instead of
+/// stopping at the first one.
+pub trait ResolveContexts {
+    type Output;
+
+    fn resolve(self) -> Result<Self::Output, ContextError>;
+}
+
+macro_rules! impl_resolve_contexts {
+    ($($name:ident),+) => {
+        impl<$($name),+> ResolveContexts for ($(Result<$name, ContextError>,)+) {
+            type Output = ($($name,)+);
+
+            #[allow(non_snake_case)]
+            fn resolve(self) -> Result<Self::Output, ContextError> {
+                match self {
+                    ($(Ok($name),)+) => Ok(($($name,)+)),
+                    ($($name,)+) => {
+                        let errors = [$($name.err()),+].into_iter().flatten().collect();
+                        Err(ContextError::combine(errors).expect("at least one context is missing"))
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_resolve_contexts!(A);
+impl_resolve_contexts!(A, B);
+impl_resolve_contexts!(A, B, C);
+impl_resolve_contexts!(A, B, C, D);
+impl_resolve_contexts!(A, B, C, D, E);
+impl_resolve_contexts!(A, B, C, D, E, F);
+impl_resolve_contexts!(A, B, C, D, E, F, G);
+impl_resolve_contexts!(A, B, C, D, E, F, G, H);
+
+/// Resolve several contexts at once from their `try_use_ctx` hooks.
+///
+/// Panics with a single message listing every missing provider.
+///
+/// ```rust,ignore
+/// let (open, value, dir) = use_contexts!("SelectItem"; try_use_open, try_use_value, try_use_dir);
+/// ```
+#[macro_export]
+macro_rules! use_contexts {
+    ($consumer:expr; $($try_use_ctx:expr),+ $(,)?) => {
+        $crate::try_use_contexts!($consumer; $($try_use_ctx),+)
+            .unwrap_or_else(|err| panic!("{}", err))
+    };
+}
+
+/// Like [`use_contexts!`](crate::use_contexts), but returns the combined
+/// [`ContextError`] instead of panicking.
+#[macro_export]
+macro_rules! try_use_contexts {
+    ($consumer:expr; $($try_use_ctx:expr),+ $(,)?) => {{
+        let consumer_name: &str = $consumer;
+        $crate::primitives::context::use_contexts::ResolveContexts::resolve((
+            $(($try_use_ctx)(consumer_name),)+
+        ))
+    }};
+}
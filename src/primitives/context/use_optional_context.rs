@@ -0,0 +1,12 @@
+use dioxus::prelude::*;
+
+/// Read the value of type `T` provided by
+/// [`create_context`](super::create_context::create_context), or `None` when
+/// no such provider exists above the caller.
+///
+/// Unlike the consumer hooks returned by `create_context`, this never panics
+/// and ignores the context's default, so a component can adapt to being used
+/// standalone (e.g. a `Label` outside of a `Form`).
+pub fn use_optional_context<T: 'static + Clone + PartialEq>() -> Option<T> {
+    try_use_context::<Memo<T>>().map(|context| context.read().clone())
+}
@@ -0,0 +1,595 @@
+//! DataTable Primitive
+//!
+//! A headless `table` with the WAI-ARIA grid pattern: sortable columns, row
+//! selection and keyboard navigation between cells. The table keeps the
+//! sort and selection state; sorting and rendering the rows is up to you.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     DataTable { selection_mode: SelectionMode::Multiple, on_sort_change: move |sort| order.set(sort),
+//!         DataTableHeader {
+//!             DataTableColumn { column: "select", DataTableSelectAllCheckbox {} }
+//!             DataTableColumn { column: "name", allows_sorting: true, "Name" }
+//!         }
+//!         DataTableBody {
+//!             for user in sorted_users() {
+//!                 DataTableRow { key: "{user.id}", row_id: user.id.clone(),
+//!                     DataTableCell { DataTableSelectionCheckbox {} }
+//!                     DataTableCell { "{user.name}" }
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The arrow keys move between cells, Home and End to the ends of the row,
+//! Ctrl+Home and Ctrl+End to the first and last cell, and Space selects the
+//! focused row.
+
+use super::checkbox::{Checkbox, CheckedState};
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::listbox::SelectionMode;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The direction a column is sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The value of the `aria-sort` and `data-sort` attributes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        }
+    }
+}
+
+/// The column a [`DataTable`] is sorted by, and in which direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortDescriptor {
+    pub column: String,
+    pub direction: SortDirection,
+}
+
+#[derive(Clone)]
+struct GridRow {
+    id: usize,
+    // 標題列沒有 key，不能被選取
+    key: Option<String>,
+    disabled: bool,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Clone)]
+struct GridCell {
+    id: usize,
+    row: usize,
+    node: Rc<MountedData>,
+}
+
+#[derive(Clone, Copy)]
+struct DataTableContext {
+    sort: Memo<Option<SortDescriptor>>,
+    set_sort: Callback<Option<SortDescriptor>>,
+    selection_mode: Signal<Option<SelectionMode>>,
+    selected: Memo<Vec<String>>,
+    set_selected: Callback<Vec<String>>,
+    rows: Signal<Vec<GridRow>>,
+    cells: Signal<Vec<GridCell>>,
+    // 目前的 Tab 停駐格
+    focused: Signal<Option<usize>>,
+}
+
+impl DataTableContext {
+    fn toggle_row(&self, key: &str) {
+        let Some(mode) = *self.selection_mode.peek() else {
+            return;
+        };
+        let mut selected = self.selected.peek().clone();
+        let was_selected = selected.iter().any(|other| other == key);
+        match mode {
+            SelectionMode::Single if was_selected => selected.clear(),
+            SelectionMode::Single => selected = vec![key.to_string()],
+            SelectionMode::Multiple if was_selected => selected.retain(|other| other != key),
+            SelectionMode::Multiple => selected.push(key.to_string()),
+        }
+        self.set_selected.call(selected);
+    }
+
+    fn selectable_keys(&self) -> Vec<String> {
+        self.rows
+            .read()
+            .iter()
+            .filter(|row| !row.disabled)
+            .filter_map(|row| row.key.clone())
+            .collect()
+    }
+
+    // 依文件順序排列的列，以及每一列依序的格子
+    fn grid(&self) -> Vec<Vec<GridCell>> {
+        let rows: Vec<GridRow> = self.rows.peek().clone();
+        let cells: Vec<GridCell> = self.cells.peek().clone();
+        #[cfg(feature = "web")]
+        let (rows, cells) = {
+            use super::dom::document_order;
+            let (mut rows, mut cells) = (rows, cells);
+            rows.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            cells.sort_by(|a, b| document_order(&a.node, &b.node));
+            (rows, cells)
+        };
+        rows.iter()
+            .map(|row| {
+                cells
+                    .iter()
+                    .filter(|cell| cell.row == row.id)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect()
+    }
+
+    fn first_cell(&self) -> Option<usize> {
+        self.grid()
+            .first()
+            .and_then(|cells| cells.first())
+            .map(|cell| cell.id)
+    }
+
+    fn navigate(&self, from: usize, event: &KeyboardEvent) -> bool {
+        let grid = self.grid();
+        let Some((row, column)) = grid.iter().enumerate().find_map(|(row, cells)| {
+            cells
+                .iter()
+                .position(|cell| cell.id == from)
+                .map(|column| (row, column))
+        }) else {
+            return false;
+        };
+        let ctrl = event.modifiers().ctrl() || event.modifiers().meta();
+        let last_row = grid.len() - 1;
+        let (row, column) = match event.key() {
+            Key::ArrowRight => (row, column + 1),
+            Key::ArrowLeft if column > 0 => (row, column - 1),
+            Key::ArrowDown => (row + 1, column),
+            Key::ArrowUp if row > 0 => (row - 1, column),
+            Key::Home if ctrl => (0, 0),
+            Key::End if ctrl => (last_row, usize::MAX),
+            Key::Home => (row, 0),
+            Key::End => (row, usize::MAX),
+            Key::ArrowLeft | Key::ArrowUp => return true,
+            _ => return false,
+        };
+        let Some(cells) = grid.get(row.min(last_row)) else {
+            return true;
+        };
+        // 列的格子數不同時停在最後一格
+        if let Some(cell) = cells.get(column.min(cells.len() - 1)) {
+            let node = cell.node.clone();
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        }
+        true
+    }
+}
+
+/// The root of a data table, owning the sort and selection state.
+///
+/// Pass `sort` and `on_sort_change` to control the sort, or `default_sort`
+/// to let the table manage it; the same goes for `selected`. Rows are only
+/// selectable with a `selection_mode`.
+#[component]
+pub fn DataTable(
+    sort: Option<Option<SortDescriptor>>,
+    #[props(default)] default_sort: Option<SortDescriptor>,
+    on_sort_change: Option<EventHandler<Option<SortDescriptor>>>,
+    selection_mode: Option<SelectionMode>,
+    /// The `row_id`s of the selected rows.
+    selected: Option<Vec<String>>,
+    #[props(default)] default_selected: Vec<String>,
+    on_selection_change: Option<EventHandler<Vec<String>>>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (sort, set_sort) = use_controllable_state(sort, || default_sort, on_sort_change);
+    let (selected, set_selected) =
+        use_controllable_state(selected, || default_selected, on_selection_change);
+    let selection_mode_signal = use_synced_signal(selection_mode);
+    use_context_provider(|| DataTableContext {
+        sort,
+        set_sort,
+        selection_mode: selection_mode_signal,
+        selected,
+        set_selected,
+        rows: Signal::new(Vec::new()),
+        cells: Signal::new(Vec::new()),
+        focused: Signal::new(None),
+    });
+
+    let own_attributes = vec![
+        Attribute::new("role", "grid", None, false),
+        Attribute::new(
+            "aria-multiselectable",
+            (selection_mode == Some(SelectionMode::Multiple)).then_some("true"),
+            None,
+            false,
+        ),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        table {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RowContext {
+    id: usize,
+    key: Signal<Option<String>>,
+}
+
+fn use_grid_row(key: Option<String>, disabled: bool) -> Signal<Option<Rc<MountedData>>> {
+    let mut ctx = use_context::<DataTableContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let node = use_signal(|| None);
+    let key = use_synced_signal(key);
+
+    use_effect(move || {
+        let row = GridRow {
+            id,
+            key: key(),
+            disabled,
+            node: node(),
+        };
+        let mut rows = ctx.rows.write();
+        match rows.iter_mut().find(|other| other.id == id) {
+            Some(existing) => *existing = row,
+            None => rows.push(row),
+        }
+    });
+    use_drop(move || {
+        ctx.rows.write().retain(|row| row.id != id);
+    });
+
+    use_context_provider(|| RowContext { id, key });
+    node
+}
+
+/// The header row, rendered as a `thead` with one `tr`. Place the
+/// [`DataTableColumn`]s inside.
+#[component]
+pub fn DataTableHeader(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut node = use_grid_row(None, false);
+
+    rsx! {
+        thead {
+            role: "rowgroup",
+            ..attributes,
+            tr {
+                role: "row",
+                onmounted: move |event: MountedEvent| node.set(Some(event.data())),
+                {children}
+            }
+        }
+    }
+}
+
+/// The body of the table, rendered as a `tbody`.
+#[component]
+pub fn DataTableBody(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        tbody {
+            role: "rowgroup",
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 格子共用的焦點處理：登記位置、roving tabindex 與方向鍵
+fn use_grid_cell() -> (usize, Vec<Attribute>) {
+    let mut ctx = use_context::<DataTableContext>();
+    let row = use_context::<RowContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    use_drop(move || {
+        ctx.cells.write().retain(|cell| cell.id != id);
+        if *ctx.focused.peek() == Some(id) {
+            ctx.focused.set(None);
+        }
+    });
+
+    // 沒有聚焦過的格子時，第一格是 Tab 停駐點
+    let _ = (ctx.rows.read(), ctx.cells.read());
+    let tab_stop = match (ctx.focused)() {
+        Some(focused) => focused == id,
+        None => ctx.first_cell() == Some(id),
+    };
+
+    let attributes = vec![
+        Attribute::new("tabindex", if tab_stop { "0" } else { "-1" }, None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            let mut cells = ctx.cells.write();
+            cells.retain(|cell| cell.id != id);
+            cells.push(GridCell {
+                id,
+                row: row.id,
+                node: event.data(),
+            });
+        }),
+        dioxus_elements::events::onfocus(move |_| {
+            if *ctx.focused.peek() != Some(id) {
+                ctx.focused.set(Some(id));
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if ctx.navigate(id, &event) {
+                event.prevent_default();
+                return;
+            }
+            if event.key() == Key::Character(" ".into()) {
+                if let Some(key) = row.key.peek().clone() {
+                    event.prevent_default();
+                    ctx.toggle_row(&key);
+                }
+            }
+        }),
+    ];
+    (id, attributes)
+}
+
+/// A column header, rendered as a `th`.
+///
+/// With `allows_sorting` pressing it sorts the table by `column`,
+/// ascending first and then toggling the direction.
+#[component]
+pub fn DataTableColumn(
+    /// Identifies the column in [`SortDescriptor::column`].
+    column: String,
+    #[props(default)] allows_sorting: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DataTableContext>();
+    let (_, cell_attributes) = use_grid_cell();
+    let direction = (ctx.sort)()
+        .filter(|sort| sort.column == column)
+        .map(|sort| sort.direction);
+
+    let toggle = {
+        let column = column.clone();
+        move || {
+            if !allows_sorting {
+                return;
+            }
+            let direction = match direction {
+                Some(SortDirection::Ascending) => SortDirection::Descending,
+                _ => SortDirection::Ascending,
+            };
+            ctx.set_sort.call(Some(SortDescriptor {
+                column: column.clone(),
+                direction,
+            }));
+        }
+    };
+
+    let mut own_attributes = vec![
+        Attribute::new("role", "columnheader", None, false),
+        Attribute::new(
+            "aria-sort",
+            allows_sorting.then(|| direction.map_or("none", |direction| direction.as_str())),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "data-sort",
+            direction.map(|direction| direction.as_str()),
+            None,
+            false,
+        ),
+        Attribute::new("data-sortable", allows_sorting.then_some(""), None, false),
+        dioxus_elements::events::onclick({
+            let toggle = toggle.clone();
+            move |_| toggle()
+        }),
+        dioxus_elements::events::onkeydown({
+            move |event: KeyboardEvent| {
+                if event.key() == Key::Enter {
+                    event.prevent_default();
+                    toggle();
+                }
+            }
+        }),
+    ];
+    own_attributes.extend(cell_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        th {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A row of data, rendered as a `tr`. Clicking it selects it.
+#[component]
+pub fn DataTableRow(
+    /// Identifies the row in the selection.
+    row_id: String,
+    #[props(default)] disabled: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DataTableContext>();
+    let mut node = use_grid_row(Some(row_id.clone()), disabled);
+    let selectable = (ctx.selection_mode)().is_some() && !disabled;
+    let selected = ctx.selected.read().contains(&row_id);
+
+    let own_attributes = vec![
+        Attribute::new("role", "row", None, false),
+        Attribute::new(
+            "aria-selected",
+            (ctx.selection_mode)().is_some().then_some(selected),
+            None,
+            false,
+        ),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("data-state", selected.then_some("selected"), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onclick(move |_| {
+            if selectable {
+                ctx.toggle_row(&row_id);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        tr {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A cell of a [`DataTableRow`], rendered as a `td`.
+#[component]
+pub fn DataTableCell(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (_, cell_attributes) = use_grid_cell();
+    let mut own_attributes = vec![Attribute::new("role", "gridcell", None, false)];
+    own_attributes.extend(cell_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        td {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 勾選框由格子負責聚焦；自己處理點擊，不讓事件冒泡到列上再切換一次
+fn checkbox_attributes(label: &'static str) -> Vec<Attribute> {
+    vec![
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new("aria-label", label, None, false),
+        dioxus_elements::events::onclick(move |event: MouseEvent| event.stop_propagation()),
+    ]
+}
+
+/// A [`Checkbox`] that selects the row it is in.
+#[component]
+pub fn DataTableSelectionCheckbox(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DataTableContext>();
+    let row = use_context::<RowContext>();
+    let Some(key) = (row.key)() else {
+        return rsx! {};
+    };
+    let selected = ctx.selected.read().contains(&key);
+    let disabled = ctx
+        .rows
+        .read()
+        .iter()
+        .any(|row| row.key.as_ref() == Some(&key) && row.disabled);
+
+    let attributes = merge_attributes(checkbox_attributes("Select row"), attributes);
+
+    rsx! {
+        Checkbox {
+            checked: CheckedState::from(selected),
+            on_checked_change: move |_| ctx.toggle_row(&key),
+            disabled: disabled || (ctx.selection_mode)().is_none(),
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A [`Checkbox`] for the header that selects or clears every row. It is
+/// indeterminate while only some rows are selected, and only enabled with
+/// [`SelectionMode::Multiple`].
+#[component]
+pub fn DataTableSelectAllCheckbox(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DataTableContext>();
+    let keys = ctx.selectable_keys();
+    let selected = ctx.selected.read();
+    let count = keys.iter().filter(|key| selected.contains(key)).count();
+    drop(selected);
+    let state = match count {
+        0 => CheckedState::Unchecked,
+        count if count == keys.len() => CheckedState::Checked,
+        _ => CheckedState::Indeterminate,
+    };
+
+    let attributes = merge_attributes(checkbox_attributes("Select all rows"), attributes);
+
+    rsx! {
+        Checkbox {
+            checked: state,
+            on_checked_change: move |_| {
+                let keys = ctx.selectable_keys();
+                let next = if state == CheckedState::Checked { Vec::new() } else { keys };
+                ctx.set_selected.call(next);
+            },
+            disabled: (ctx.selection_mode)() != Some(SelectionMode::Multiple),
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,1010 @@
+//! DateRangePicker Primitive
+//!
+//! A field with segments for the start and end of a range of days, and a
+//! calendar in a floating panel to pick them. The first day chosen in the
+//! calendar starts the range and the second ends it; in between, the range
+//! up to the hovered or focused day is previewed.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     DateRangePicker { value: stay(), on_value_change: move |range| stay.set(range),
+//!         DateRangePickerField {
+//!             DateRangePickerTrigger { "Pick dates" }
+//!         }
+//!         DateRangePickerContent {
+//!             DateRangePickerCalendar {}
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Each segment takes digits, and ArrowUp and ArrowDown step its value;
+//! ArrowLeft and ArrowRight move between segments. In the calendar, the arrow
+//! keys move between days, PageUp and PageDown between months (years with
+//! Shift), and Enter or Space picks the focused day. Day cells expose
+//! `data-range-start`, `data-range-middle` and `data-range-end` for the
+//! selected range and for the preview, which also sets `data-preview`.
+
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::focus_guards::use_focus_guards;
+use super::focus_scope::{AutoFocusEvent, FocusScope};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, PositionOptions, Side};
+use super::presence::Presence;
+use super::segment::{SegmentFocus, step, type_digit, use_segment_focus};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::fmt;
+use std::rc::Rc;
+
+/// A day of the Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// # Panics
+    ///
+    /// Panics if the day does not exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        assert!(
+            (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day),
+            "invalid date {year}-{month}-{day}"
+        );
+        Date { year, month, day }
+    }
+
+    /// The day of the week, from 0 for Sunday to 6 for Saturday.
+    pub fn weekday(&self) -> u8 {
+        // 1970-01-01 是星期四
+        (self.to_days() + 4).rem_euclid(7) as u8
+    }
+
+    /// The day `days` days later, or earlier for a negative count.
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// The same day `months` months later, or earlier for a negative count.
+    /// Days past the end of a shorter month become its last day.
+    pub fn add_months(&self, months: i32) -> Date {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u8 + 1;
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    // 自 1970-01-01 起的天數
+    fn to_days(self) -> i64 {
+        let month = self.month as i64;
+        let year = self.year as i64 - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        // 月份從三月起算，讓閏日落在一年的最後
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+        let month = ((shifted_month + 2) % 12 + 1) as u8;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Date { year, month, day }
+    }
+
+    // 使用者所在時區的今天；非 web 平台以 UTC 計算
+    fn today() -> Date {
+        #[cfg(feature = "web")]
+        {
+            let now = js_sys::Date::new_0();
+            Date::new(
+                now.get_full_year() as i32,
+                now.get_month() as u8 + 1,
+                now.get_date() as u8,
+            )
+        }
+        #[cfg(not(feature = "web"))]
+        {
+            let seconds = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs());
+            Date::from_days((seconds / 86_400) as i64)
+        }
+    }
+
+    fn label(&self) -> String {
+        format!(
+            "{} {}, {}",
+            MONTH_NAMES[self.month as usize - 1],
+            self.day,
+            self.year
+        )
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The number of days in `month` of `year`.
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The days from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    pub start: Date,
+    pub end: Date,
+}
+
+impl DateRange {
+    /// The range between two days given in either order.
+    pub fn new(a: Date, b: Date) -> Self {
+        DateRange {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    pub fn contains(&self, date: Date) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+fn state_str(open: bool) -> &'static str {
+    if open { "open" } else { "closed" }
+}
+
+#[derive(Clone, Copy)]
+struct DateRangePickerContext {
+    value: Memo<Option<DateRange>>,
+    set_value: Callback<Option<DateRange>>,
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    min: Signal<Option<Date>>,
+    max: Signal<Option<Date>>,
+    disabled: Signal<bool>,
+    read_only: Signal<bool>,
+    // 在日曆中選了起點、還沒選終點時的起點
+    pending_start: Signal<Option<Date>>,
+    // 指標或焦點所在的日期，進行中的範圍預覽到這一天
+    hovered: Signal<Option<Date>>,
+    // 日曆中可聚焦的日期，也決定顯示的月份
+    focused: Signal<Date>,
+    // 打開日曆或以鍵盤移動後，由 focused 的格子取得焦點
+    focus_requested: Signal<bool>,
+    trigger: Signal<Option<Rc<MountedData>>>,
+    content_id: Signal<String>,
+}
+
+impl DateRangePickerContext {
+    fn is_unavailable(&self, date: Date) -> bool {
+        (self.min)().is_some_and(|min| date < min) || (self.max)().is_some_and(|max| date > max)
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.min.peek().map_or(date, |min| date.max(min));
+        self.max.peek().map_or(date, |max| date.min(max))
+    }
+
+    /// The range to highlight: the preview while a range is being picked,
+    /// otherwise the value.
+    fn shown_range(&self) -> Option<DateRange> {
+        match (self.pending_start)() {
+            Some(start) => Some(DateRange::new(start, (self.hovered)().unwrap_or(start))),
+            None => (self.value)(),
+        }
+    }
+
+    fn pick(&mut self, date: Date) {
+        if *self.disabled.peek() || *self.read_only.peek() || self.is_unavailable(date) {
+            return;
+        }
+        let pending_start = *self.pending_start.peek();
+        match pending_start {
+            None => self.pending_start.set(Some(date)),
+            Some(start) => {
+                self.pending_start.set(None);
+                self.set_value.call(Some(DateRange::new(start, date)));
+                self.set_open.call(false);
+            }
+        }
+    }
+
+    fn move_focus(&mut self, date: Date) {
+        self.focused.set(self.clamp(date));
+        self.focus_requested.set(true);
+    }
+}
+
+/// The root of a date range picker, owning the selected range and whether
+/// the calendar is open. Each can be controlled with its prop and change
+/// handler, or left to the picker.
+#[component]
+pub fn DateRangePicker(
+    value: Option<Option<DateRange>>,
+    #[props(default)] default_value: Option<DateRange>,
+    on_value_change: Option<EventHandler<Option<DateRange>>>,
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// The earliest day that can be part of the range.
+    min: Option<Date>,
+    /// The latest day that can be part of the range.
+    max: Option<Date>,
+    #[props(default)] disabled: bool,
+    #[props(default)] read_only: bool,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let min = use_synced_signal(min);
+    let max = use_synced_signal(max);
+    let disabled = use_synced_signal(disabled);
+    let read_only = use_synced_signal(read_only);
+    let content_id = use_id();
+    let mut ctx = use_context_provider(|| DateRangePickerContext {
+        value,
+        set_value,
+        open,
+        set_open,
+        min,
+        max,
+        disabled,
+        read_only,
+        pending_start: Signal::new(None),
+        hovered: Signal::new(None),
+        focused: Signal::new(Date::today()),
+        focus_requested: Signal::new(false),
+        trigger: Signal::new(None),
+        content_id: Signal::new(content_id),
+    });
+
+    // 打開時從範圍的起點或今天開始，並捨棄上次未完成的選取
+    use_effect(move || {
+        if open() {
+            let start = value.peek().map_or_else(Date::today, |range| range.start);
+            ctx.focused.set(ctx.clamp(start));
+            ctx.pending_start.set(None);
+            ctx.hovered.set(None);
+        }
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    Year,
+    Month,
+    Day,
+}
+
+const SEGMENTS: [Segment; 3] = [Segment::Year, Segment::Month, Segment::Day];
+
+impl Segment {
+    fn label(&self) -> &'static str {
+        match self {
+            Segment::Year => "year",
+            Segment::Month => "month",
+            Segment::Day => "day",
+        }
+    }
+
+    fn placeholder(&self) -> &'static str {
+        match self {
+            Segment::Year => "yyyy",
+            Segment::Month => "mm",
+            Segment::Day => "dd",
+        }
+    }
+}
+
+// 尚未完成的輸入；日只檢查 1 到 31，湊成日期時才檢查月份的天數
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct DateSegments {
+    year: Option<u32>,
+    month: Option<u32>,
+    day: Option<u32>,
+}
+
+impl DateSegments {
+    fn from_date(date: Option<Date>) -> Self {
+        let Some(date) = date else {
+            return DateSegments::default();
+        };
+        DateSegments {
+            year: Some(date.year as u32),
+            month: Some(date.month as u32),
+            day: Some(date.day as u32),
+        }
+    }
+
+    fn to_date(self) -> Option<Date> {
+        let year = self.year? as i32;
+        let month = self.month? as u8;
+        let day = self.day? as u8;
+        (day <= days_in_month(year, month)).then(|| Date::new(year, month, day))
+    }
+
+    fn get(&self, segment: Segment) -> Option<u32> {
+        match segment {
+            Segment::Year => self.year,
+            Segment::Month => self.month,
+            Segment::Day => self.day,
+        }
+    }
+
+    fn with(mut self, segment: Segment, value: Option<u32>) -> Self {
+        match segment {
+            Segment::Year => self.year = value,
+            Segment::Month => self.month = value,
+            Segment::Day => self.day = value,
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct RangeSegments {
+    start: DateSegments,
+    end: DateSegments,
+}
+
+impl RangeSegments {
+    fn from_range(range: Option<DateRange>) -> Self {
+        RangeSegments {
+            start: DateSegments::from_date(range.map(|range| range.start)),
+            end: DateSegments::from_date(range.map(|range| range.end)),
+        }
+    }
+
+    fn get(&self, edge: Edge) -> DateSegments {
+        match edge {
+            Edge::Start => self.start,
+            Edge::End => self.end,
+        }
+    }
+
+    fn with(mut self, edge: Edge, date: DateSegments) -> Self {
+        match edge {
+            Edge::Start => self.start = date,
+            Edge::End => self.end = date,
+        }
+        self
+    }
+
+    fn is_complete(&self) -> bool {
+        self.start.to_date().is_some() && self.end.to_date().is_some()
+    }
+
+    // 兩個日期都可選且起點不晚於終點時才是有效的範圍
+    fn to_range(self, ctx: &DateRangePickerContext) -> Option<DateRange> {
+        let start = self.start.to_date()?;
+        let end = self.end.to_date()?;
+        let valid = start <= end && !ctx.is_unavailable(start) && !ctx.is_unavailable(end);
+        valid.then_some(DateRange { start, end })
+    }
+}
+
+/// The segments of the start and end days, as a group. The calendar is
+/// positioned against it.
+///
+/// The value is `None` until both days are filled in, within `min` and
+/// `max`, with the start not after the end; until then a complete but
+/// invalid range sets `aria-invalid`.
+#[component]
+pub fn DateRangePickerField(
+    /// The text shown between the start and end days.
+    #[props(default = String::from("–"))]
+    separator: String,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    /// Rendered after the segments, e.g. [`DateRangePickerTrigger`].
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DateRangePickerContext>();
+    let value = ctx.value;
+    let set_value = ctx.set_value;
+    let mut segments = use_signal(|| RangeSegments::from_range(*value.peek()));
+    let focus = use_segment_focus();
+
+    // 外部改變值時同步各段；未完成或無效的輸入不受自己送出的 None 影響
+    use_effect(move || {
+        let range = value();
+        let current = *segments.peek();
+        if range.is_some() || current.to_range(&ctx).is_some() {
+            segments.set(RangeSegments::from_range(range));
+        }
+    });
+
+    let mut update = move |next: RangeSegments| {
+        segments.set(next);
+        let range = next.to_range(&ctx);
+        if range != *value.peek() {
+            set_value.call(range);
+        }
+    };
+    let disabled = (ctx.disabled)();
+    let read_only = (ctx.read_only)();
+    let editable = !disabled && !read_only;
+    let current = segments();
+    let invalid = current.is_complete() && current.to_range(&ctx).is_none();
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new("aria-invalid", invalid.then_some("true"), None, false),
+        Attribute::new("data-invalid", invalid.then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-readonly", read_only.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| rsx! {
+                div {
+                    ..merge_attributes(anchor, attributes.clone()),
+                    for edge in [Edge::Start, Edge::End] {
+                        if edge == Edge::End {
+                            span { aria_hidden: "true", "data-primitive-date-range-separator": "", "{separator}" }
+                        }
+                        for (index, segment) in SEGMENTS.into_iter().enumerate() {
+                            if index > 0 {
+                                span { aria_hidden: "true", "data-primitive-date-separator": "", "-" }
+                            }
+                            DateSegmentSpinner {
+                                key: "{edge:?}-{segment:?}",
+                                edge,
+                                segment,
+                                current,
+                                editable,
+                                disabled,
+                                focus,
+                                on_change: move |next: RangeSegments| update(next),
+                            }
+                        }
+                    }
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn DateSegmentSpinner(
+    edge: Edge,
+    segment: Segment,
+    current: RangeSegments,
+    editable: bool,
+    disabled: bool,
+    focus: SegmentFocus,
+    on_change: EventHandler<RangeSegments>,
+) -> Element {
+    // 依日期與段的種類排列焦點順序，而不是掛載順序
+    let id = use_hook(|| edge as usize * SEGMENTS.len() + segment as usize);
+    let mut buffer = use_signal(String::new);
+    use_drop(move || {
+        let mut focus = focus;
+        focus.unregister(id);
+    });
+
+    let (min, max) = match segment {
+        Segment::Year => (1, 9999),
+        Segment::Month => (1, 12),
+        Segment::Day => (1, 31),
+    };
+    let date = current.get(edge);
+    let value = date.get(segment);
+    let with_value = move |value: Option<u32>| current.with(edge, date.with(segment, value));
+
+    let text = match (segment, value) {
+        (_, None) => segment.placeholder().to_string(),
+        (Segment::Year, Some(year)) => format!("{year:04}"),
+        (_, Some(value)) => format!("{value:02}"),
+    };
+    let value_text = match value {
+        Some(_) => text.clone(),
+        None => "Empty".to_string(),
+    };
+    let edge_label = match edge {
+        Edge::Start => "start",
+        Edge::End => "end",
+    };
+
+    rsx! {
+        span {
+            role: "spinbutton",
+            tabindex: if disabled { "-1" } else { "0" },
+            inputmode: "numeric",
+            aria_label: format!("{edge_label} {}", segment.label()),
+            aria_valuemin: min,
+            aria_valuemax: max,
+            aria_valuenow: value,
+            aria_valuetext: value_text,
+            aria_disabled: disabled.then_some("true"),
+            aria_readonly: (!editable && !disabled).then_some("true"),
+            "data-edge": edge_label,
+            "data-segment": segment.label(),
+            "data-placeholder": value.is_none().then_some(""),
+            onmounted: move |event: MountedEvent| {
+                let mut focus = focus;
+                focus.register(id, event.data());
+            },
+            onblur: move |_| buffer.set(String::new()),
+            onkeydown: move |event: KeyboardEvent| {
+                match event.key() {
+                    Key::ArrowLeft => {
+                        event.prevent_default();
+                        focus.focus_relative(id, -1);
+                        return;
+                    }
+                    Key::ArrowRight => {
+                        event.prevent_default();
+                        focus.focus_relative(id, 1);
+                        return;
+                    }
+                    Key::Tab => return,
+                    _ => {}
+                }
+                if !editable {
+                    return;
+                }
+                match event.key() {
+                    Key::ArrowUp | Key::ArrowDown => {
+                        event.prevent_default();
+                        buffer.set(String::new());
+                        let delta = if event.key() == Key::ArrowUp { 1 } else { -1 };
+                        let next = match (segment, value) {
+                            // 空白的年從今年開始
+                            (Segment::Year, None) => Date::today().year.clamp(1, 9999) as u32,
+                            _ => step(value, delta, min, max),
+                        };
+                        on_change.call(with_value(Some(next)));
+                    }
+                    Key::Backspace | Key::Delete => {
+                        event.prevent_default();
+                        buffer.set(String::new());
+                        on_change.call(with_value(None));
+                    }
+                    Key::Character(character) => {
+                        let Some(digit) = character.chars().next().filter(|_| character.len() == 1)
+                        else {
+                            return;
+                        };
+                        let Some(typed) = type_digit(&buffer.peek(), digit, max) else {
+                            return;
+                        };
+                        event.prevent_default();
+                        // 開頭的 0 要等下一個數字才成為有效值
+                        if typed.value >= min {
+                            on_change.call(with_value(Some(typed.value)));
+                        }
+                        if typed.complete {
+                            buffer.set(String::new());
+                            focus.focus_relative(id, 1);
+                        } else {
+                            buffer.set(typed.buffer);
+                        }
+                    }
+                    _ => {}
+                }
+            },
+            {text}
+        }
+    }
+}
+
+/// The button that toggles the calendar.
+#[component]
+pub fn DateRangePickerTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DateRangePickerContext>();
+    let open = (ctx.open)();
+    let disabled = (ctx.disabled)();
+    let set_open = ctx.set_open;
+    let mut trigger = ctx.trigger;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("aria-haspopup", "dialog", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", (ctx.content_id)(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-state", state_str(open), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            trigger.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| set_open.call(!open)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The floating panel holding the calendar, rendered while the picker is
+/// open and until its exit animation finishes.
+///
+/// Focus moves to the first day of the range, or to today, when it opens.
+#[component]
+pub fn DateRangePickerContent(
+    #[props(default)] side: Side,
+    #[props(default)] side_offset: f64,
+    #[props(default = Align::Start)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired when focus returns to the trigger on close. Prevent it to focus
+    /// another element yourself.
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on Escape; prevent it to keep the calendar open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired for any interaction outside the content; prevent it to keep the
+    /// calendar open.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DateRangePickerContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                DateRangePickerContentImpl {
+                    position,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_close_auto_focus,
+                    on_escape_key_down,
+                    on_interact_outside,
+                    as_child,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+// 只在內容掛載時存在，讓焦點管理與圖層跟著 Presence 一起掛載與卸載
+#[component]
+fn DateRangePickerContentImpl(
+    position: PositionOptions,
+    element_attributes: Vec<Attribute>,
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    as_child: Option<AsChild>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<DateRangePickerContext>();
+    let set_open = ctx.set_open;
+    let trigger = ctx.trigger;
+    use_focus_guards();
+
+    let own_attributes = vec![
+        Attribute::new("role", "dialog", None, false),
+        Attribute::new("id", (ctx.content_id)(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            FocusScope {
+                // 焦點交給日曆中目前的日期
+                on_mount_auto_focus: move |event: AutoFocusEvent| {
+                    event.prevent_default();
+                    ctx.focus_requested.set(true);
+                },
+                on_unmount_auto_focus: on_close_auto_focus,
+                as_child: move |scope: Vec<Attribute>| {
+                    let element_attributes = merge_attributes(scope, attributes.clone());
+                    let children = children.clone();
+                    rsx! {
+                        DismissableLayer {
+                            // 按下觸發按鈕時由按鈕自己切換，不視為外部互動
+                            inside_elements: vec![trigger],
+                            on_escape_key_down,
+                            on_interact_outside,
+                            on_dismiss: move |_| set_open.call(false),
+                            as_child: move |layer: Vec<Attribute>| {
+                                let attributes = merge_attributes(layer, element_attributes.clone());
+                                if let Some(as_child) = as_child {
+                                    return as_child.call(attributes);
+                                }
+                                rsx! {
+                                    div {
+                                        ..attributes,
+                                        {children.clone()}
+                                    }
+                                }
+                            },
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// The month grid to pick the range from, with buttons for the previous
+/// and next month.
+#[component]
+pub fn DateRangePickerCalendar(
+    /// The first day of the week, from 0 for Sunday to 6 for Saturday.
+    #[props(default)]
+    week_start: u8,
+    #[props(default = String::from("Previous month"))] previous_label: String,
+    #[props(default = String::from("Next month"))] next_label: String,
+    /// Swaps ArrowLeft and ArrowRight in right-to-left layouts. Defaults to
+    /// the direction from [`use_direction`].
+    dir: Option<Direction>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<DateRangePickerContext>();
+    let inherited_dir = use_direction();
+    let dir = dir.unwrap_or(inherited_dir);
+    let heading_id = use_id();
+    let week_start = week_start % 7;
+    let today = Date::today();
+
+    let focused = (ctx.focused)();
+    let first = Date::new(focused.year, focused.month, 1);
+    let offset = (first.weekday() + 7 - week_start) % 7;
+    let grid_start = first.add_days(-(offset as i64));
+    let weeks = (offset as usize + days_in_month(first.year, first.month) as usize).div_ceil(7);
+    let disabled = (ctx.disabled)();
+    let previous_disabled = disabled || (ctx.min)().is_some_and(|min| first.add_days(-1) < min);
+    let next_disabled = disabled || (ctx.max)().is_some_and(|max| first.add_months(1) > max);
+
+    let own_attributes = vec![Attribute::new("data-primitive-calendar", "", None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div {
+            ..attributes,
+            div { "data-primitive-calendar-header": "",
+                button {
+                    r#type: "button",
+                    aria_label: previous_label,
+                    disabled: previous_disabled,
+                    onclick: move |_| {
+                        let date = ctx.focused.peek().add_months(-1);
+                        ctx.focused.set(ctx.clamp(date));
+                    },
+                    "‹"
+                }
+                div { id: heading_id.clone(), aria_live: "polite",
+                    {format!("{} {}", MONTH_NAMES[first.month as usize - 1], first.year)}
+                }
+                button {
+                    r#type: "button",
+                    aria_label: next_label,
+                    disabled: next_disabled,
+                    onclick: move |_| {
+                        let date = ctx.focused.peek().add_months(1);
+                        ctx.focused.set(ctx.clamp(date));
+                    },
+                    "›"
+                }
+            }
+            table {
+                role: "grid",
+                aria_labelledby: heading_id,
+                aria_multiselectable: "true",
+                aria_disabled: disabled.then_some("true"),
+                onpointerleave: move |_| ctx.hovered.set(None),
+                thead {
+                    tr {
+                        for index in 0..7 {
+                            th {
+                                scope: "col",
+                                abbr: WEEKDAY_NAMES[(week_start as usize + index) % 7],
+                                {WEEKDAY_NAMES[(week_start as usize + index) % 7][..2].to_string()}
+                            }
+                        }
+                    }
+                }
+                tbody {
+                    for week in 0..weeks {
+                        tr { key: "{week}",
+                            for day in 0..7 {
+                                DayCell {
+                                    key: "{grid_start.add_days((week * 7 + day) as i64)}",
+                                    date: grid_start.add_days((week * 7 + day) as i64),
+                                    month: first.month,
+                                    today: grid_start.add_days((week * 7 + day) as i64) == today,
+                                    week_start,
+                                    dir,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DayCell(date: Date, month: u8, today: bool, week_start: u8, dir: Direction) -> Element {
+    let mut ctx = use_context::<DateRangePickerContext>();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+
+    // 打開日曆或以鍵盤移到另一天後，由該日期的格子取得焦點
+    use_effect(move || {
+        if (ctx.focused)() == date
+            && (ctx.focus_requested)()
+            && let Some(node) = node()
+        {
+            ctx.focus_requested.set(false);
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        }
+    });
+
+    let range = ctx.shown_range();
+    let previewing = ctx.pending_start.read().is_some();
+    let selected = (ctx.value)().is_some_and(|range| range.contains(date));
+    let in_range = range.is_some_and(|range| range.contains(date));
+    let is_start = range.is_some_and(|range| range.start == date);
+    let is_end = range.is_some_and(|range| range.end == date);
+    let unavailable = (ctx.disabled)() || ctx.is_unavailable(date);
+    let focused = (ctx.focused)() == date;
+    let days_into_week = ((date.weekday() + 7 - week_start) % 7) as i64;
+
+    rsx! {
+        td {
+            role: "gridcell",
+            tabindex: if focused && !(ctx.disabled)() { "0" } else { "-1" },
+            aria_label: date.label(),
+            aria_selected: selected,
+            aria_disabled: unavailable.then_some("true"),
+            aria_current: today.then_some("date"),
+            "data-date": date.to_string(),
+            "data-selected": selected.then_some(""),
+            "data-range-start": is_start.then_some(""),
+            "data-range-middle": (in_range && !is_start && !is_end).then_some(""),
+            "data-range-end": is_end.then_some(""),
+            "data-preview": (previewing && in_range).then_some(""),
+            "data-outside-month": (date.month != month).then_some(""),
+            "data-today": today.then_some(""),
+            "data-unavailable": unavailable.then_some(""),
+            onmounted: move |event: MountedEvent| node.set(Some(event.data())),
+            onpointerenter: move |_| ctx.hovered.set(Some(date)),
+            onfocus: move |_| {
+                ctx.hovered.set(Some(date));
+                if *ctx.focused.peek() != date {
+                    ctx.focused.set(date);
+                }
+            },
+            onclick: move |_| ctx.pick(date),
+            onkeydown: move |event: KeyboardEvent| {
+                let (back, forward) = match dir {
+                    Direction::Ltr => (-1, 1),
+                    Direction::Rtl => (1, -1),
+                };
+                let years = if event.modifiers().shift() { 12 } else { 1 };
+                let target = match event.key() {
+                    Key::ArrowLeft => date.add_days(back),
+                    Key::ArrowRight => date.add_days(forward),
+                    Key::ArrowUp => date.add_days(-7),
+                    Key::ArrowDown => date.add_days(7),
+                    Key::PageUp => date.add_months(-years),
+                    Key::PageDown => date.add_months(years),
+                    Key::Home => date.add_days(-days_into_week),
+                    Key::End => date.add_days(6 - days_into_week),
+                    Key::Enter => {
+                        event.prevent_default();
+                        ctx.pick(date);
+                        return;
+                    }
+                    key if key == Key::Character(" ".into()) => {
+                        event.prevent_default();
+                        ctx.pick(date);
+                        return;
+                    }
+                    _ => return,
+                };
+                event.prevent_default();
+                ctx.move_focus(target);
+            },
+            "{date.day}"
+        }
+    }
+}
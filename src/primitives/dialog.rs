@@ -0,0 +1,469 @@
+//! Dialog Primitive
+//!
+//! A window overlaid on the page, rendering the content behind it inert.
+//! While open, focus is trapped inside the content and returns to the
+//! trigger on close, the page does not scroll, and Escape or a press outside
+//! the content closes it.
+//!
+//! A non-modal dialog leaves the page usable, e.g. for inspector panels: it
+//! does not trap focus, lock scrolling or hide the page, and renders no
+//! overlay. Escape and presses outside it still close it.
+//!
+//! Dialogs can be opened from inside other dialogs. Only the topmost one
+//! reacts to Escape and outside presses, and closing it returns focus to the
+//! dialog below. The content and overlay expose `data-nested` when the
+//! dialog is opened from another layer, e.g. a dialog or popover, and
+//! `data-has-nested` while a layer is open inside it; the content sets
+//! `--primitive-dialog-depth` to the number of layers it is nested in, e.g.
+//! to offset stacked dialogs.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Dialog {
+//!         DialogTrigger { "Edit profile" }
+//!         DialogPortal {
+//!             DialogOverlay { class: "overlay" }
+//!             DialogContent { class: "content",
+//!                 DialogTitle { "Edit profile" }
+//!                 DialogDescription { "Make changes to your profile." }
+//!                 DialogClose { "Save" }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::focus_guards::use_focus_guards;
+use super::focus_scope::{AutoFocusEvent, FocusScope};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::layer_stack::{LayerId, LayerStack};
+use super::portal::{Portal, PortalTarget};
+use super::presence::Presence;
+use super::scroll_lock::use_scroll_lock;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct DialogContext {
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    modal: Signal<bool>,
+    trigger: Signal<Option<Rc<MountedData>>>,
+    content_id: String,
+    title_id: String,
+    description_id: String,
+    // 只有實際渲染了標題或說明時才加上 aria-labelledby/aria-describedby
+    has_title: Signal<bool>,
+    has_description: Signal<bool>,
+    // 內容在圖層堆疊中的圖層，巢狀關係由堆疊記錄
+    layer: Signal<Option<LayerId>>,
+}
+
+impl DialogContext {
+    fn depth(&self) -> usize {
+        (self.layer)().and_then(LayerStack::depth).unwrap_or(0)
+    }
+
+    fn nesting_attributes(&self) -> Vec<Attribute> {
+        let has_nested = (self.layer)().is_some_and(|id| !LayerStack::nested(id).is_empty());
+        vec![
+            Attribute::new("data-nested", (self.depth() > 0).then_some(""), None, false),
+            Attribute::new("data-has-nested", has_nested.then_some(""), None, false),
+        ]
+    }
+}
+
+fn state_str(open: bool) -> &'static str {
+    if open { "open" } else { "closed" }
+}
+
+/// The root of a dialog, owning its open state.
+///
+/// Pass `open` and `on_open_change` to control the state, or
+/// `default_open` to let the dialog manage it.
+#[component]
+pub fn Dialog(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Trap focus in the content, lock scrolling and make the rest of the
+    /// page inert while open. Turn it off to keep the page usable.
+    #[props(default = true)]
+    modal: bool,
+    children: Element,
+) -> Element {
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let modal = use_synced_signal(modal);
+    let content_id = use_id();
+    let title_id = use_id();
+    let description_id = use_id();
+    use_context_provider(|| DialogContext {
+        open,
+        set_open,
+        modal,
+        trigger: Signal::new(None),
+        content_id,
+        title_id,
+        description_id,
+        has_title: Signal::new(false),
+        has_description: Signal::new(false),
+        layer: Signal::new(None),
+    });
+
+    rsx! { {children} }
+}
+
+/// The button that toggles the dialog.
+#[component]
+pub fn DialogTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let open = (ctx.open)();
+    let set_open = ctx.set_open;
+    let mut trigger = ctx.trigger;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("aria-haspopup", "dialog", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", ctx.content_id.clone(), None, false),
+        Attribute::new("data-state", state_str(open), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            trigger.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| set_open.call(!open)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Renders the overlay and content into `target`, outside of the page's
+/// layout; see [`Portal`].
+#[component]
+pub fn DialogPortal(#[props(default)] target: PortalTarget, children: Element) -> Element {
+    rsx! {
+        Portal { target, {children} }
+    }
+}
+
+/// A layer covering the page behind the content while a modal dialog is
+/// open. Not rendered for non-modal dialogs.
+#[component]
+pub fn DialogOverlay(
+    /// Keep the overlay mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let open = (ctx.open)();
+    if !(ctx.modal)() {
+        return rsx! {};
+    }
+
+    let mut own_attributes = vec![Attribute::new(
+        "data-primitive-dialog-overlay",
+        "",
+        None,
+        false,
+    )];
+    own_attributes.extend(ctx.nesting_attributes());
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child,
+            ..attributes,
+        }
+    }
+}
+
+/// The dialog window, rendered while the dialog is open and until its exit
+/// animation finishes.
+///
+/// Focus moves into the content when it opens; Tab and Shift+Tab loop
+/// within it. A non-modal dialog does not return focus to the trigger when
+/// it closes because of a press or focus outside of it.
+#[component]
+pub fn DialogContent(
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired when focus moves into the content on open. Prevent it to focus
+    /// another element yourself.
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired when focus returns to the trigger on close. Prevent it to focus
+    /// another element yourself.
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on Escape; prevent it to keep the dialog open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the content; prevent it to keep the
+    /// dialog open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired when focus moves outside a non-modal dialog's content; prevent
+    /// it to keep the dialog open.
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired for any interaction outside the content; prevent it to keep the
+    /// dialog open.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let open = (ctx.open)();
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                DialogContentImpl {
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_open_auto_focus,
+                    on_close_auto_focus,
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    on_focus_outside,
+                    on_interact_outside,
+                    as_child,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+// 只在內容掛載時存在，讓焦點陷阱、捲動鎖定與圖層跟著 Presence 一起掛載與卸載
+#[component]
+fn DialogContentImpl(
+    element_attributes: Vec<Attribute>,
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    as_child: Option<AsChild>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let open = (ctx.open)();
+    let modal = (ctx.modal)();
+    let set_open = ctx.set_open;
+    let trigger = ctx.trigger;
+    use_focus_guards();
+    use_scroll_lock(open && modal);
+    // 非 modal 對話框因外部互動而關閉時，焦點留在使用者互動的位置
+    let mut interacted_outside = use_signal(|| false);
+
+    let mut layer_id = ctx.layer;
+    let depth = ctx.depth();
+
+    let mut own_attributes = vec![
+        Attribute::new("role", "dialog", None, false),
+        Attribute::new("id", ctx.content_id.clone(), None, false),
+        Attribute::new("aria-modal", modal.then_some("true"), None, false),
+        Attribute::new(
+            "aria-labelledby",
+            ctx.has_title.read().then(|| ctx.title_id.clone()),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "aria-describedby",
+            ctx.has_description
+                .read()
+                .then(|| ctx.description_id.clone()),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "style",
+            format!("--primitive-dialog-depth: {depth};"),
+            None,
+            false,
+        ),
+    ];
+    own_attributes.extend(ctx.nesting_attributes());
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        FocusScope {
+            // 關閉動畫期間不再困住焦點
+            trapped: open && modal,
+            loop_focus: true,
+            on_mount_auto_focus: on_open_auto_focus,
+            on_unmount_auto_focus: move |event: AutoFocusEvent| {
+                if let Some(handler) = on_close_auto_focus {
+                    handler.call(event.clone());
+                }
+                if *interacted_outside.peek() {
+                    event.prevent_default();
+                }
+            },
+            as_child: move |scope: Vec<Attribute>| {
+                let element_attributes = merge_attributes(scope, attributes.clone());
+                let children = children.clone();
+                rsx! {
+                    DismissableLayer {
+                        disable_outside_pointer_events: open && modal,
+                        modal: open && modal,
+                        // 按下觸發按鈕時由按鈕自己切換，不視為外部互動
+                        inside_elements: vec![trigger],
+                        on_escape_key_down,
+                        on_pointer_down_outside,
+                        on_focus_outside: move |event: DismissEvent| {
+                            if let Some(handler) = on_focus_outside {
+                                handler.call(event.clone());
+                            }
+                            // 焦點已被困在內容中，移到外部只會是暫時的
+                            if modal {
+                                event.prevent_default();
+                            }
+                        },
+                        on_interact_outside: move |event: DismissEvent| {
+                            if let Some(handler) = on_interact_outside {
+                                handler.call(event.clone());
+                            }
+                            if !modal && !event.is_default_prevented() {
+                                interacted_outside.set(true);
+                            }
+                        },
+                        on_dismiss: move |_| set_open.call(false),
+                        on_layer: move |id: LayerId| layer_id.set(Some(id)),
+                        as_child: move |layer: Vec<Attribute>| {
+                            let attributes = merge_attributes(layer, element_attributes.clone());
+                            if let Some(as_child) = as_child {
+                                return as_child.call(attributes);
+                            }
+                            rsx! {
+                                div {
+                                    ..attributes,
+                                    {children.clone()}
+                                }
+                            }
+                        },
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// The accessible name of the dialog, announced when it opens.
+#[component]
+pub fn DialogTitle(
+    /// Render your own element instead of an `h2`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let mut has_title = ctx.has_title;
+
+    use_effect(move || has_title.set(true));
+    use_drop(move || has_title.set(false));
+
+    let own_attributes = vec![Attribute::new("id", ctx.title_id.clone(), None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        h2 {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An optional description of the dialog, announced after its title.
+#[component]
+pub fn DialogDescription(
+    /// Render your own element instead of a `p`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let mut has_description = ctx.has_description;
+
+    use_effect(move || has_description.set(true));
+    use_drop(move || has_description.set(false));
+
+    let own_attributes = vec![Attribute::new(
+        "id",
+        ctx.description_id.clone(),
+        None,
+        false,
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        p {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button that closes the dialog.
+#[component]
+pub fn DialogClose(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<DialogContext>();
+    let set_open = ctx.set_open;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        dioxus_elements::events::onclick(move |_: MouseEvent| set_open.call(false)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Reading direction
+//!
+//! Primitives with horizontal keyboard navigation swap the meaning of the
+//! left and right arrow keys in right-to-left layouts.
+
+use super::context::create_context::use_provide_context_value;
+use super::context::use_optional_context::use_optional_context;
+use dioxus::prelude::*;
+
+/// The reading direction of a primitive's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// The value of the HTML `dir` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Sets the reading direction of every primitive below it.
+///
+/// Primitives with their own `dir` prop still prefer it.
+#[component]
+pub fn DirectionProvider(dir: Direction, children: Element) -> Element {
+    use_provide_context_value(dir);
+
+    rsx! { {children} }
+}
+
+/// The reading direction set by the nearest [`DirectionProvider`].
+///
+/// Without a provider, the `dir` attribute of the document's root element is
+/// used (with the `web` feature), falling back to left-to-right.
+pub fn use_direction() -> Direction {
+    let document_dir = use_hook(document_direction);
+    use_optional_context::<Direction>().unwrap_or(document_dir)
+}
+
+#[cfg(feature = "web")]
+fn document_direction() -> Direction {
+    let dir = super::dom::document()
+        .and_then(|document| document.document_element())
+        .and_then(|root| root.get_attribute("dir"));
+
+    match dir.as_deref() {
+        Some(dir) if dir.eq_ignore_ascii_case("rtl") => Direction::Rtl,
+        _ => Direction::Ltr,
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn document_direction() -> Direction {
+    Direction::Ltr
+}
@@ -0,0 +1,198 @@
+//! DismissableLayer Primitive
+//!
+//! The dismissal behavior shared by popovers, menus and dialogs: a layer is
+//! dismissed when Escape is pressed, or when the user presses a pointer or
+//! moves focus outside of it. Mounted layers form a stack, and only the
+//! topmost layer reacts, so closing a nested menu leaves its dialog open.
+//! Listening to the document requires the `web` feature; elsewhere the layer
+//! only renders its content.
+
+use super::hooks::use_focus_outside::use_focus_outside;
+#[cfg(feature = "web")]
+use super::hooks::use_global_event::use_document_event;
+use super::hooks::use_pointer_down_outside::use_pointer_down_outside;
+use super::layer_stack::{LayerId, LayerOptions, LayerStack, use_layer};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// What caused a [`DismissableLayer`] to request dismissal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DismissReason {
+    EscapeKey,
+    PointerDownOutside,
+    FocusOutside,
+}
+
+/// Passed to the `DismissableLayer` handlers before the layer is dismissed.
+///
+/// Call [`prevent_default`](Self::prevent_default) to keep the layer open.
+#[derive(Debug, Clone)]
+pub struct DismissEvent {
+    reason: DismissReason,
+    prevented: Rc<Cell<bool>>,
+}
+
+impl DismissEvent {
+    fn new(reason: DismissReason) -> Self {
+        DismissEvent {
+            reason,
+            prevented: Rc::new(Cell::new(false)),
+        }
+    }
+
+    pub fn reason(&self) -> DismissReason {
+        self.reason
+    }
+
+    pub fn prevent_default(&self) {
+        self.prevented.set(true);
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevented.get()
+    }
+}
+
+// 一個圖層的事件處理器
+#[derive(Clone, Copy)]
+struct DismissHandlers {
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    on_dismiss: Option<EventHandler<()>>,
+}
+
+impl DismissHandlers {
+    /// Calls the handlers for `reason`, then `on_dismiss` unless one of
+    /// them prevented it. Returns whether the layer was dismissed.
+    fn dismiss(&self, reason: DismissReason) -> bool {
+        let event = DismissEvent::new(reason);
+        let specific = match reason {
+            DismissReason::EscapeKey => self.on_escape_key_down,
+            DismissReason::PointerDownOutside => self.on_pointer_down_outside,
+            DismissReason::FocusOutside => self.on_focus_outside,
+        };
+        if let Some(handler) = specific {
+            handler.call(event.clone());
+        }
+        if reason != DismissReason::EscapeKey
+            && let Some(handler) = self.on_interact_outside
+        {
+            handler.call(event.clone());
+        }
+
+        if event.is_default_prevented() {
+            return false;
+        }
+        if let Some(handler) = self.on_dismiss {
+            handler.call(());
+        }
+        true
+    }
+}
+
+/// A layer that asks to be dismissed on Escape or on interaction outside.
+///
+/// The layer does not unmount itself: close it from `on_dismiss`.
+#[component]
+pub fn DismissableLayer(
+    /// Block pointer events on everything below this layer, e.g. for modals.
+    #[props(default)]
+    disable_outside_pointer_events: bool,
+    /// Hide everything outside this layer from assistive technology and
+    /// make it inert, e.g. for dialogs; see
+    /// [`LayerOptions::modal`](super::layer_stack::LayerOptions::modal).
+    #[props(default)]
+    modal: bool,
+    /// Elements outside the layer that count as part of it, e.g. the trigger
+    /// that toggles it, so pressing or focusing them does not dismiss it.
+    #[props(default)]
+    inside_elements: Vec<Signal<Option<Rc<MountedData>>>>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired for both pointer-down-outside and focus-outside.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired when the layer should close, unless a handler prevented it.
+    on_dismiss: Option<EventHandler<()>>,
+    /// Receives the id of the layer once it is on the [`LayerStack`], e.g. to
+    /// ask how deeply it is nested.
+    on_layer: Option<EventHandler<LayerId>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let id = use_layer(
+        node,
+        LayerOptions {
+            modal,
+            disable_outside_pointer_events,
+        },
+    );
+    use_effect(move || {
+        if let Some(handler) = on_layer {
+            handler.call(id);
+        }
+    });
+
+    let handlers = DismissHandlers {
+        on_escape_key_down,
+        on_pointer_down_outside,
+        on_focus_outside,
+        on_interact_outside,
+        on_dismiss,
+    };
+
+    let mut elements = vec![node];
+    elements.extend(inside_elements);
+
+    use_pointer_down_outside(elements.clone(), move |_| {
+        if LayerStack::is_topmost(id) && LayerStack::pointer_events_enabled(id) {
+            handlers.dismiss(DismissReason::PointerDownOutside);
+        }
+    });
+    use_focus_outside(elements, move |_| {
+        if LayerStack::is_topmost(id) {
+            handlers.dismiss(DismissReason::FocusOutside);
+        }
+    });
+
+    #[cfg(feature = "web")]
+    use_document_event("keydown", false, move |event: web_sys::KeyboardEvent| {
+        if event.key() == "Escape"
+            && LayerStack::is_topmost(id)
+            && handlers.dismiss(DismissReason::EscapeKey)
+        {
+            event.prevent_default();
+        }
+    });
+
+    let any_disabling = LayerStack::layers()
+        .iter()
+        .any(|layer| layer.options.disable_outside_pointer_events);
+    let pointer_events =
+        (any_disabling && LayerStack::pointer_events_enabled(id)).then_some("auto");
+
+    let own_attributes = vec![
+        Attribute::new("data-primitive-dismissable-layer", "", None, false),
+        Attribute::new("pointer-events", pointer_events, Some("style"), false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| node.set(Some(event.data()))),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
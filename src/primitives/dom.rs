@@ -0,0 +1,214 @@
+//! DOM helpers shared by the primitives.
+//!
+//! Only the `web` feature has access to the real DOM; the primitives built on
+//! these helpers degrade to inert behavior on other platforms.
+
+#[cfg(feature = "web")]
+use dioxus::dioxus_core::{Runtime, RuntimeGuard};
+#[cfg(feature = "web")]
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "web")]
+use wasm_bindgen::closure::Closure;
+
+/// The `web_sys` element behind a mounted node.
+#[cfg(feature = "web")]
+pub(crate) fn web_element(node: &MountedData) -> Option<web_sys::Element> {
+    node.downcast::<web_sys::Element>().cloned()
+}
+
+/// The trimmed text of a mounted node.
+#[cfg(feature = "web")]
+pub(crate) fn text_content(node: &MountedData) -> Option<String> {
+    let text = web_element(node)?.text_content()?;
+    Some(text.trim().to_string())
+}
+
+#[cfg(feature = "web")]
+pub(crate) fn document() -> Option<web_sys::Document> {
+    web_sys::window()?.document()
+}
+
+#[cfg(feature = "web")]
+pub(crate) fn active_element() -> Option<web_sys::Element> {
+    document()?.active_element()
+}
+
+/// Focus `element` if it can receive focus.
+#[cfg(feature = "web")]
+pub(crate) fn focus(element: &web_sys::Element) {
+    if let Some(element) = element.dyn_ref::<web_sys::HtmlElement>() {
+        let _ = element.focus();
+    }
+}
+
+#[cfg(feature = "web")]
+const TABBABLE_SELECTOR: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type='hidden']), select:not([disabled]), \
+    textarea:not([disabled]), [tabindex]:not([tabindex='-1']), [contenteditable='true']";
+
+/// Elements inside `container` reachable with Tab, in document order.
+#[cfg(feature = "web")]
+pub(crate) fn tabbable_elements(container: &web_sys::Element) -> Vec<web_sys::Element> {
+    let Ok(nodes) = container.query_selector_all(TABBABLE_SELECTOR) else {
+        return Vec::new();
+    };
+
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+        .filter(|element| {
+            // 隱藏的元素（例如 display: none）沒有 client rects
+            element.get_client_rects().length() > 0
+        })
+        .collect()
+}
+
+/// Whether `node` is `container` or one of its descendants.
+#[cfg(feature = "web")]
+pub(crate) fn contains(container: &web_sys::Element, node: &web_sys::EventTarget) -> bool {
+    node.dyn_ref::<web_sys::Node>()
+        .is_some_and(|node| container.contains(Some(node)))
+}
+
+/// Compare two mounted nodes by their position in the document, e.g. to
+/// order items that registered in mount order but were moved since.
+#[cfg(feature = "web")]
+pub(crate) fn document_order(a: &MountedData, b: &MountedData) -> std::cmp::Ordering {
+    let (Some(a), Some(b)) = (web_element(a), web_element(b)) else {
+        return std::cmp::Ordering::Equal;
+    };
+    if a == b {
+        std::cmp::Ordering::Equal
+    } else if a.compare_document_position(&b) & web_sys::Node::DOCUMENT_POSITION_FOLLOWING != 0 {
+        std::cmp::Ordering::Less
+    } else {
+        std::cmp::Ordering::Greater
+    }
+}
+
+/// The nearest ancestor of `element` that scrolls its overflow, or `None`
+/// when only the document scrolls.
+#[cfg(feature = "web")]
+pub(crate) fn scroll_parent(element: &web_sys::Element) -> Option<web_sys::Element> {
+    let window = web_sys::window()?;
+    let mut current = element.parent_element();
+    while let Some(parent) = current {
+        let scrolls = window
+            .get_computed_style(&parent)
+            .ok()
+            .flatten()
+            .and_then(|style| style.get_property_value("overflow").ok())
+            .is_some_and(|overflow| {
+                overflow.contains("auto")
+                    || overflow.contains("scroll")
+                    || overflow.contains("overlay")
+            });
+        if scrolls {
+            return Some(parent);
+        }
+        current = parent.parent_element();
+    }
+    None
+}
+
+/// Route the following events of the pointer behind `event` to its target
+/// until the pointer is released, even when it leaves the element.
+#[cfg(feature = "web")]
+pub(crate) fn capture_pointer(event: &PointerEvent) {
+    let Some(event) = event.data().downcast::<web_sys::PointerEvent>().cloned() else {
+        return;
+    };
+    if let Some(target) = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+    {
+        let _ = target.set_pointer_capture(event.pointer_id());
+    }
+}
+
+/// Wrap `callback` so it runs inside the Dioxus runtime that is current now,
+/// letting callbacks invoked by the browser write signals and call event
+/// handlers.
+#[cfg(feature = "web")]
+pub(crate) fn in_current_runtime<A: 'static>(
+    mut callback: impl FnMut(A) + 'static,
+) -> impl FnMut(A) + 'static {
+    let runtime = Runtime::current();
+    move |argument| {
+        let _guard = runtime.clone().map(RuntimeGuard::new);
+        callback(argument);
+    }
+}
+
+/// A DOM event listener that is removed when dropped.
+///
+/// The callback runs [`in_current_runtime`].
+#[cfg(feature = "web")]
+pub(crate) struct EventListener {
+    target: web_sys::EventTarget,
+    event: String,
+    capture: bool,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+#[cfg(feature = "web")]
+impl EventListener {
+    pub(crate) fn new(
+        target: &web_sys::EventTarget,
+        event: &str,
+        capture: bool,
+        callback: impl FnMut(web_sys::Event) + 'static,
+    ) -> Self {
+        let callback = Closure::<dyn FnMut(web_sys::Event)>::new(in_current_runtime(callback));
+        let _ = target.add_event_listener_with_callback_and_bool(
+            event,
+            callback.as_ref().unchecked_ref(),
+            capture,
+        );
+
+        EventListener {
+            target: target.clone(),
+            event: event.to_string(),
+            capture,
+            callback,
+        }
+    }
+
+    /// Like [`new`](Self::new), but the listener may call `prevent_default`
+    /// on events that browsers otherwise dispatch to passive listeners, such
+    /// as `touchmove` and `wheel` on the document.
+    pub(crate) fn non_passive(
+        target: &web_sys::EventTarget,
+        event: &str,
+        callback: impl FnMut(web_sys::Event) + 'static,
+    ) -> Self {
+        let callback = Closure::<dyn FnMut(web_sys::Event)>::new(in_current_runtime(callback));
+        let options = web_sys::AddEventListenerOptions::new();
+        options.set_passive(false);
+        let _ = target.add_event_listener_with_callback_and_add_event_listener_options(
+            event,
+            callback.as_ref().unchecked_ref(),
+            &options,
+        );
+
+        EventListener {
+            target: target.clone(),
+            event: event.to_string(),
+            capture: false,
+            callback,
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback_and_bool(
+            &self.event,
+            self.callback.as_ref().unchecked_ref(),
+            self.capture,
+        );
+    }
+}
@@ -0,0 +1,633 @@
+//! FileUpload Primitive
+//!
+//! A managed list of files chosen with the file dialog or dropped on a
+//! dropzone. Files are checked against `accept`, the size limits and
+//! `max_files` as they are added; accepted files join the list, and the
+//! others are reported with the reasons they were rejected.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     FileUpload { accept: vec!["image/*".into()], max_size: 5 * 1024 * 1024,
+//!         on_files_reject: move |rejected| show_errors(rejected),
+//!         FileUploadDropzone { "Drop images here" }
+//!         FileUploadTrigger { "Browse" }
+//!         FileUploadHiddenInput {}
+//!         FileUploadList {}
+//!     }
+//! }
+//! ```
+//!
+//! Reading the files is up to you: an [`UploadFile`] reads its bytes with
+//! [`UploadFile::read`], e.g. when a form is submitted.
+//!
+//! Opening the file dialog from a trigger or dropzone needs the `web`
+//! feature; elsewhere, wrap the hidden input in a `label` instead.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::html::FileEngine;
+use dioxus::prelude::*;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A file in a [`FileUpload`]'s list.
+#[derive(Clone)]
+pub struct UploadFile {
+    pub name: String,
+    /// The size in bytes.
+    pub size: u64,
+    /// The MIME type, e.g. `image/png`, when known.
+    pub mime_type: Option<String>,
+    engine: Arc<dyn FileEngine>,
+}
+
+impl UploadFile {
+    /// Read the contents of the file.
+    pub async fn read(&self) -> Option<Vec<u8>> {
+        self.engine.read_file(&self.name).await
+    }
+
+    pub async fn read_to_string(&self) -> Option<String> {
+        self.engine.read_file_to_string(&self.name).await
+    }
+}
+
+impl PartialEq for UploadFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.size == other.size
+            && Arc::ptr_eq(&self.engine, &other.engine)
+    }
+}
+
+impl fmt::Debug for UploadFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadFile")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field("mime_type", &self.mime_type)
+            .finish()
+    }
+}
+
+/// Why a file was not added to a [`FileUpload`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileError {
+    /// The file type does not match `accept`.
+    InvalidType,
+    TooLarge,
+    TooSmall,
+    /// Adding the file would exceed `max_files`.
+    TooMany,
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileError::InvalidType => write!(f, "the file type is not accepted"),
+            FileError::TooLarge => write!(f, "the file is too large"),
+            FileError::TooSmall => write!(f, "the file is too small"),
+            FileError::TooMany => write!(f, "too many files"),
+        }
+    }
+}
+
+/// A file that was not added, with every reason it was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedFile {
+    pub file: UploadFile,
+    pub errors: Vec<FileError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rules {
+    accept: Vec<String>,
+    max_size: Option<u64>,
+    min_size: Option<u64>,
+    max_files: Option<usize>,
+    multiple: bool,
+    disabled: bool,
+}
+
+impl Rules {
+    // accept 的寫法與 <input accept> 相同：副檔名、MIME 或 type/*
+    fn accepts(&self, file: &UploadFile) -> bool {
+        if self.accept.is_empty() {
+            return true;
+        }
+        let name = file.name.to_lowercase();
+        let mime = file.mime_type.as_deref().unwrap_or_default().to_lowercase();
+        self.accept.iter().any(|pattern| {
+            let pattern = pattern.trim().to_lowercase();
+            if pattern.starts_with('.') {
+                name.ends_with(&pattern)
+            } else if let Some(prefix) = pattern.strip_suffix("/*") {
+                mime.split('/').next() == Some(prefix)
+            } else {
+                mime == pattern
+            }
+        })
+    }
+
+    fn errors(&self, file: &UploadFile) -> Vec<FileError> {
+        let mut errors = Vec::new();
+        if !self.accepts(file) {
+            errors.push(FileError::InvalidType);
+        }
+        if self.max_size.is_some_and(|max| file.size > max) {
+            errors.push(FileError::TooLarge);
+        }
+        if self.min_size.is_some_and(|min| file.size < min) {
+            errors.push(FileError::TooSmall);
+        }
+        errors
+    }
+
+    fn limit(&self) -> Option<usize> {
+        if self.multiple {
+            self.max_files
+        } else {
+            Some(1)
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FileUploadContext {
+    files: Memo<Vec<UploadFile>>,
+    set_files: Callback<Vec<UploadFile>>,
+    on_reject: Callback<Vec<RejectedFile>>,
+    rules: Signal<Rules>,
+    dragging: Signal<bool>,
+    input: Signal<Option<Rc<MountedData>>>,
+    input_id: Signal<String>,
+}
+
+impl FileUploadContext {
+    fn add(&self, engine: Arc<dyn FileEngine>) {
+        let ctx = *self;
+        spawn(async move {
+            let mut incoming = Vec::new();
+            for name in engine.files() {
+                let size = engine.file_size(&name).await.unwrap_or_default();
+                let mime_type = mime_type(&engine, &name).await;
+                incoming.push(UploadFile {
+                    name,
+                    size,
+                    mime_type,
+                    engine: engine.clone(),
+                });
+            }
+            ctx.accept(incoming);
+        });
+    }
+
+    fn accept(&self, incoming: Vec<UploadFile>) {
+        let rules = self.rules.peek().clone();
+        if rules.disabled {
+            return;
+        }
+        let limit = rules.limit();
+        // 只能選一個檔案時，新的檔案取代舊的
+        let mut files = if rules.multiple {
+            self.files.peek().clone()
+        } else {
+            Vec::new()
+        };
+        let mut rejected = Vec::new();
+        for file in incoming {
+            let mut errors = rules.errors(&file);
+            if errors.is_empty() && limit.is_some_and(|limit| files.len() >= limit) {
+                errors.push(FileError::TooMany);
+            }
+            if errors.is_empty() {
+                files.push(file);
+            } else {
+                rejected.push(RejectedFile { file, errors });
+            }
+        }
+        if files != *self.files.peek() {
+            self.set_files.call(files);
+        }
+        if !rejected.is_empty() {
+            self.on_reject.call(rejected);
+        }
+    }
+
+    fn remove(&self, file: &UploadFile) {
+        let mut files = self.files.peek().clone();
+        files.retain(|other| other != file);
+        self.set_files.call(files);
+    }
+
+    fn open_dialog(&self) {
+        if self.rules.peek().disabled {
+            return;
+        }
+        #[cfg(feature = "web")]
+        if let Some(input) = self.input.peek().as_deref() {
+            web::click(input);
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+async fn mime_type(engine: &Arc<dyn FileEngine>, name: &str) -> Option<String> {
+    let native = engine.get_native_file(name).await?;
+    let file = native.downcast::<web_sys::File>().ok()?;
+    let mime = file.type_();
+    if mime.is_empty() {
+        guess_mime_type(name)
+    } else {
+        Some(mime)
+    }
+}
+
+#[cfg(not(feature = "web"))]
+async fn mime_type(_engine: &Arc<dyn FileEngine>, name: &str) -> Option<String> {
+    guess_mime_type(name)
+}
+
+// 平台沒有提供 MIME 時，從常見的副檔名推測
+fn guess_mime_type(name: &str) -> Option<String> {
+    let extension = name.rsplit_once('.')?.1.to_lowercase();
+    let mime = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "avif" => "image/avif",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => return None,
+    };
+    Some(mime.to_string())
+}
+
+/// The root of a file upload, owning the list of files.
+///
+/// Pass `files` and `on_files_change` to control the list, or let the
+/// upload manage it. Without `multiple`, a new file replaces the current
+/// one.
+#[component]
+pub fn FileUpload(
+    files: Option<Vec<UploadFile>>,
+    on_files_change: Option<EventHandler<Vec<UploadFile>>>,
+    /// Called with the files that failed validation.
+    on_files_reject: Option<EventHandler<Vec<RejectedFile>>>,
+    /// Accepted types, as in `<input accept>`: extensions like `.pdf`, MIME
+    /// types like `image/png`, or `image/*`. Empty accepts every file.
+    #[props(default)]
+    accept: Vec<String>,
+    /// The largest accepted file, in bytes.
+    max_size: Option<u64>,
+    /// The smallest accepted file, in bytes.
+    min_size: Option<u64>,
+    max_files: Option<usize>,
+    #[props(default = true)] multiple: bool,
+    #[props(default)] disabled: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (files, set_files) = use_controllable_state(files, Vec::new, on_files_change);
+    let on_reject = use_callback(move |rejected: Vec<RejectedFile>| {
+        if let Some(handler) = on_files_reject {
+            handler.call(rejected);
+        }
+    });
+    let rules = use_synced_signal(Rules {
+        accept,
+        max_size,
+        min_size,
+        max_files,
+        multiple,
+        disabled,
+    });
+    let input_id = use_id();
+    let ctx = use_context_provider(|| FileUploadContext {
+        files,
+        set_files,
+        on_reject,
+        rules,
+        dragging: Signal::new(false),
+        input: Signal::new(None),
+        input_id: Signal::new(input_id),
+    });
+
+    let own_attributes = vec![
+        Attribute::new("data-dragging", (ctx.dragging)().then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An area that accepts dropped files, and opens the file dialog when
+/// clicked or activated with Enter or Space. It is `data-dragging` while
+/// files are dragged over it.
+#[component]
+pub fn FileUploadDropzone(
+    /// Open the file dialog on click and on Enter or Space.
+    #[props(default = true)]
+    open_on_click: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<FileUploadContext>();
+    let mut dragging = ctx.dragging;
+    // dragenter 與 dragleave 也會在子元素之間觸發，計數到零才算離開
+    let mut depth = use_signal(|| 0usize);
+    let disabled = ctx.rules.read().disabled;
+
+    use_effect(use_reactive!(|(disabled,)| {
+        if disabled {
+            depth.set(0);
+            dragging.set(false);
+        }
+    }));
+
+    let mut own_attributes = vec![
+        Attribute::new("data-dragging", dragging().then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+    ];
+    if open_on_click {
+        own_attributes.extend([
+            Attribute::new("role", "button", None, false),
+            Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+            Attribute::new("aria-controls", (ctx.input_id)(), None, false),
+            dioxus_elements::events::onclick(move |_| ctx.open_dialog()),
+            dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+                if event.key() == Key::Enter || event.key() == Key::Character(" ".into()) {
+                    event.prevent_default();
+                    ctx.open_dialog();
+                }
+            }),
+        ]);
+    }
+    if !disabled {
+        own_attributes.extend([
+            dioxus_elements::events::ondragenter(move |event: DragEvent| {
+                event.prevent_default();
+                let next = *depth.peek() + 1;
+                depth.set(next);
+                dragging.set(true);
+            }),
+            // 必須阻止 dragover 的預設行為，瀏覽器才允許放下
+            dioxus_elements::events::ondragover(move |event: DragEvent| {
+                event.prevent_default();
+            }),
+            dioxus_elements::events::ondragleave(move |_| {
+                let next = depth.peek().saturating_sub(1);
+                depth.set(next);
+                if next == 0 {
+                    dragging.set(false);
+                }
+            }),
+            dioxus_elements::events::ondrop(move |event: DragEvent| {
+                event.prevent_default();
+                depth.set(0);
+                dragging.set(false);
+                if let Some(engine) = event.files() {
+                    ctx.add(engine);
+                }
+            }),
+        ]);
+    }
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button that opens the file dialog.
+#[component]
+pub fn FileUploadTrigger(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<FileUploadContext>();
+    let disabled = ctx.rules.read().disabled;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("aria-controls", (ctx.input_id)(), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onclick(move |_| ctx.open_dialog()),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The native file input the dialog is opened from. It is hidden, but
+/// required for [`FileUploadTrigger`] and [`FileUploadDropzone`] to open
+/// the dialog; give it a `name` to submit the files with a form.
+#[component]
+pub fn FileUploadHiddenInput(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<FileUploadContext>();
+    let mut input = ctx.input;
+    let rules = ctx.rules.read().clone();
+
+    let own_attributes = vec![
+        Attribute::new("id", (ctx.input_id)(), None, false),
+        Attribute::new("type", "file", None, false),
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new("aria-hidden", "true", None, false),
+        Attribute::new("style", "display: none;", None, false),
+        Attribute::new("multiple", rules.multiple, None, false),
+        Attribute::new("disabled", rules.disabled, None, false),
+        Attribute::new(
+            "accept",
+            (!rules.accept.is_empty()).then(|| rules.accept.join(",")),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            input.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onchange(move |event: FormEvent| {
+            if let Some(engine) = event.files() {
+                ctx.add(engine);
+            }
+            // 清空輸入框，再次選同一個檔案時才會觸發 change
+            #[cfg(feature = "web")]
+            if let Some(input) = ctx.input.peek().as_deref() {
+                web::clear(input);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ItemContext {
+    file: Signal<UploadFile>,
+}
+
+/// Renders a [`FileUploadItem`] for every file in the list, inside a `ul`.
+///
+/// Pass `render` to render each item's content yourself; by default it is
+/// the file name and a [`FileUploadItemDelete`] button labelled "Remove".
+#[component]
+pub fn FileUploadList(
+    render: Option<Callback<UploadFile, Element>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<FileUploadContext>();
+    let files = (ctx.files)();
+
+    rsx! {
+        ul {
+            ..attributes,
+            for file in files {
+                FileUploadItem { key: "{file.name}-{file.size}", file: file.clone(),
+                    {match render {
+                        Some(render) => render.call(file.clone()),
+                        None => rsx! {
+                            span { "{file.name}" }
+                            FileUploadItemDelete { "Remove" }
+                        },
+                    }}
+                }
+            }
+        }
+    }
+}
+
+/// A file of the list, rendered as an `li`; see [`FileUploadList`].
+#[component]
+pub fn FileUploadItem(
+    file: UploadFile,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let file_signal = use_synced_signal(file.clone());
+    use_context_provider(|| ItemContext { file: file_signal });
+
+    let own_attributes = vec![Attribute::new(
+        "data-type",
+        file.mime_type.clone(),
+        None,
+        false,
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        li {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button that removes its [`FileUploadItem`]'s file from the list.
+#[component]
+pub fn FileUploadItemDelete(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<FileUploadContext>();
+    let item = use_context::<ItemContext>();
+    let name = item.file.read().name.clone();
+    let disabled = ctx.rules.read().disabled;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("aria-label", format!("Remove {name}"), None, false),
+        dioxus_elements::events::onclick(move |_| {
+            let file = item.file.peek().clone();
+            ctx.remove(&file);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use dioxus::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    // 程式觸發 click 時，瀏覽器仍把它當成使用者開啟對話框的手勢
+    pub(super) fn click(node: &MountedData) {
+        if let Some(element) = crate::primitives::dom::web_element(node)
+            .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            element.click();
+        }
+    }
+
+    pub(super) fn clear(node: &MountedData) {
+        if let Some(element) = crate::primitives::dom::web_element(node) {
+            let _ = js_sys::Reflect::set(&element, &"value".into(), &"".into());
+        }
+    }
+}
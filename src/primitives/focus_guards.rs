@@ -0,0 +1,102 @@
+//! FocusGuards Primitive
+//!
+//! Injects a focusable sentinel at the start and the end of `document.body`
+//! while any modal layer is open. Without them, Tab from the last element of
+//! a modal placed at the end of the body moves focus to the browser chrome,
+//! where a [`FocusScope`](super::focus_scope::FocusScope) can no longer pull
+//! it back, and screen-reader virtual cursors walk straight out of the page.
+//!
+//! The guards are shared and reference counted: they are inserted by the
+//! first open layer and removed when the last one closes. They require the
+//! `web` feature; elsewhere this is a no-op.
+
+use dioxus::prelude::*;
+
+/// Keep the focus guards in the document while the calling component is
+/// mounted.
+pub fn use_focus_guards() {
+    #[cfg(feature = "web")]
+    {
+        use_hook(web::acquire);
+        use_drop(web::release);
+    }
+}
+
+/// Keeps the focus guards in the document while it is rendered; see
+/// [`use_focus_guards`].
+#[component]
+pub fn FocusGuards(children: Element) -> Element {
+    use_focus_guards();
+
+    rsx! { {children} }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use crate::primitives::dom::document;
+    use std::cell::Cell;
+    use wasm_bindgen::JsCast;
+
+    const GUARD_ATTRIBUTE: &str = "data-primitive-focus-guard";
+    const GUARD_STYLE: &str = "outline: none; opacity: 0; position: fixed; pointer-events: none; width: 1px; height: 1px;";
+
+    thread_local! {
+        static COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn guards(document: &web_sys::Document) -> Vec<web_sys::Element> {
+        let Ok(nodes) = document.query_selector_all(&format!("[{GUARD_ATTRIBUTE}]")) else {
+            return Vec::new();
+        };
+        (0..nodes.length())
+            .filter_map(|index| nodes.item(index))
+            .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+            .collect()
+    }
+
+    fn create_guard(document: &web_sys::Document) -> Option<web_sys::Element> {
+        let guard = document.create_element("span").ok()?;
+        let _ = guard.set_attribute(GUARD_ATTRIBUTE, "");
+        let _ = guard.set_attribute("tabindex", "0");
+        let _ = guard.set_attribute("style", GUARD_STYLE);
+        Some(guard)
+    }
+
+    pub(super) fn acquire() {
+        COUNT.with(|count| count.set(count.get() + 1));
+
+        let Some(document) = document() else {
+            return;
+        };
+        let Some(body) = document.body() else {
+            return;
+        };
+
+        // 每次開啟都把守衛移回 body 的兩端，後來插入的 portal 才會被包在中間
+        let mut existing = guards(&document).into_iter();
+        let first = existing.next().or_else(|| create_guard(&document));
+        let last = existing.next().or_else(|| create_guard(&document));
+        if let Some(first) = first {
+            let _ = body.insert_adjacent_element("afterbegin", &first);
+        }
+        if let Some(last) = last {
+            let _ = body.insert_adjacent_element("beforeend", &last);
+        }
+    }
+
+    pub(super) fn release() {
+        let remaining = COUNT.with(|count| {
+            let remaining = count.get().saturating_sub(1);
+            count.set(remaining);
+            remaining
+        });
+        if remaining > 0 {
+            return;
+        }
+        if let Some(document) = document() {
+            for guard in guards(&document) {
+                guard.remove();
+            }
+        }
+    }
+}
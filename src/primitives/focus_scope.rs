@@ -0,0 +1,303 @@
+//! FocusScope Primitive
+//!
+//! Manages focus for the content of modal overlays: focus moves into the
+//! scope when it mounts, can be trapped and looped within it while mounted,
+//! and returns to the previously focused element when it unmounts. Only the
+//! most recently mounted trapped scope holds on to focus, so scopes can be
+//! nested, e.g. a dialog opened from a dialog. Focus management requires the
+//! `web` feature; elsewhere the scope only renders its content.
+
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Fired when a [`FocusScope`] is about to move focus automatically.
+///
+/// Call [`prevent_default`](Self::prevent_default) to keep focus where it is,
+/// e.g. to focus a specific element yourself.
+#[derive(Debug, Clone, Default)]
+pub struct AutoFocusEvent {
+    prevented: Rc<Cell<bool>>,
+}
+
+impl AutoFocusEvent {
+    pub fn prevent_default(&self) {
+        self.prevented.set(true);
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevented.get()
+    }
+}
+
+#[component]
+pub fn FocusScope(
+    /// Keep focus inside the scope: focus leaving it is moved back.
+    #[props(default)]
+    trapped: bool,
+    /// Tab on the last tabbable element focuses the first one, and
+    /// Shift+Tab on the first focuses the last.
+    #[props(default)]
+    loop_focus: bool,
+    /// Fired on mount, before focus moves into the scope.
+    on_mount_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on unmount, before focus returns to the previously focused element.
+    on_unmount_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    #[cfg(feature = "web")]
+    let state = {
+        let state = use_hook(|| Rc::new(std::cell::RefCell::new(web::ScopeState::default())));
+        // trapped 可能在掛載後改變，監聽器讀取的是最新的值
+        state.borrow_mut().trapped = trapped;
+
+        use_drop({
+            let state = state.clone();
+            move || web::unmount(&state, on_unmount_auto_focus)
+        });
+
+        state
+    };
+    #[cfg(not(feature = "web"))]
+    let _ = (trapped, on_mount_auto_focus, on_unmount_auto_focus);
+
+    let own_attributes = vec![
+        Attribute::new("tabindex", "-1", None, false),
+        dioxus_elements::events::onmounted({
+            #[cfg(feature = "web")]
+            let state = state.clone();
+            move |event: MountedEvent| {
+                #[cfg(feature = "web")]
+                web::mount(&state, &event.data(), on_mount_auto_focus);
+                #[cfg(not(feature = "web"))]
+                let _ = event;
+            }
+        }),
+        dioxus_elements::events::onkeydown({
+            #[cfg(feature = "web")]
+            let state = state.clone();
+            move |event: KeyboardEvent| {
+                #[cfg(feature = "web")]
+                web::handle_tab(&state, &event, loop_focus);
+                #[cfg(not(feature = "web"))]
+                let _ = (event, loop_focus);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::AutoFocusEvent;
+    use crate::primitives::dom::{
+        EventListener, active_element, contains, document, focus, tabbable_elements, web_element,
+    };
+    use dioxus::prelude::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::JsCast;
+
+    thread_local! {
+        // 已掛載的範圍，依掛載順序排列；只有最上層被困住的範圍會拉回焦點，
+        // 讓從對話框開啟的對話框不會與其競爭焦點
+        static SCOPES: RefCell<Vec<Rc<RefCell<ScopeState>>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // 呼叫時 state 可能已被借用，因此不讀取它本身的 trapped
+    fn is_topmost_trapped(state: &Rc<RefCell<ScopeState>>) -> bool {
+        SCOPES.with(|scopes| {
+            scopes
+                .borrow()
+                .iter()
+                .rev()
+                .find(|scope| {
+                    Rc::ptr_eq(scope, state) || scope.try_borrow().is_ok_and(|scope| scope.trapped)
+                })
+                .is_some_and(|scope| Rc::ptr_eq(scope, state))
+        })
+    }
+
+    #[derive(Default)]
+    pub(super) struct ScopeState {
+        pub(super) trapped: bool,
+        container: Option<web_sys::Element>,
+        previously_focused: Option<web_sys::Element>,
+        last_focused: Option<web_sys::Element>,
+        listeners: Vec<EventListener>,
+    }
+
+    pub(super) fn mount(
+        state: &Rc<RefCell<ScopeState>>,
+        node: &MountedData,
+        on_mount_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    ) {
+        let Some(container) = web_element(node) else {
+            return;
+        };
+
+        {
+            let mut state = state.borrow_mut();
+            state.previously_focused = active_element();
+            state.container = Some(container.clone());
+        }
+        SCOPES.with(|scopes| scopes.borrow_mut().push(state.clone()));
+
+        let already_inside =
+            active_element().is_some_and(|active| container.contains(Some(active.unchecked_ref())));
+
+        if !already_inside {
+            let event = AutoFocusEvent::default();
+            if let Some(handler) = on_mount_auto_focus {
+                handler.call(event.clone());
+            }
+            if !event.is_default_prevented() {
+                focus_first(&container);
+            }
+        }
+
+        // 焦點移出範圍時，若被困住則移回範圍內
+        if let Some(document) = document() {
+            let listener_state = state.clone();
+            let listener = EventListener::new(&document, "focusin", false, move |event| {
+                let Some(target) = event.target() else {
+                    return;
+                };
+                let mut state = listener_state.borrow_mut();
+                let Some(container) = state.container.clone() else {
+                    return;
+                };
+
+                if contains(&container, &target) {
+                    state.last_focused = target.dyn_into::<web_sys::Element>().ok();
+                } else if state.trapped && is_topmost_trapped(&listener_state) {
+                    let last_focused = state.last_focused.clone();
+                    drop(state);
+                    match last_focused {
+                        Some(element) => focus(&element),
+                        None => focus_first(&container),
+                    }
+                }
+            });
+            state.borrow_mut().listeners.push(listener);
+        }
+    }
+
+    pub(super) fn unmount(
+        state: &Rc<RefCell<ScopeState>>,
+        on_unmount_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    ) {
+        let previously_focused = {
+            let mut state = state.borrow_mut();
+            state.listeners.clear();
+            state.container = None;
+            state.previously_focused.take()
+        };
+        SCOPES.with(|scopes| {
+            scopes
+                .borrow_mut()
+                .retain(|scope| !Rc::ptr_eq(scope, state))
+        });
+
+        let event = AutoFocusEvent::default();
+        if let Some(handler) = on_unmount_auto_focus {
+            handler.call(event.clone());
+        }
+        if event.is_default_prevented() {
+            return;
+        }
+
+        // 觸發元素已不存在時，移回上一層範圍（例如開啟它的對話框），而不是頁面
+        let parent = SCOPES.with(|scopes| scopes.borrow().last().cloned());
+        let parent = parent.and_then(|parent| {
+            let parent = parent.borrow();
+            let container = parent.container.clone()?;
+            Some((container, parent.last_focused.clone()))
+        });
+        match (previously_focused, parent) {
+            (Some(element), _) if element.is_connected() => focus(&element),
+            (_, Some((_, Some(last_focused)))) if last_focused.is_connected() => {
+                focus(&last_focused)
+            }
+            (_, Some((container, _))) => focus_first(&container),
+            _ => {
+                if let Some(body) = document().and_then(|document| document.body()) {
+                    let _ = body.focus();
+                }
+            }
+        }
+    }
+
+    pub(super) fn handle_tab(
+        state: &Rc<RefCell<ScopeState>>,
+        event: &KeyboardEvent,
+        loop_focus: bool,
+    ) {
+        let modifiers = event.modifiers();
+        if event.key() != Key::Tab || modifiers.alt() || modifiers.ctrl() || modifiers.meta() {
+            return;
+        }
+
+        let (container, trapped) = {
+            let state = state.borrow();
+            (state.container.clone(), state.trapped)
+        };
+        if !(loop_focus || trapped) {
+            return;
+        }
+        let Some(container) = container else {
+            return;
+        };
+
+        let tabbables = tabbable_elements(&container);
+        let (Some(first), Some(last)) = (tabbables.first(), tabbables.last()) else {
+            // 沒有可聚焦的元素時，讓焦點留在容器上
+            event.prevent_default();
+            return;
+        };
+        let Some(active) = active_element() else {
+            return;
+        };
+
+        if modifiers.shift() && (active == *first || active == container) {
+            event.prevent_default();
+            if loop_focus {
+                focus(last);
+            }
+        } else if !modifiers.shift() && active == *last {
+            event.prevent_default();
+            if loop_focus {
+                focus(first);
+            }
+        }
+    }
+
+    // 優先聚焦第一個非連結的可聚焦元素，否則聚焦容器本身
+    fn focus_first(container: &web_sys::Element) {
+        let tabbables = tabbable_elements(container);
+        let candidate = tabbables
+            .iter()
+            .find(|element| element.tag_name() != "A")
+            .or(tabbables.first());
+
+        match candidate {
+            Some(element) => focus(element),
+            None => focus(container),
+        }
+    }
+}
@@ -0,0 +1,539 @@
+//! Form Primitive
+//!
+//! Forms validated with the browser's constraint validation (`required`,
+//! `type="email"`, `min`, `pattern`, ...) and custom validators, with an
+//! error message per failed constraint. The browser's own error bubbles are
+//! replaced by [`FormMessage`]s, which are wired to their control with
+//! `aria-describedby`, and invalid controls get `aria-invalid`.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Form { on_submit: move |event| save(event),
+//!         FormField { name: "email",
+//!             FormLabel { "Email" }
+//!             FormControl { r#type: "email", required: true }
+//!             FormMessage { r#match: ValidityMatcher::ValueMissing, "Enter your email" }
+//!             FormMessage { r#match: ValidityMatcher::TypeMismatch, "Enter a valid email" }
+//!         }
+//!         FormSubmit { "Sign up" }
+//!     }
+//! }
+//! ```
+//!
+//! Native constraints are only checked with the `web` feature; custom
+//! validators work everywhere.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_id::use_id;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The outcome of validating a control, following the DOM's
+/// `ValidityState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Validity {
+    pub bad_input: bool,
+    pub pattern_mismatch: bool,
+    pub range_overflow: bool,
+    pub range_underflow: bool,
+    pub step_mismatch: bool,
+    pub too_long: bool,
+    pub too_short: bool,
+    pub type_mismatch: bool,
+    pub value_missing: bool,
+    /// A custom validator of a [`FormMessage`] matched.
+    pub custom_error: bool,
+}
+
+impl Validity {
+    /// Whether no constraint failed.
+    pub fn valid(&self) -> bool {
+        !(self.bad_input
+            || self.pattern_mismatch
+            || self.range_overflow
+            || self.range_underflow
+            || self.step_mismatch
+            || self.too_long
+            || self.too_short
+            || self.type_mismatch
+            || self.value_missing
+            || self.custom_error)
+    }
+}
+
+/// When a [`FormMessage`] is shown.
+#[derive(Clone, PartialEq)]
+pub enum ValidityMatcher {
+    BadInput,
+    PatternMismatch,
+    RangeOverflow,
+    RangeUnderflow,
+    StepMismatch,
+    TooLong,
+    TooShort,
+    TypeMismatch,
+    ValueMissing,
+    /// While the field is valid, e.g. for a success message.
+    Valid,
+    /// A custom validator, called with the control's value. Returning `true`
+    /// shows the message and makes the field invalid.
+    Custom(Callback<String, bool>),
+}
+
+impl ValidityMatcher {
+    fn matches(&self, validity: &Validity, custom_errors: &[usize], id: usize) -> bool {
+        match self {
+            ValidityMatcher::BadInput => validity.bad_input,
+            ValidityMatcher::PatternMismatch => validity.pattern_mismatch,
+            ValidityMatcher::RangeOverflow => validity.range_overflow,
+            ValidityMatcher::RangeUnderflow => validity.range_underflow,
+            ValidityMatcher::StepMismatch => validity.step_mismatch,
+            ValidityMatcher::TooLong => validity.too_long,
+            ValidityMatcher::TooShort => validity.too_short,
+            ValidityMatcher::TypeMismatch => validity.type_mismatch,
+            ValidityMatcher::ValueMissing => validity.value_missing,
+            ValidityMatcher::Valid => validity.valid(),
+            ValidityMatcher::Custom(_) => custom_errors.contains(&id),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FormContext {
+    fields: Signal<Vec<FieldContext>>,
+}
+
+#[derive(Clone, Copy)]
+struct FieldContext {
+    id: usize,
+    control: Signal<Option<Rc<MountedData>>>,
+    value: Signal<String>,
+    // None 表示尚未驗證過
+    validity: Signal<Option<Validity>>,
+    server_invalid: Signal<bool>,
+    validators: Signal<Vec<(usize, Callback<String, bool>)>>,
+    // 目前符合的自訂驗證，對應 validators 的 id
+    custom_errors: Signal<Vec<usize>>,
+    message_ids: Signal<Vec<(usize, String)>>,
+}
+
+impl FieldContext {
+    fn validate(&mut self) -> bool {
+        let value = self.value.peek().clone();
+        let custom_errors: Vec<usize> = self
+            .validators
+            .peek()
+            .iter()
+            .filter(|(_, validator)| validator.call(value.clone()))
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut validity = self
+            .control
+            .peek()
+            .as_deref()
+            .and_then(native_validity)
+            .unwrap_or_default();
+        validity.custom_error = !custom_errors.is_empty();
+
+        self.custom_errors.set(custom_errors);
+        self.validity.set(Some(validity));
+        validity.valid()
+    }
+
+    fn reset(&mut self) {
+        self.validity.set(None);
+        self.custom_errors.set(Vec::new());
+    }
+
+    fn invalid(&self) -> bool {
+        (self.server_invalid)()
+            || self
+                .validity
+                .read()
+                .is_some_and(|validity| !validity.valid())
+    }
+
+    fn state_attributes(&self) -> Vec<Attribute> {
+        let invalid = self.invalid();
+        let valid = !invalid && self.validity.read().is_some();
+        vec![
+            Attribute::new("data-invalid", invalid.then_some(""), None, false),
+            Attribute::new("data-valid", valid.then_some(""), None, false),
+        ]
+    }
+}
+
+#[cfg(feature = "web")]
+fn native_validity(node: &MountedData) -> Option<Validity> {
+    web::validity(node)
+}
+
+// 沒有 web 功能時只檢查自訂驗證
+#[cfg(not(feature = "web"))]
+fn native_validity(_node: &MountedData) -> Option<Validity> {
+    None
+}
+
+/// A form whose fields are validated on submit. `on_submit` is only called
+/// once every field is valid; otherwise the first invalid control is
+/// focused.
+#[component]
+pub fn Form(
+    on_submit: Option<EventHandler<FormEvent>>,
+    /// Called on submit and reset, to clear the errors reported by the
+    /// server through [`FormField`]'s `server_invalid`.
+    on_clear_server_errors: Option<EventHandler<()>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context_provider(|| FormContext {
+        fields: Signal::new(Vec::new()),
+    });
+
+    let own_attributes = vec![
+        dioxus_elements::events::onsubmit(move |event: FormEvent| {
+            if let Some(handler) = on_clear_server_errors {
+                handler.call(());
+            }
+            let mut first_invalid = None;
+            for mut field in ctx.fields.peek().iter().copied() {
+                if !field.validate() && first_invalid.is_none() {
+                    first_invalid = field.control.peek().clone();
+                }
+            }
+            let Some(control) = first_invalid else {
+                if let Some(handler) = on_submit {
+                    handler.call(event);
+                }
+                return;
+            };
+            event.prevent_default();
+            spawn(async move {
+                let _ = control.set_focus(true).await;
+            });
+        }),
+        dioxus_elements::events::onreset(move |_: FormEvent| {
+            if let Some(handler) = on_clear_server_errors {
+                handler.call(());
+            }
+            for mut field in ctx.fields.peek().iter().copied() {
+                field.reset();
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        form {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+static NEXT_FIELD_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A field of the form, grouping a control with its label and messages.
+#[component]
+pub fn FormField(
+    /// The name of the control, as submitted with the form.
+    name: String,
+    /// Mark the field invalid, e.g. after the server rejected its value.
+    #[props(default)]
+    server_invalid: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let form = try_use_context::<FormContext>();
+    let control_id = use_id();
+    let server_invalid_signal = use_synced_signal(server_invalid);
+    let field = use_context_provider(|| FieldContext {
+        id: NEXT_FIELD_ID.fetch_add(1, Ordering::Relaxed),
+        control: Signal::new(None),
+        value: Signal::new(String::new()),
+        validity: Signal::new(None),
+        server_invalid: server_invalid_signal,
+        validators: Signal::new(Vec::new()),
+        custom_errors: Signal::new(Vec::new()),
+        message_ids: Signal::new(Vec::new()),
+    });
+    use_context_provider(|| FieldNames { name, control_id });
+
+    use_effect(move || {
+        if let Some(mut form) = form {
+            form.fields.write().push(field);
+        }
+    });
+    use_drop(move || {
+        if let Some(mut form) = form {
+            form.fields.write().retain(|other| other.id != field.id);
+        }
+    });
+
+    let attributes = merge_attributes(field.state_attributes(), attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Clone)]
+struct FieldNames {
+    name: String,
+    control_id: String,
+}
+
+/// The label of the field's control.
+#[component]
+pub fn FormLabel(
+    /// Render your own element instead of a `label`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let field = use_context::<FieldContext>();
+    let names = use_context::<FieldNames>();
+
+    let mut own_attributes = field.state_attributes();
+    own_attributes.push(Attribute::new("for", names.control_id, None, false));
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        label {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The field's control. Validated when it changes and on submit; typing
+/// clears the errors until the next validation.
+///
+/// Pass the constraints as attributes, e.g. `required: true` or
+/// `r#type: "email"`.
+#[component]
+pub fn FormControl(
+    /// Render your own element instead of an `input`, e.g. a `textarea`;
+    /// see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let form = try_use_context::<FormContext>();
+    let mut field = use_context::<FieldContext>();
+    let names = use_context::<FieldNames>();
+
+    let invalid = field.invalid();
+    let described_by = field
+        .message_ids
+        .read()
+        .iter()
+        .map(|(_, id)| id.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut own_attributes = field.state_attributes();
+    own_attributes.extend([
+        Attribute::new("id", names.control_id, None, false),
+        Attribute::new("name", names.name, None, false),
+        Attribute::new("aria-invalid", invalid.then_some("true"), None, false),
+        Attribute::new(
+            "aria-describedby",
+            (!described_by.is_empty()).then_some(described_by),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            field.control.set(Some(event.data()))
+        }),
+        dioxus_elements::events::oninput(move |event: FormEvent| {
+            field.value.set(event.value());
+            field.reset();
+        }),
+        dioxus_elements::events::onchange(move |event: FormEvent| {
+            field.value.set(event.value());
+            field.validate();
+        }),
+        // 以訊息取代瀏覽器內建的錯誤提示
+        dioxus_elements::events::oninvalid(move |event: FormEvent| {
+            event.prevent_default();
+            field.validate();
+            // 瀏覽器不再聚焦，改由第一個無效的欄位自行聚焦
+            let first_invalid = form.and_then(|form| {
+                form.fields
+                    .peek()
+                    .iter()
+                    .find(|other| {
+                        other
+                            .validity
+                            .peek()
+                            .is_some_and(|validity| !validity.valid())
+                    })
+                    .map(|other| other.id)
+            });
+            if first_invalid == Some(field.id) {
+                let control = field.control.peek().clone();
+                if let Some(control) = control {
+                    spawn(async move {
+                        let _ = control.set_focus(true).await;
+                    });
+                }
+            }
+        }),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+static NEXT_MESSAGE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// An error, or success, message of the field, shown while `match` matches
+/// its validity.
+#[component]
+pub fn FormMessage(
+    /// Defaults to any failed constraint.
+    r#match: Option<ValidityMatcher>,
+    /// Show the message regardless of the field's validity, e.g. for an
+    /// error from the server.
+    #[props(default)]
+    force_match: bool,
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut field = use_context::<FieldContext>();
+    let id = use_hook(|| NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed));
+    let element_id = use_id();
+
+    // 自訂驗證在欄位驗證時一併執行
+    let matcher = r#match.clone();
+    use_effect(use_reactive!(|(matcher,)| {
+        let mut validators = field.validators.write();
+        validators.retain(|(other, _)| *other != id);
+        if let Some(ValidityMatcher::Custom(validator)) = matcher {
+            validators.push((id, validator));
+        }
+    }));
+    use_drop(move || {
+        field.validators.write().retain(|(other, _)| *other != id);
+        field.message_ids.write().retain(|(other, _)| *other != id);
+    });
+
+    let shown = force_match
+        || match &r#match {
+            Some(matcher) => field.validity.read().is_some_and(|validity| {
+                matcher.matches(&validity, &field.custom_errors.read(), id)
+            }),
+            None => field.invalid(),
+        };
+
+    // 只有顯示中的訊息會加入 aria-describedby
+    let message_id = element_id.clone();
+    use_effect(use_reactive!(|(shown,)| {
+        let mut message_ids = field.message_ids.write();
+        message_ids.retain(|(other, _)| *other != id);
+        if shown {
+            message_ids.push((id, message_id.clone()));
+        }
+    }));
+
+    if !shown {
+        return rsx! {};
+    }
+
+    let own_attributes = vec![Attribute::new("id", element_id, None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Renders `render` with the field's validity, or `None` before it has
+/// been validated, for custom error displays.
+#[component]
+pub fn FormValidityState(render: Callback<Option<Validity>, Element>) -> Element {
+    let field = use_context::<FieldContext>();
+    render.call((field.validity)())
+}
+
+/// The submit button of the form.
+#[component]
+pub fn FormSubmit(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let own_attributes = vec![Attribute::new("type", "submit", None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::Validity;
+    use dioxus::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    // input、textarea 與 select 都有 validity 屬性，直接讀取即可
+    pub(super) fn validity(node: &MountedData) -> Option<Validity> {
+        let element = crate::primitives::dom::web_element(node)?;
+        let validity = js_sys::Reflect::get(&element, &"validity".into())
+            .ok()?
+            .dyn_into::<web_sys::ValidityState>()
+            .ok()?;
+        Some(Validity {
+            bad_input: validity.bad_input(),
+            pattern_mismatch: validity.pattern_mismatch(),
+            range_overflow: validity.range_overflow(),
+            range_underflow: validity.range_underflow(),
+            step_mismatch: validity.step_mismatch(),
+            too_long: validity.too_long(),
+            too_short: validity.too_short(),
+            type_mismatch: validity.type_mismatch(),
+            value_missing: validity.value_missing(),
+            custom_error: false,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+//! Hook Primitives
+//!
+//! This module provides the hooks shared by the primitives. They are also
+//! available to applications building their own components.
+
+pub mod use_after_paint;
+pub mod use_clipboard;
+pub mod use_controllable_state;
+pub mod use_debounce;
+pub mod use_event_callback;
+pub mod use_focus_outside;
+pub mod use_focus_visible;
+pub mod use_focus_within;
+#[cfg(feature = "web")]
+pub mod use_global_event;
+pub mod use_hover;
+pub mod use_id;
+pub mod use_intersection_observer;
+pub mod use_long_press;
+pub mod use_media_query;
+pub mod use_move;
+pub mod use_mutation_observer;
+pub mod use_pointer_down_outside;
+pub mod use_prefers_reduced_motion;
+pub mod use_press;
+pub mod use_rect;
+pub mod use_size;
+pub mod use_state_machine;
+#[cfg(feature = "web")]
+pub mod use_storage;
+pub mod use_swipe;
+pub mod use_timer;
+pub mod use_typeahead;
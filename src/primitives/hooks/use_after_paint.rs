@@ -0,0 +1,57 @@
+use super::use_event_callback::use_event_callback;
+use dioxus::prelude::*;
+
+/// Run `callback` once the current DOM changes have been committed and
+/// painted, e.g. to measure an element that was just rendered or to scroll
+/// it into view without a visible jump.
+///
+/// On the web this waits two animation frames; elsewhere it runs after the
+/// current render.
+pub fn after_paint(callback: impl FnOnce() + 'static) {
+    #[cfg(feature = "web")]
+    {
+        use dioxus::dioxus_core::{Runtime, RuntimeGuard};
+
+        let runtime = Runtime::current();
+        // 第一個 frame 在繪製前執行，第二個才在繪製之後
+        web::request_frame(move || {
+            web::request_frame(move || {
+                let _guard = runtime.map(RuntimeGuard::new);
+                callback();
+            });
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    spawn(async move { callback() });
+}
+
+/// Run `callback` after the component's first paint.
+///
+/// Call the returned [`Callback`] to run it again after the next paint,
+/// e.g. from an event handler or a `use_effect` that tracks the values the
+/// measurement depends on. `callback` always sees the latest props.
+pub fn use_after_paint(mut callback: impl FnMut() + 'static) -> Callback<()> {
+    let callback = use_event_callback(move |()| callback());
+    let schedule = use_callback(move |()| after_paint(move || callback.call(())));
+
+    // 只在掛載後執行一次：schedule 不是 signal，不會讓 effect 重新執行
+    use_effect(move || schedule.call(()));
+
+    schedule
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    // 沒有 window 時直接執行
+    pub(super) fn request_frame(callback: impl FnOnce() + 'static) {
+        let Some(window) = web_sys::window() else {
+            callback();
+            return;
+        };
+        let closure = Closure::once_into_js(callback);
+        let _ = window.request_animation_frame(closure.unchecked_ref());
+    }
+}
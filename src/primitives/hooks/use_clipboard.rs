@@ -0,0 +1,115 @@
+use super::use_timer::{Timer, use_timeout};
+use dioxus::prelude::*;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// Errors returned by [`Clipboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// No clipboard is available on this platform, or the required feature
+    /// (`web` or `desktop`) is not enabled.
+    Unavailable,
+    /// The platform refused the operation, e.g. because the page is not
+    /// focused or the user denied the permission.
+    Failed(String),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Unavailable => write!(f, "the clipboard is not available"),
+            ClipboardError::Failed(reason) => write!(f, "clipboard access failed: {}", reason),
+        }
+    }
+}
+
+impl Error for ClipboardError {}
+
+/// A handle to the system clipboard, returned by [`use_clipboard`].
+#[derive(Clone, Copy)]
+pub struct Clipboard {
+    copied: Signal<bool>,
+    reset: Timer,
+}
+
+impl Clipboard {
+    /// Write `text` to the clipboard and mark it as [`copied`](Self::copied).
+    pub async fn copy(&mut self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        write_text(text.into()).await?;
+        self.copied.set(true);
+        self.reset.start();
+        Ok(())
+    }
+
+    /// Read the text currently on the clipboard.
+    pub async fn read(&self) -> Result<String, ClipboardError> {
+        read_text().await
+    }
+
+    /// Whether text was copied recently, e.g. to show "Copied!" on a copy
+    /// button.
+    pub fn copied(&self) -> bool {
+        (self.copied)()
+    }
+}
+
+/// Read and write the system clipboard.
+///
+/// [`Clipboard::copied`] turns `true` after a successful copy and back to
+/// `false` after `reset_after`. Uses `navigator.clipboard` with the `web`
+/// feature and the native clipboard with the `desktop` feature.
+pub fn use_clipboard(reset_after: Duration) -> Clipboard {
+    let mut copied = use_signal(|| false);
+    let reset = use_timeout(reset_after, move || copied.set(false));
+    Clipboard { copied, reset }
+}
+
+#[cfg(feature = "web")]
+fn clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+    web_sys::window()
+        .map(|window| window.navigator().clipboard())
+        .ok_or(ClipboardError::Unavailable)
+}
+
+#[cfg(feature = "web")]
+async fn write_text(text: String) -> Result<(), ClipboardError> {
+    let promise = clipboard()?.write_text(&text);
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(|error| ClipboardError::Failed(format!("{:?}", error)))
+}
+
+#[cfg(feature = "web")]
+async fn read_text() -> Result<String, ClipboardError> {
+    let promise = clipboard()?.read_text();
+    let text = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|error| ClipboardError::Failed(format!("{:?}", error)))?;
+    Ok(text.as_string().unwrap_or_default())
+}
+
+#[cfg(all(feature = "desktop", not(feature = "web")))]
+async fn write_text(text: String) -> Result<(), ClipboardError> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|error| ClipboardError::Failed(error.to_string()))
+}
+
+#[cfg(all(feature = "desktop", not(feature = "web")))]
+async fn read_text() -> Result<String, ClipboardError> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|error| ClipboardError::Failed(error.to_string()))
+}
+
+#[cfg(not(any(feature = "web", feature = "desktop")))]
+async fn write_text(_text: String) -> Result<(), ClipboardError> {
+    Err(ClipboardError::Unavailable)
+}
+
+#[cfg(not(any(feature = "web", feature = "desktop")))]
+async fn read_text() -> Result<String, ClipboardError> {
+    Err(ClipboardError::Unavailable)
+}
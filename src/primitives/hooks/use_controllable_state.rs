@@ -0,0 +1,34 @@
+use crate::primitives::context::create_context::use_synced_signal;
+use dioxus::prelude::*;
+
+/// State that is either controlled by a prop or kept internally, e.g. the
+/// `open`/`default_open`/`on_open_change` props of an overlay.
+///
+/// While `value` is `Some`, it is the state and setting it only calls
+/// `on_change`; the owner is expected to pass the new value back. While it is
+/// `None`, the state starts as `default_value` and setting it updates the
+/// internal value as well. `on_change` is only called for actual changes.
+pub fn use_controllable_state<T: Clone + PartialEq + 'static>(
+    value: Option<T>,
+    default_value: impl FnOnce() -> T,
+    on_change: Option<EventHandler<T>>,
+) -> (Memo<T>, Callback<T>) {
+    let mut internal = use_signal(default_value);
+    let controlled = use_synced_signal(value);
+    let on_change = use_synced_signal(on_change);
+
+    let state = use_memo(move || controlled().unwrap_or_else(|| internal()));
+    let set_state = use_callback(move |next: T| {
+        if *state.peek() == next {
+            return;
+        }
+        if controlled.peek().is_none() {
+            internal.set(next.clone());
+        }
+        if let Some(on_change) = *on_change.peek() {
+            on_change.call(next);
+        }
+    });
+
+    (state, set_state)
+}
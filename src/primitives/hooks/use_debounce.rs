@@ -0,0 +1,182 @@
+use super::use_event_callback::use_event_callback;
+use crate::primitives::context::create_context::use_synced_signal;
+use crate::primitives::time::sleep;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// On which edges of a burst of calls a debounced or throttled callback
+/// runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edges {
+    /// Run on the first call of a burst.
+    pub leading: bool,
+    /// Run with the last arguments once the burst is over.
+    pub trailing: bool,
+}
+
+impl Edges {
+    /// Only at the end of a burst; the usual debounce.
+    pub const TRAILING: Edges = Edges {
+        leading: false,
+        trailing: true,
+    };
+    /// Only at the start of a burst.
+    pub const LEADING: Edges = Edges {
+        leading: true,
+        trailing: false,
+    };
+    /// At both ends; the usual throttle.
+    pub const BOTH: Edges = Edges {
+        leading: true,
+        trailing: true,
+    };
+}
+
+struct RateLimiter<T: 'static> {
+    scope: ScopeId,
+    wait: Signal<Duration>,
+    edges: Signal<Edges>,
+    callback: Callback<T>,
+    task: Signal<Option<Task>>,
+    // 等待期間收到的最後一組參數
+    pending: Signal<Option<T>>,
+}
+
+impl<T: 'static> Clone for RateLimiter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for RateLimiter<T> {}
+
+fn use_rate_limiter<T: 'static>(
+    wait: Duration,
+    edges: Edges,
+    callback: impl FnMut(T) + 'static,
+) -> RateLimiter<T> {
+    let callback = use_event_callback(callback);
+    RateLimiter {
+        scope: use_hook(|| {
+            current_scope_id().expect("rate limiters must be created in a component")
+        }),
+        // 改變只在下一輪等待時生效，不重新排程目前的計時
+        wait: use_synced_signal(wait),
+        edges: use_synced_signal(edges),
+        callback,
+        task: use_signal(|| None),
+        pending: use_signal(|| None),
+    }
+}
+
+impl<T: 'static> RateLimiter<T> {
+    fn is_waiting(&self) -> bool {
+        self.task.peek().is_some()
+    }
+
+    // 開始新的等待；結束時若有待處理的參數就以 trailing 呼叫
+    fn wait(&mut self, then_restart: bool) {
+        if let Some(task) = self.task.take() {
+            task.cancel();
+        }
+        let wait = *self.wait.peek();
+        let mut limiter = *self;
+        let task = self.scope.in_runtime(|| {
+            spawn(async move {
+                sleep(wait).await;
+                limiter.task.set(None);
+                let pending = limiter.pending.take();
+                if let Some(argument) = pending
+                    && limiter.edges.peek().trailing
+                {
+                    if then_restart {
+                        limiter.wait(true);
+                    }
+                    limiter.callback.call(argument);
+                }
+            })
+        });
+        self.task.set(Some(task));
+    }
+
+    fn debounce(&mut self, argument: T) {
+        if !self.is_waiting() && self.edges.peek().leading {
+            self.pending.set(None);
+            self.callback.call(argument);
+        } else {
+            self.pending.set(Some(argument));
+        }
+        self.wait(false);
+    }
+
+    fn throttle(&mut self, argument: T) {
+        if self.is_waiting() {
+            self.pending.set(Some(argument));
+            return;
+        }
+        if self.edges.peek().leading {
+            self.callback.call(argument);
+        } else {
+            self.pending.set(Some(argument));
+        }
+        self.wait(true);
+    }
+}
+
+/// Wrap `callback` so a burst of calls runs it once: by default with the
+/// last arguments, after no call has been made for `delay`.
+///
+/// Use `edges` to also or instead run it on the first call of the burst.
+pub fn use_debounced_callback<T: 'static>(
+    delay: Duration,
+    edges: Edges,
+    callback: impl FnMut(T) + 'static,
+) -> Callback<T> {
+    let mut limiter = use_rate_limiter(delay, edges, callback);
+    use_callback(move |argument: T| limiter.debounce(argument))
+}
+
+/// Wrap `callback` so it runs at most once every `interval`, e.g. for
+/// scroll or resize handlers.
+///
+/// With [`Edges::BOTH`], the first call runs immediately and the last call
+/// of a burst runs at the end of the interval.
+pub fn use_throttled_callback<T: 'static>(
+    interval: Duration,
+    edges: Edges,
+    callback: impl FnMut(T) + 'static,
+) -> Callback<T> {
+    let mut limiter = use_rate_limiter(interval, edges, callback);
+    use_callback(move |argument: T| limiter.throttle(argument))
+}
+
+/// A copy of `value` that only follows it once it has stopped changing for
+/// `delay`, e.g. the query of a search box.
+pub fn use_debounced<T: 'static + Clone + PartialEq>(
+    value: T,
+    delay: Duration,
+) -> ReadOnlySignal<T> {
+    let mut debounced = use_signal(|| value.clone());
+    let update = use_debounced_callback(delay, Edges::TRAILING, move |value: T| {
+        if *debounced.peek() != value {
+            debounced.set(value);
+        }
+    });
+    use_effect(use_reactive!(|(value,)| update.call(value)));
+    debounced.into()
+}
+
+/// A copy of `value` that follows it at most once every `interval`.
+pub fn use_throttled<T: 'static + Clone + PartialEq>(
+    value: T,
+    interval: Duration,
+) -> ReadOnlySignal<T> {
+    let mut throttled = use_signal(|| value.clone());
+    let update = use_throttled_callback(interval, Edges::BOTH, move |value: T| {
+        if *throttled.peek() != value {
+            throttled.set(value);
+        }
+    });
+    use_effect(use_reactive!(|(value,)| update.call(value)));
+    throttled.into()
+}
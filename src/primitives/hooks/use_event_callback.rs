@@ -0,0 +1,18 @@
+use dioxus::prelude::*;
+
+/// A [`Callback`] with a stable identity whose body is replaced on every
+/// render, so it always sees the latest props and captured values.
+///
+/// Pass it to long-lived listeners such as document events or timers:
+/// they are registered once and never call a stale closure.
+///
+/// ```rust,ignore
+/// let on_escape = use_event_callback(move |_: ()| on_close.call(()));
+/// use_hook(|| listen_for_escape(on_escape));
+/// ```
+pub fn use_event_callback<T: 'static, R: 'static>(
+    handler: impl FnMut(T) -> R + 'static,
+) -> Callback<T, R> {
+    // use_callback 在每次渲染時替換內部的閉包，但保留相同的 Callback
+    use_callback(handler)
+}
@@ -0,0 +1,31 @@
+use super::use_pointer_down_outside::OutsideEvent;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Call `handler` when focus moves to an element outside of all `elements`.
+///
+/// Elements that are not mounted yet are ignored. Requires the `web`
+/// feature; elsewhere the handler never runs.
+pub fn use_focus_outside(
+    elements: Vec<Signal<Option<Rc<MountedData>>>>,
+    handler: impl FnMut(OutsideEvent) + 'static,
+) {
+    #[cfg(feature = "web")]
+    {
+        use super::use_global_event::use_document_event;
+        use super::use_pointer_down_outside::{is_inside, use_outside_state};
+
+        let state = use_outside_state(elements, handler);
+        use_document_event("focusin", false, move |event: web_sys::FocusEvent| {
+            let outside = event
+                .target()
+                .is_some_and(|target| !is_inside(&state, &target));
+            if outside {
+                let handler = state.borrow().handler;
+                handler.call(OutsideEvent::new(event.into(), String::new()));
+            }
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (elements, handler);
+}
@@ -0,0 +1,106 @@
+use dioxus::prelude::*;
+
+/// The kind of input the user interacted with last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modality {
+    Keyboard,
+    Pointer,
+}
+
+// 預設為鍵盤，頁面載入後第一次聚焦時顯示焦點框
+static MODALITY: GlobalSignal<Modality> = Signal::global(|| Modality::Keyboard);
+
+fn set_modality(modality: Modality) {
+    if *MODALITY.peek() != modality {
+        *MODALITY.write() = modality;
+    }
+}
+
+/// The kind of input the user interacted with last, tracked for the whole
+/// document.
+pub fn use_modality() -> Modality {
+    #[cfg(feature = "web")]
+    use_hook(web::install);
+
+    MODALITY()
+}
+
+/// Returned by [`use_focus_visible`].
+pub struct FocusVisible {
+    /// Whether the element is focused and the user is navigating with the
+    /// keyboard.
+    pub focus_visible: Memo<bool>,
+    /// Spread on the focusable element. Includes `data-focus-visible` while
+    /// the focus should be shown.
+    pub attributes: Vec<Attribute>,
+}
+
+/// Track whether the focus of an element should be visible, like
+/// `:focus-visible`: focus reached or used with the keyboard is visible,
+/// focus from a click or tap is not.
+///
+/// Style the `[data-focus-visible]` attribute to draw focus rings only for
+/// keyboard users.
+pub fn use_focus_visible() -> FocusVisible {
+    let mut focused = use_signal(|| false);
+    use_modality();
+    let focus_visible = use_memo(move || focused() && MODALITY() == Modality::Keyboard);
+
+    let mut attributes = vec![
+        dioxus_elements::events::onfocus(move |_: FocusEvent| focused.set(true)),
+        dioxus_elements::events::onblur(move |_: FocusEvent| focused.set(false)),
+        // 沒有 web 功能時只能從元素本身的事件判斷
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let modifiers = event.modifiers();
+            if !(modifiers.meta() || modifiers.ctrl() || modifiers.alt()) {
+                set_modality(Modality::Keyboard);
+            }
+        }),
+        dioxus_elements::events::onpointerdown(move |_: PointerEvent| {
+            set_modality(Modality::Pointer)
+        }),
+    ];
+    if focus_visible() {
+        attributes.push(Attribute::new("data-focus-visible", "", None, false));
+    }
+
+    FocusVisible {
+        focus_visible,
+        attributes,
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::{Modality, set_modality};
+    use crate::primitives::dom::{EventListener, document};
+    use std::cell::Cell;
+    use wasm_bindgen::JsCast;
+
+    thread_local! {
+        static INSTALLED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    // 整個文件只註冊一次，監聽器在頁面存在期間一直保留
+    pub(super) fn install() {
+        if INSTALLED.with(|installed| installed.replace(true)) {
+            return;
+        }
+        let Some(document) = document() else {
+            return;
+        };
+
+        let keydown = EventListener::new(&document, "keydown", true, |event| {
+            let shortcut = event
+                .dyn_ref::<web_sys::KeyboardEvent>()
+                .is_some_and(|event| event.meta_key() || event.ctrl_key() || event.alt_key());
+            if !shortcut {
+                set_modality(Modality::Keyboard);
+            }
+        });
+        let pointerdown = EventListener::new(&document, "pointerdown", true, |_| {
+            set_modality(Modality::Pointer);
+        });
+        std::mem::forget((keydown, pointerdown));
+    }
+}
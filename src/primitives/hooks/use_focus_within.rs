@@ -0,0 +1,43 @@
+use super::use_timer::use_timeout;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+// focus 在子元素之間移動時會先 focusout 再 focusin，稍等一下再判定為離開
+const BLUR_DELAY: Duration = Duration::from_millis(20);
+
+/// Returned by [`use_focus_within`].
+pub struct FocusWithin {
+    /// Whether the element or one of its descendants has focus.
+    pub focus_within: ReadOnlySignal<bool>,
+    /// Spread on the container element. Includes `data-focus-within` while
+    /// focus is inside.
+    pub attributes: Vec<Attribute>,
+}
+
+/// Track whether focus is inside an element subtree, like `:focus-within`.
+///
+/// Moving focus from one descendant to another does not report a change:
+/// leaving the subtree is only reported once focus has not come back
+/// shortly after the blur.
+pub fn use_focus_within() -> FocusWithin {
+    let mut focus_within = use_signal(|| false);
+    let mut blur = use_timeout(BLUR_DELAY, move || focus_within.set(false));
+
+    let mut attributes = vec![
+        dioxus_elements::events::onfocusin(move |_: FocusEvent| {
+            blur.reset();
+            if !*focus_within.peek() {
+                focus_within.set(true);
+            }
+        }),
+        dioxus_elements::events::onfocusout(move |_: FocusEvent| blur.start()),
+    ];
+    if focus_within() {
+        attributes.push(Attribute::new("data-focus-within", "", None, false));
+    }
+
+    FocusWithin {
+        focus_within: focus_within.into(),
+        attributes,
+    }
+}
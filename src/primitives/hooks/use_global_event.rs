@@ -0,0 +1,57 @@
+//! Listeners on `window` and `document` that live as long as the calling
+//! component. Requires the `web` feature.
+
+use super::use_event_callback::use_event_callback;
+use crate::primitives::dom::{EventListener, document};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+fn use_global_event<E: JsCast + 'static>(
+    target: Option<web_sys::EventTarget>,
+    event: &str,
+    capture: bool,
+    handler: impl FnMut(E) + 'static,
+) {
+    let handler = use_event_callback(handler);
+    use_hook(|| {
+        Rc::new(target.map(|target| {
+            EventListener::new(&target, event, capture, move |event| {
+                // 事件型別不符時（例如合成的 Event）直接略過
+                if let Ok(event) = event.dyn_into::<E>() {
+                    handler.call(event);
+                }
+            })
+        }))
+    });
+}
+
+/// Listen for `event` on `window` while the calling component is mounted,
+/// e.g. `resize` or `keydown`.
+///
+/// `E` is the `web_sys` type of the event, such as
+/// `web_sys::KeyboardEvent`; events of another type are ignored. Set
+/// `capture` to run before listeners on the target. The listener is
+/// registered once, with the event name of the first render, and always
+/// calls the latest `handler`.
+pub fn use_window_event<E: JsCast + 'static>(
+    event: &str,
+    capture: bool,
+    handler: impl FnMut(E) + 'static,
+) {
+    let window = web_sys::window().map(Into::into);
+    use_global_event(window, event, capture, handler);
+}
+
+/// Listen for `event` on `document` while the calling component is mounted,
+/// e.g. `keydown`, `focusin` or `visibilitychange`.
+///
+/// Behaves like [`use_window_event`].
+pub fn use_document_event<E: JsCast + 'static>(
+    event: &str,
+    capture: bool,
+    handler: impl FnMut(E) + 'static,
+) {
+    let document = document().map(Into::into);
+    use_global_event(document, event, capture, handler);
+}
@@ -0,0 +1,278 @@
+use super::use_event_callback::use_event_callback;
+use crate::primitives::context::create_context::use_synced_signal;
+use crate::primitives::time::sleep;
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Configures [`use_hover`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoverOptions {
+    /// How long the pointer must rest on the trigger before opening.
+    pub open_delay: Duration,
+    /// How long after the pointer leaves before closing.
+    pub close_delay: Duration,
+}
+
+impl Default for HoverOptions {
+    fn default() -> Self {
+        HoverOptions {
+            open_delay: Duration::from_millis(700),
+            close_delay: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Returned by [`use_hover`].
+pub struct Hover {
+    /// Whether the content should be shown.
+    pub open: ReadOnlySignal<bool>,
+    /// Spread on the element that opens the content when hovered.
+    pub trigger: Vec<Attribute>,
+    /// Spread on the floating content.
+    pub content: Vec<Attribute>,
+}
+
+#[derive(Clone, Copy)]
+struct HoverState {
+    options: Signal<HoverOptions>,
+    open: Signal<bool>,
+    timer: Signal<Option<Task>>,
+    on_open_change: Callback<bool>,
+    #[cfg(feature = "web")]
+    grace: Signal<Option<web::GraceArea>>,
+}
+
+impl HoverState {
+    fn cancel_timer(&mut self) {
+        if let Some(task) = self.timer.take() {
+            task.cancel();
+        }
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.cancel_timer();
+        #[cfg(feature = "web")]
+        self.grace.set(None);
+        if *self.open.peek() != open {
+            self.open.set(open);
+            self.on_open_change.call(open);
+        }
+    }
+
+    fn schedule(&mut self, open: bool) {
+        self.cancel_timer();
+        if *self.open.peek() == open {
+            return;
+        }
+        let delay = if open {
+            self.options.peek().open_delay
+        } else {
+            self.options.peek().close_delay
+        };
+        let mut state = *self;
+        self.timer.set(Some(spawn(async move {
+            sleep(delay).await;
+            state.timer.set(None);
+            state.set_open(open);
+        })));
+    }
+}
+
+/// Open floating content while a trigger is hovered, e.g. a tooltip or a
+/// hover card.
+///
+/// The content opens after [`HoverOptions::open_delay`] and closes
+/// [`HoverOptions::close_delay`] after the pointer leaves both the trigger
+/// and the content. With the `web` feature, moving from one to the other
+/// does not start the close delay as long as the pointer stays within the
+/// "grace area" between the exit point and the element it is heading to.
+/// Only mouse and pen pointers hover; touch input is ignored.
+pub fn use_hover(options: HoverOptions, on_open_change: impl FnMut(bool) + 'static) -> Hover {
+    let options = use_synced_signal(options);
+    let on_open_change = use_event_callback(on_open_change);
+    let open = use_signal(|| false);
+    let timer = use_signal(|| None::<Task>);
+    let mut state = HoverState {
+        options,
+        open,
+        timer,
+        on_open_change,
+        #[cfg(feature = "web")]
+        grace: use_signal(|| None),
+    };
+
+    let mut trigger_node = use_signal(|| None::<Rc<MountedData>>);
+    let mut content_node = use_signal(|| None::<Rc<MountedData>>);
+
+    // 離開元素時，若正朝另一個元素移動就先建立通道，否則開始關閉計時
+    let mut leave = move |event: PointerEvent, towards: Signal<Option<Rc<MountedData>>>| {
+        if event.pointer_type() == "touch" {
+            return;
+        }
+        #[cfg(feature = "web")]
+        {
+            let point = event.client_coordinates();
+            let target = towards
+                .peek()
+                .as_deref()
+                .and_then(super::use_rect::bounding_client_rect);
+            if *state.open.peek()
+                && let Some(target) = target
+            {
+                state.cancel_timer();
+                let area = web::grace_polygon((point.x, point.y), &target);
+                state.grace.set(web::GraceArea::new(area, state));
+                return;
+            }
+        }
+        #[cfg(not(feature = "web"))]
+        let _ = towards;
+        state.schedule(false);
+    };
+
+    let mut enter = move |event: PointerEvent, open: bool| {
+        if event.pointer_type() == "touch" {
+            return;
+        }
+        #[cfg(feature = "web")]
+        state.grace.set(None);
+        if open {
+            state.schedule(true);
+        } else {
+            // 指標進入內容時保持開啟
+            state.cancel_timer();
+        }
+    };
+
+    let trigger = vec![
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            trigger_node.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onpointerenter(move |event: PointerEvent| enter(event, true)),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            leave(event, content_node)
+        }),
+        // 按下觸發元素時立即關閉，跟原生的 title 提示一樣
+        dioxus_elements::events::onpointerdown(move |_: PointerEvent| state.set_open(false)),
+    ];
+    let content = vec![
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            content_node.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onpointerenter(move |event: PointerEvent| enter(event, false)),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            leave(event, trigger_node)
+        }),
+    ];
+
+    use_drop(move || {
+        trigger_node.set(None);
+        content_node.set(None);
+    });
+
+    Hover {
+        open: open.into(),
+        trigger,
+        content,
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::HoverState;
+    use crate::primitives::dom::{EventListener, document};
+    use crate::primitives::hooks::use_rect::Rect;
+    use wasm_bindgen::JsCast;
+
+    /// A point in viewport coordinates.
+    type Point = (f64, f64);
+
+    /// The area the pointer may cross without closing: the convex hull of the
+    /// exit point, padded a few pixels to either side, and the corners of the
+    /// target rectangle.
+    pub(super) fn grace_polygon(exit: Point, target: &Rect) -> Vec<Point> {
+        const PADDING: f64 = 5.0;
+
+        let (x, y) = exit;
+        let mut points = vec![
+            (x - PADDING, y - PADDING),
+            (x + PADDING, y - PADDING),
+            (x - PADDING, y + PADDING),
+            (x + PADDING, y + PADDING),
+            (target.x, target.y),
+            (target.right(), target.y),
+            (target.right(), target.bottom()),
+            (target.x, target.bottom()),
+        ];
+        convex_hull(&mut points)
+    }
+
+    // Andrew's monotone chain
+    fn convex_hull(points: &mut [Point]) -> Vec<Point> {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        let cross =
+            |o: Point, a: Point, b: Point| (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0);
+
+        let mut hull: Vec<Point> = Vec::with_capacity(points.len() * 2);
+        for pass in 0..2 {
+            let start = hull.len();
+            let iter: Box<dyn Iterator<Item = &Point>> = if pass == 0 {
+                Box::new(points.iter())
+            } else {
+                Box::new(points.iter().rev())
+            };
+            for &point in iter {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            // 每一半的最後一點是另一半的起點
+            hull.pop();
+        }
+        hull
+    }
+
+    fn polygon_contains(polygon: &[Point], (x, y): Point) -> bool {
+        let mut inside = false;
+        let mut previous = match polygon.last() {
+            Some(point) => *point,
+            None => return false,
+        };
+        for &point in polygon {
+            let crosses = (point.1 > y) != (previous.1 > y)
+                && x < (previous.0 - point.0) * (y - point.1) / (previous.1 - point.1) + point.0;
+            if crosses {
+                inside = !inside;
+            }
+            previous = point;
+        }
+        inside
+    }
+
+    // 存在期間監聽整份文件的指標移動，離開通道時開始關閉計時
+    pub(super) struct GraceArea {
+        _listener: EventListener,
+    }
+
+    impl GraceArea {
+        pub(super) fn new(polygon: Vec<Point>, mut state: HoverState) -> Option<Self> {
+            let document = document()?;
+            let listener = EventListener::new(&document, "pointermove", false, move |event| {
+                let Some(event) = event.dyn_ref::<web_sys::MouseEvent>() else {
+                    return;
+                };
+                let point = (event.client_x() as f64, event.client_y() as f64);
+                if !polygon_contains(&polygon, point) && state.timer.peek().is_none() {
+                    state.schedule(false);
+                }
+            });
+            Some(GraceArea {
+                _listener: listener,
+            })
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::primitives::root::RootContext;
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// 每個 VirtualDom 各自計數，同一個伺服器處理多個請求時也不會互相影響
+#[derive(Clone, Default)]
+struct IdCounter(Rc<Cell<usize>>);
+
+impl IdCounter {
+    fn next(&self) -> usize {
+        let id = self.0.get() + 1;
+        self.0.set(id);
+        id
+    }
+}
+
+/// A unique id for this component instance, e.g. `primitive-:r3:`, for wiring
+/// `aria-labelledby`/`aria-controls` between the parts of a primitive.
+///
+/// Inside a [`PrimitivesRoot`](crate::primitives::root::PrimitivesRoot) the
+/// id starts with its `id_prefix` instead of `primitive`.
+///
+/// Ids are numbered in render order, so the server render and the client
+/// hydration of the same tree produce the same ids. The id never changes for
+/// the lifetime of the component.
+pub fn use_id() -> String {
+    use_hook(|| {
+        let counter = consume_context_from_scope::<IdCounter>(ScopeId::ROOT)
+            .unwrap_or_else(|| provide_root_context(IdCounter::default()));
+        let prefix = try_consume_context::<RootContext>()
+            .map(|root| root.id_prefix)
+            .unwrap_or_else(|| "primitive".to_string());
+        format!("{}-:r{}:", prefix, counter.next())
+    })
+}
+
+/// Like [`use_id`], but a user-supplied `id` takes precedence, e.g. an `id`
+/// prop of a primitive part.
+pub fn use_id_or(id: Option<String>) -> String {
+    let generated = use_id();
+    id.unwrap_or(generated)
+}
@@ -0,0 +1,145 @@
+use super::use_rect::Rect;
+use crate::primitives::context::create_context::use_synced_signal;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Configures [`use_intersection_observer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntersectionOptions {
+    /// The visible fraction of the element, between 0 and 1, at which the
+    /// intersection is reported again.
+    pub threshold: f64,
+    /// Grows or shrinks the viewport before computing intersections, in CSS
+    /// `margin` syntax, e.g. `"200px 0px"` to load content before it
+    /// scrolls into view.
+    pub root_margin: String,
+    /// Intersect with the nearest scrolling ancestor instead of the
+    /// viewport, so `root_margin` grows that ancestor's visible area.
+    pub scroll_parent: bool,
+}
+
+impl Default for IntersectionOptions {
+    fn default() -> Self {
+        IntersectionOptions {
+            threshold: 0.0,
+            root_margin: "0px".to_string(),
+            scroll_parent: false,
+        }
+    }
+}
+
+/// How an element intersects the viewport.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    pub is_intersecting: bool,
+    /// The visible fraction of the element, between 0 and 1.
+    pub ratio: f64,
+    /// The element's bounding rectangle when the intersection changed.
+    pub bounding_rect: Rect,
+}
+
+/// Track how `element` intersects the viewport with an
+/// `IntersectionObserver`, e.g. to mount heavy content once it scrolls into
+/// view.
+///
+/// The intersection is `None` until the first observation, while the
+/// element is not mounted, and always outside the `web` feature.
+pub fn use_intersection_observer(
+    element: Signal<Option<Rc<MountedData>>>,
+    options: IntersectionOptions,
+) -> ReadOnlySignal<Option<Intersection>> {
+    let intersection = use_signal(|| None);
+    let options = use_synced_signal(options);
+
+    #[cfg(feature = "web")]
+    {
+        let observer = use_hook(|| Rc::new(std::cell::RefCell::new(None::<web::Observer>)));
+        use_effect(move || {
+            let mut intersection = intersection;
+            let node = element.read().clone();
+            let options = options.read().clone();
+            // 換成新的元素或選項時，舊的 observer 會在 drop 時斷開
+            let next = node
+                .as_deref()
+                .and_then(|node| web::Observer::new(node, &options, intersection));
+            if next.is_none() {
+                intersection.set(None);
+            }
+            observer.replace(next);
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (element, options);
+
+    intersection.into()
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::{Intersection, IntersectionOptions, Rect};
+    use crate::primitives::dom::{in_current_runtime, scroll_parent, web_element};
+    use dioxus::prelude::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    pub(super) struct Observer {
+        observer: web_sys::IntersectionObserver,
+        _callback: Closure<dyn FnMut(js_sys::Array)>,
+    }
+
+    impl Observer {
+        pub(super) fn new(
+            node: &MountedData,
+            options: &IntersectionOptions,
+            mut intersection: Signal<Option<Intersection>>,
+        ) -> Option<Self> {
+            let element = web_element(node)?;
+
+            let callback =
+                Closure::<dyn FnMut(js_sys::Array)>::new(in_current_runtime(move |entries| {
+                    // 同一批可能有多筆紀錄，最後一筆是最新的狀態
+                    let Some(entry) = entries.iter().last().and_then(|entry| {
+                        entry.dyn_into::<web_sys::IntersectionObserverEntry>().ok()
+                    }) else {
+                        return;
+                    };
+                    let rect = entry.bounding_client_rect();
+                    intersection.set(Some(Intersection {
+                        is_intersecting: entry.is_intersecting(),
+                        ratio: entry.intersection_ratio(),
+                        bounding_rect: Rect {
+                            x: rect.x(),
+                            y: rect.y(),
+                            width: rect.width(),
+                            height: rect.height(),
+                        },
+                    }));
+                }));
+
+            let init = web_sys::IntersectionObserverInit::new();
+            init.set_threshold(&options.threshold.into());
+            init.set_root_margin(&options.root_margin);
+            if options.scroll_parent {
+                let root = scroll_parent(&element);
+                init.set_root(root.as_ref().map(|root| root.as_ref()));
+            }
+            let observer = web_sys::IntersectionObserver::new_with_options(
+                callback.as_ref().unchecked_ref(),
+                &init,
+            )
+            .ok()?;
+            observer.observe(&element);
+
+            Some(Observer {
+                observer,
+                _callback: callback,
+            })
+        }
+    }
+
+    impl Drop for Observer {
+        fn drop(&mut self) {
+            self.observer.disconnect();
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::use_event_callback::use_event_callback;
+use crate::primitives::time::sleep;
+use dioxus::html::geometry::ClientPoint;
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// Configures [`use_long_press`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressOptions {
+    /// How long the pointer must stay down.
+    pub delay: Duration,
+    /// How far, in pixels, the pointer may move before the press is
+    /// cancelled, so scrolling with a finger does not trigger it.
+    pub tolerance: f64,
+}
+
+impl Default for LongPressOptions {
+    fn default() -> Self {
+        LongPressOptions {
+            delay: Duration::from_millis(500),
+            tolerance: 10.0,
+        }
+    }
+}
+
+/// Fired when a pointer has been held down long enough.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongPressEvent {
+    /// Where the press started, in viewport coordinates.
+    pub x: f64,
+    pub y: f64,
+    /// `"mouse"`, `"pen"` or `"touch"`.
+    pub pointer_type: String,
+}
+
+#[derive(Clone, Copy)]
+struct PendingPress {
+    pointer_id: i32,
+    origin: ClientPoint,
+}
+
+/// Detect a press held for [`LongPressOptions::delay`], e.g. to open a
+/// context menu on touch devices.
+///
+/// Spread the returned attributes on the pressed element. The press is
+/// cancelled when the pointer moves past the tolerance, is released, leaves
+/// the element, or is cancelled by the browser. After a long press, the
+/// click that follows the release and the native context menu are
+/// suppressed.
+pub fn use_long_press(
+    options: LongPressOptions,
+    on_long_press: impl FnMut(LongPressEvent) + 'static,
+) -> Vec<Attribute> {
+    let on_long_press = use_event_callback(on_long_press);
+    let mut pending = use_signal(|| None::<PendingPress>);
+    let mut timer = use_signal(|| None::<Task>);
+    // 長按已觸發，接下來的 click 與 contextmenu 不應再被處理
+    let mut fired = use_signal(|| false);
+
+    let mut cancel = move || {
+        if let Some(task) = timer.take() {
+            task.cancel();
+        }
+        pending.set(None);
+    };
+
+    vec![
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            let is_mouse = event.pointer_type() == "mouse";
+            if is_mouse && event.trigger_button() != Some(MouseButton::Primary) {
+                return;
+            }
+            cancel();
+            fired.set(false);
+
+            let origin = event.client_coordinates();
+            let long_press = LongPressEvent {
+                x: origin.x,
+                y: origin.y,
+                pointer_type: event.pointer_type(),
+            };
+            pending.set(Some(PendingPress {
+                pointer_id: event.pointer_id(),
+                origin,
+            }));
+            timer.set(Some(spawn(async move {
+                sleep(options.delay).await;
+                timer.set(None);
+                pending.set(None);
+                fired.set(true);
+                on_long_press.call(long_press);
+            })));
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            let Some(press) = *pending.peek() else {
+                return;
+            };
+            if press.pointer_id != event.pointer_id() {
+                return;
+            }
+            let point = event.client_coordinates();
+            if (point - press.origin).length() > options.tolerance {
+                cancel();
+            }
+        }),
+        dioxus_elements::events::onpointerup(move |_: PointerEvent| cancel()),
+        dioxus_elements::events::onpointerleave(move |_: PointerEvent| cancel()),
+        dioxus_elements::events::onpointercancel(move |_: PointerEvent| cancel()),
+        dioxus_elements::events::onclick(move |event: MouseEvent| {
+            if *fired.peek() {
+                fired.set(false);
+                event.prevent_default();
+                event.stop_propagation();
+            }
+        }),
+        // 觸控長按時瀏覽器會自己開啟原生選單
+        dioxus_elements::events::oncontextmenu(move |event: MouseEvent| {
+            if *fired.peek() || pending.peek().is_some() {
+                event.prevent_default();
+            }
+        }),
+    ]
+}
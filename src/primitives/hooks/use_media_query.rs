@@ -0,0 +1,58 @@
+use dioxus::prelude::*;
+
+/// Whether the CSS media `query` (e.g. `"(min-width: 768px)"`) currently
+/// matches, updated as it changes.
+///
+/// Always `false` outside the `web` feature, where media queries cannot be
+/// evaluated.
+pub fn use_media_query(query: &str) -> ReadOnlySignal<bool> {
+    let query = query.to_string();
+
+    #[cfg(feature = "web")]
+    {
+        let mut matches = use_signal({
+            let query = query.clone();
+            move || web::matches(&query)
+        });
+        let listener = use_hook(|| std::rc::Rc::new(std::cell::RefCell::new(None)));
+        use_effect(use_reactive!(|(query,)| {
+            // 換成新的查詢時，舊的監聽器會在 drop 時移除
+            listener.replace(web::listen(&query, matches));
+            let next = web::matches(&query);
+            if *matches.peek() != next {
+                matches.set(next);
+            }
+        }));
+        matches.into()
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        let _ = query;
+        use_signal(|| false).into()
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use crate::primitives::dom::EventListener;
+    use dioxus::prelude::*;
+
+    fn media_query_list(query: &str) -> Option<web_sys::MediaQueryList> {
+        web_sys::window()?.match_media(query).ok().flatten()
+    }
+
+    pub(super) fn matches(query: &str) -> bool {
+        media_query_list(query).is_some_and(|list| list.matches())
+    }
+
+    pub(super) fn listen(query: &str, mut matches: Signal<bool>) -> Option<EventListener> {
+        let list = media_query_list(query)?;
+        Some(EventListener::new(
+            &list.clone(),
+            "change",
+            false,
+            move |_| matches.set(list.matches()),
+        ))
+    }
+}
@@ -0,0 +1,134 @@
+use super::use_event_callback::use_event_callback;
+use dioxus::html::geometry::ClientPoint;
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+
+/// The stage of a drag reported by a [`MoveEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovePhase {
+    Start,
+    Move,
+    End,
+}
+
+/// Reported by [`use_move`] while an element is dragged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveEvent {
+    pub phase: MovePhase,
+    /// Movement since the previous event, in pixels for pointers and in
+    /// steps of one for the keyboard. Zero for `Start` and `End`.
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// `"mouse"`, `"pen"`, `"touch"` or `"keyboard"`.
+    pub pointer_type: String,
+    /// Whether Shift was held, e.g. to move in larger steps.
+    pub shift_key: bool,
+}
+
+#[derive(Clone, Copy)]
+struct Drag {
+    pointer_id: i32,
+    last: ClientPoint,
+    started: bool,
+}
+
+/// Report drags of an element by a pointer, and arrow-key presses on it as
+/// "virtual drags" of one step, e.g. for slider thumbs and resize handles.
+///
+/// Spread the returned attributes on the dragged element. The pointer is
+/// captured while it is held, so the drag continues outside the element.
+/// A pointer drag reports `Start` on its first movement, `Move` for every
+/// movement and `End` when released; an arrow key reports all three at once.
+/// Give the element `touch-action: none` so touch drags are not taken over
+/// by scrolling.
+pub fn use_move(on_move: impl FnMut(MoveEvent) + 'static) -> Vec<Attribute> {
+    let on_move = use_event_callback(on_move);
+    let mut drag = use_signal(|| None::<Drag>);
+
+    let emit = move |phase: MovePhase, delta: (f64, f64), pointer_type: &str, shift_key: bool| {
+        on_move.call(MoveEvent {
+            phase,
+            delta_x: delta.0,
+            delta_y: delta.1,
+            pointer_type: pointer_type.to_string(),
+            shift_key,
+        });
+    };
+
+    let mut end = move |event: PointerEvent| {
+        let Some(current) = *drag.peek() else {
+            return;
+        };
+        if current.pointer_id != event.pointer_id() {
+            return;
+        }
+        drag.set(None);
+        if current.started {
+            let shift_key = event.modifiers().shift();
+            emit(MovePhase::End, (0.0, 0.0), &event.pointer_type(), shift_key);
+        }
+    };
+
+    vec![
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            let is_mouse = event.pointer_type() == "mouse";
+            if is_mouse && event.trigger_button() != Some(MouseButton::Primary) {
+                return;
+            }
+            #[cfg(feature = "web")]
+            crate::primitives::dom::capture_pointer(&event);
+
+            drag.set(Some(Drag {
+                pointer_id: event.pointer_id(),
+                last: event.client_coordinates(),
+                started: false,
+            }));
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            let Some(current) = *drag.peek() else {
+                return;
+            };
+            if current.pointer_id != event.pointer_id() {
+                return;
+            }
+            let point = event.client_coordinates();
+            let delta = point - current.last;
+            if delta.x == 0.0 && delta.y == 0.0 {
+                return;
+            }
+            drag.set(Some(Drag {
+                last: point,
+                started: true,
+                ..current
+            }));
+
+            let pointer_type = event.pointer_type();
+            let shift_key = event.modifiers().shift();
+            if !current.started {
+                emit(MovePhase::Start, (0.0, 0.0), &pointer_type, shift_key);
+            }
+            emit(
+                MovePhase::Move,
+                (delta.x, delta.y),
+                &pointer_type,
+                shift_key,
+            );
+        }),
+        dioxus_elements::events::onpointerup(move |event: PointerEvent| end(event)),
+        dioxus_elements::events::onpointercancel(move |event: PointerEvent| end(event)),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let delta = match event.key() {
+                Key::ArrowLeft => (-1.0, 0.0),
+                Key::ArrowRight => (1.0, 0.0),
+                Key::ArrowUp => (0.0, -1.0),
+                Key::ArrowDown => (0.0, 1.0),
+                _ => return,
+            };
+            event.prevent_default();
+            let shift_key = event.modifiers().shift();
+            emit(MovePhase::Start, (0.0, 0.0), "keyboard", shift_key);
+            emit(MovePhase::Move, delta, "keyboard", shift_key);
+            emit(MovePhase::End, (0.0, 0.0), "keyboard", shift_key);
+        }),
+    ]
+}
@@ -0,0 +1,166 @@
+#[cfg(feature = "web")]
+use super::use_event_callback::use_event_callback;
+#[cfg(feature = "web")]
+use crate::primitives::context::create_context::use_synced_signal;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Which changes [`use_mutation_observer`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MutationOptions {
+    /// Children added or removed.
+    pub child_list: bool,
+    /// Attribute changes.
+    pub attributes: bool,
+    /// Text content changes.
+    pub character_data: bool,
+    /// Also watch every descendant, not only the element itself.
+    pub subtree: bool,
+}
+
+impl Default for MutationOptions {
+    fn default() -> Self {
+        MutationOptions {
+            child_list: true,
+            attributes: false,
+            character_data: false,
+            subtree: true,
+        }
+    }
+}
+
+/// The kind of change reported in a [`Mutation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationKind {
+    ChildList,
+    Attributes,
+    CharacterData,
+}
+
+/// A change observed by [`use_mutation_observer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutation {
+    pub kind: MutationKind,
+    /// The changed attribute, for [`MutationKind::Attributes`].
+    pub attribute_name: Option<String>,
+    /// How many nodes were added and removed, for
+    /// [`MutationKind::ChildList`].
+    pub added_nodes: u32,
+    pub removed_nodes: u32,
+}
+
+/// Call `callback` with the batch of changes whenever the DOM inside
+/// `element` changes, including changes made outside of Dioxus, e.g. by
+/// third-party scripts.
+///
+/// The observer follows `element` as it is mounted and replaced. Requires
+/// the `web` feature; elsewhere `callback` never runs.
+pub fn use_mutation_observer(
+    element: Signal<Option<Rc<MountedData>>>,
+    options: MutationOptions,
+    callback: impl FnMut(Vec<Mutation>) + 'static,
+) {
+    #[cfg(feature = "web")]
+    {
+        let callback = use_event_callback(callback);
+        let options = use_synced_signal(options);
+        let observer = use_hook(|| Rc::new(std::cell::RefCell::new(None::<web::Observer>)));
+        use_effect(move || {
+            let target = element
+                .read()
+                .as_deref()
+                .and_then(crate::primitives::dom::web_element);
+            // 換成新的元素或選項時，舊的 observer 會在 drop 時斷開
+            let next =
+                target.and_then(|target| web::Observer::new(&target, *options.read(), callback));
+            observer.replace(next);
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (element, options, callback);
+}
+
+/// Like [`use_mutation_observer`], for the whole `document.body`, e.g. to
+/// notice portals and overlays added anywhere on the page.
+pub fn use_document_mutation_observer(
+    options: MutationOptions,
+    callback: impl FnMut(Vec<Mutation>) + 'static,
+) {
+    #[cfg(feature = "web")]
+    {
+        let callback = use_event_callback(callback);
+        use_hook(|| {
+            let body = crate::primitives::dom::document().and_then(|document| document.body());
+            Rc::new(body.and_then(|body| web::Observer::new(&body, options, callback)))
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (options, callback);
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::{Mutation, MutationKind, MutationOptions};
+    use crate::primitives::dom::in_current_runtime;
+    use dioxus::prelude::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    pub(super) struct Observer {
+        observer: web_sys::MutationObserver,
+        _callback: Closure<dyn FnMut(js_sys::Array)>,
+    }
+
+    fn mutation(record: &web_sys::MutationRecord) -> Mutation {
+        let kind = match record.type_().as_str() {
+            "attributes" => MutationKind::Attributes,
+            "characterData" => MutationKind::CharacterData,
+            _ => MutationKind::ChildList,
+        };
+        Mutation {
+            kind,
+            attribute_name: record.attribute_name(),
+            added_nodes: record.added_nodes().length(),
+            removed_nodes: record.removed_nodes().length(),
+        }
+    }
+
+    impl Observer {
+        pub(super) fn new(
+            target: &web_sys::Node,
+            options: MutationOptions,
+            callback: Callback<Vec<Mutation>>,
+        ) -> Option<Self> {
+            let closure =
+                Closure::<dyn FnMut(js_sys::Array)>::new(in_current_runtime(move |records| {
+                    let mutations: Vec<Mutation> = records
+                        .iter()
+                        .filter_map(|record| record.dyn_into::<web_sys::MutationRecord>().ok())
+                        .map(|record| mutation(&record))
+                        .collect();
+                    if !mutations.is_empty() {
+                        callback.call(mutations);
+                    }
+                }));
+
+            let init = web_sys::MutationObserverInit::new();
+            init.set_child_list(options.child_list);
+            init.set_attributes(options.attributes);
+            init.set_character_data(options.character_data);
+            init.set_subtree(options.subtree);
+            let observer = web_sys::MutationObserver::new(closure.as_ref().unchecked_ref()).ok()?;
+            observer.observe_with_options(target, &init).ok()?;
+
+            Some(Observer {
+                observer,
+                _callback: closure,
+            })
+        }
+    }
+
+    impl Drop for Observer {
+        fn drop(&mut self) {
+            self.observer.disconnect();
+        }
+    }
+}
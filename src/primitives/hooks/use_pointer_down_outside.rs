@@ -0,0 +1,150 @@
+#[cfg(feature = "web")]
+use super::use_event_callback::use_event_callback;
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A pointer press or focus change outside of the watched elements.
+#[derive(Clone)]
+pub struct OutsideEvent {
+    #[cfg(feature = "web")]
+    event: web_sys::Event,
+    pointer_type: String,
+}
+
+impl OutsideEvent {
+    #[cfg(feature = "web")]
+    pub(crate) fn new(event: web_sys::Event, pointer_type: String) -> Self {
+        OutsideEvent {
+            event,
+            pointer_type,
+        }
+    }
+
+    /// `"mouse"`, `"pen"` or `"touch"` for pointer presses; empty for focus
+    /// changes.
+    pub fn pointer_type(&self) -> &str {
+        &self.pointer_type
+    }
+
+    /// Prevent the browser's default action for the underlying DOM event.
+    pub fn prevent_default(&self) {
+        #[cfg(feature = "web")]
+        self.event.prevent_default();
+    }
+
+    /// The underlying DOM event.
+    #[cfg(feature = "web")]
+    pub fn event(&self) -> &web_sys::Event {
+        &self.event
+    }
+}
+
+// 監聽器只建立一次，每次渲染更新要比對的元素
+#[cfg(feature = "web")]
+pub(crate) struct OutsideState {
+    pub(crate) elements: Vec<Signal<Option<Rc<MountedData>>>>,
+    pub(crate) handler: Callback<OutsideEvent>,
+}
+
+#[cfg(feature = "web")]
+pub(crate) fn use_outside_state(
+    elements: Vec<Signal<Option<Rc<MountedData>>>>,
+    handler: impl FnMut(OutsideEvent) + 'static,
+) -> Rc<RefCell<OutsideState>> {
+    let handler = use_event_callback(handler);
+    let state = use_hook(|| {
+        Rc::new(RefCell::new(OutsideState {
+            elements: Vec::new(),
+            handler,
+        }))
+    });
+    state.borrow_mut().elements = elements;
+    state
+}
+
+// 目標是否在任何一個要比對的元素之內
+#[cfg(feature = "web")]
+pub(crate) fn is_inside(state: &RefCell<OutsideState>, target: &web_sys::EventTarget) -> bool {
+    use crate::primitives::dom::{contains, web_element};
+
+    state.borrow().elements.iter().any(|element| {
+        element
+            .peek()
+            .as_deref()
+            .and_then(web_element)
+            .is_some_and(|element| contains(&element, target))
+    })
+}
+
+/// Call `handler` when a pointer is pressed outside of all `elements`, e.g.
+/// the trigger and the content of a popover.
+///
+/// Elements that are not mounted yet are ignored. On touch devices the
+/// handler runs on the following `click` instead, so scrolling the page with a
+/// finger does not count as an outside press. Requires the `web` feature;
+/// elsewhere the handler never runs.
+pub fn use_pointer_down_outside(
+    elements: Vec<Signal<Option<Rc<MountedData>>>>,
+    handler: impl FnMut(OutsideEvent) + 'static,
+) {
+    #[cfg(feature = "web")]
+    {
+        let state = use_outside_state(elements, handler);
+        use_hook(|| pointer_down_listeners(state));
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (elements, handler);
+}
+
+#[cfg(feature = "web")]
+fn pointer_down_listeners(
+    state: Rc<RefCell<OutsideState>>,
+) -> Rc<Vec<crate::primitives::dom::EventListener>> {
+    use crate::primitives::dom::{EventListener, document};
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = document() else {
+        return Rc::new(Vec::new());
+    };
+    // 觸控按下後等到 click 才觸發，捲動時不會有 click
+    let pending = Rc::new(RefCell::new(None::<OutsideEvent>));
+
+    // 使用捕獲階段，即使內容停止了事件冒泡也能偵測到
+    let pointer_down = {
+        let state = state.clone();
+        let pending = pending.clone();
+        EventListener::new(&document, "pointerdown", true, move |event| {
+            pending.borrow_mut().take();
+            let Some(target) = event.target() else {
+                return;
+            };
+            if is_inside(&state, &target) {
+                return;
+            }
+
+            let pointer_type = event
+                .dyn_ref::<web_sys::PointerEvent>()
+                .map(|event| event.pointer_type())
+                .unwrap_or_default();
+            let outside = OutsideEvent::new(event, pointer_type);
+            if outside.pointer_type() == "touch" {
+                pending.replace(Some(outside));
+            } else {
+                let handler = state.borrow().handler;
+                handler.call(outside);
+            }
+        })
+    };
+
+    let click = EventListener::new(&document, "click", true, move |_| {
+        let outside = pending.borrow_mut().take();
+        if let Some(outside) = outside {
+            let handler = state.borrow().handler;
+            handler.call(outside);
+        }
+    });
+
+    Rc::new(vec![pointer_down, click])
+}
@@ -0,0 +1,11 @@
+use super::use_media_query::use_media_query;
+use dioxus::prelude::*;
+
+/// Whether the user has asked the system to minimize non-essential motion,
+/// via the `prefers-reduced-motion` media feature.
+///
+/// Primitives that animate, such as [`Presence`](crate::primitives::presence::Presence),
+/// skip their animations when this is `true`.
+pub fn use_prefers_reduced_motion() -> ReadOnlySignal<bool> {
+    use_media_query("(prefers-reduced-motion: reduce)")
+}
@@ -0,0 +1,177 @@
+use super::use_event_callback::use_event_callback;
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+
+/// How a press was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressSource {
+    Mouse,
+    Pen,
+    Touch,
+    Keyboard,
+}
+
+impl PressSource {
+    fn from_pointer_type(pointer_type: &str) -> Self {
+        match pointer_type {
+            "pen" => PressSource::Pen,
+            "touch" => PressSource::Touch,
+            _ => PressSource::Mouse,
+        }
+    }
+}
+
+/// Fired when an element is activated by [`use_press`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressEvent {
+    pub source: PressSource,
+}
+
+/// Returned by [`use_press`].
+pub struct Press {
+    /// Whether the element is currently held down.
+    pub pressed: Memo<bool>,
+    /// Spread on the pressable element. Includes `data-pressed` while
+    /// pressed.
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pressing {
+    Pointer {
+        pointer_id: i32,
+        source: PressSource,
+    },
+    Keyboard,
+}
+
+/// Handle activation of an element by mouse, touch, pen, Enter or Space the
+/// same way, so toggles, checkboxes and menu items respond identically to
+/// every input.
+///
+/// A pointer press fires on release over the element; dragging off the
+/// element releases `pressed`, and dragging back on restores it. A keyboard
+/// press fires when Enter or Space is released, and is cancelled if the
+/// element loses focus first. Nothing fires while `disabled` is true.
+pub fn use_press(disabled: bool, on_press: impl FnMut(PressEvent) + 'static) -> Press {
+    let on_press = use_event_callback(on_press);
+    let mut pressing = use_signal(|| None::<Pressing>);
+    // 指標按住後離開元素時暫時放開，回到元素上再恢復
+    let mut over = use_signal(|| false);
+
+    use_effect(use_reactive!(|(disabled,)| {
+        if disabled {
+            pressing.set(None);
+        }
+    }));
+
+    let pressed = use_memo(move || pressing.read().is_some() && over());
+    let mut attributes = Vec::new();
+    if pressed() {
+        attributes.push(Attribute::new("data-pressed", "", None, false));
+    }
+    if disabled {
+        return Press {
+            pressed,
+            attributes,
+        };
+    }
+
+    let mut release = move |pointer_id: i32, activate: bool| {
+        let Some(Pressing::Pointer {
+            pointer_id: id,
+            source,
+        }) = *pressing.peek()
+        else {
+            return;
+        };
+        if id != pointer_id {
+            return;
+        }
+        pressing.set(None);
+        if activate {
+            on_press.call(PressEvent { source });
+        }
+    };
+
+    attributes.extend([
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            let source = PressSource::from_pointer_type(&event.pointer_type());
+            if source == PressSource::Mouse && event.trigger_button() != Some(MouseButton::Primary)
+            {
+                return;
+            }
+            pressing.set(Some(Pressing::Pointer {
+                pointer_id: event.pointer_id(),
+                source,
+            }));
+            over.set(true);
+        }),
+        dioxus_elements::events::onpointerup(move |event: PointerEvent| {
+            let inside = *over.peek();
+            release(event.pointer_id(), inside);
+        }),
+        dioxus_elements::events::onpointercancel(move |event: PointerEvent| {
+            release(event.pointer_id(), false)
+        }),
+        dioxus_elements::events::onpointerleave(move |_: PointerEvent| {
+            over.set(false);
+            // 觸控離開元素後不會再回來，直接取消
+            if let Some(Pressing::Pointer {
+                source: PressSource::Touch,
+                ..
+            }) = *pressing.peek()
+            {
+                pressing.set(None);
+            }
+        }),
+        dioxus_elements::events::onpointerenter(move |event: PointerEvent| {
+            // 在元素外放開時收不到 pointerup，回來時若已沒有按鍵按住就清除
+            let released = event.held_buttons().is_empty();
+            if released && matches!(*pressing.peek(), Some(Pressing::Pointer { .. })) {
+                pressing.set(None);
+            }
+            over.set(true);
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if !is_press_key(&event.key()) {
+                return;
+            }
+            // 避免空白鍵捲動頁面，以及原生按鈕的 click 造成重複觸發
+            event.prevent_default();
+            if event.is_auto_repeating() || *pressing.peek() == Some(Pressing::Keyboard) {
+                return;
+            }
+            pressing.set(Some(Pressing::Keyboard));
+            over.set(true);
+        }),
+        dioxus_elements::events::onkeyup(move |event: KeyboardEvent| {
+            if !is_press_key(&event.key()) || *pressing.peek() != Some(Pressing::Keyboard) {
+                return;
+            }
+            event.prevent_default();
+            pressing.set(None);
+            on_press.call(PressEvent {
+                source: PressSource::Keyboard,
+            });
+        }),
+        dioxus_elements::events::onblur(move |_: FocusEvent| {
+            if *pressing.peek() == Some(Pressing::Keyboard) {
+                pressing.set(None);
+            }
+        }),
+    ]);
+
+    Press {
+        pressed,
+        attributes,
+    }
+}
+
+fn is_press_key(key: &Key) -> bool {
+    match key {
+        Key::Enter => true,
+        Key::Character(character) => character == " ",
+        _ => false,
+    }
+}
@@ -0,0 +1,161 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// A rectangle in viewport coordinates, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Whether the point (`x`, `y`) lies inside the rectangle.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.right() && y >= self.y && y <= self.bottom()
+    }
+}
+
+impl From<dioxus::html::geometry::PixelsRect> for Rect {
+    fn from(rect: dioxus::html::geometry::PixelsRect) -> Self {
+        Rect {
+            x: rect.origin.x,
+            y: rect.origin.y,
+            width: rect.size.width,
+            height: rect.size.height,
+        }
+    }
+}
+
+/// Measure the bounding rectangle of a mounted element once.
+#[cfg(feature = "web")]
+pub(crate) fn bounding_client_rect(node: &MountedData) -> Option<Rect> {
+    crate::primitives::dom::web_element(node).map(|element| web::measure(&element))
+}
+
+/// Track the bounding rectangle of `element`, relative to the viewport.
+///
+/// With the `web` feature the rectangle is checked on every animation frame,
+/// so it follows scrolling, resizing and layout changes anywhere on the page.
+/// Elsewhere it is measured once each time the element is mounted. The
+/// rectangle is `None` while the element is not mounted.
+pub fn use_rect(element: Signal<Option<Rc<MountedData>>>) -> ReadOnlySignal<Option<Rect>> {
+    let mut rect = use_signal(|| None);
+
+    #[cfg(feature = "web")]
+    {
+        let tracker = use_hook(|| Rc::new(std::cell::RefCell::new(None::<web::FrameLoop>)));
+        use_effect(move || {
+            let node = element.read().clone();
+            // 換成新的元素時，舊的迴圈會在 drop 時取消
+            let next = node
+                .as_deref()
+                .and_then(|node| web::FrameLoop::new(node, rect));
+            if next.is_none() {
+                rect.set(None);
+            }
+            tracker.replace(next);
+        });
+    }
+
+    #[cfg(not(feature = "web"))]
+    use_effect(move || {
+        let Some(node) = element.read().clone() else {
+            rect.set(None);
+            return;
+        };
+        spawn(async move {
+            let next = node.get_client_rect().await.ok().map(Rect::from);
+            if *rect.peek() != next {
+                rect.set(next);
+            }
+        });
+    });
+
+    rect.into()
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::Rect;
+    use crate::primitives::dom::{in_current_runtime, web_element};
+    use dioxus::prelude::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    type FrameCallback = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
+    // 每個 animation frame 量測一次，直到被 drop
+    pub(super) struct FrameLoop {
+        callback: FrameCallback,
+        handle: Rc<Cell<i32>>,
+    }
+
+    pub(super) fn measure(element: &web_sys::Element) -> Rect {
+        let rect = element.get_bounding_client_rect();
+        Rect {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+
+    fn request_frame(callback: &FrameCallback, handle: &Cell<i32>) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let callback = callback.borrow();
+        let Some(closure) = callback.as_ref() else {
+            return;
+        };
+        if let Ok(id) = window.request_animation_frame(closure.as_ref().unchecked_ref()) {
+            handle.set(id);
+        }
+    }
+
+    impl FrameLoop {
+        pub(super) fn new(node: &MountedData, mut rect: Signal<Option<Rect>>) -> Option<Self> {
+            let element = web_element(node)?;
+            let callback: FrameCallback = Rc::new(RefCell::new(None));
+            let handle = Rc::new(Cell::new(0));
+
+            let mut next_frame = {
+                let callback = callback.clone();
+                let handle = handle.clone();
+                in_current_runtime(move |()| {
+                    let next = Some(measure(&element));
+                    if *rect.peek() != next {
+                        rect.set(next);
+                    }
+                    request_frame(&callback, &handle);
+                })
+            };
+            callback.replace(Some(Closure::<dyn FnMut()>::new(move || next_frame(()))));
+            request_frame(&callback, &handle);
+
+            Some(FrameLoop { callback, handle })
+        }
+    }
+
+    impl Drop for FrameLoop {
+        fn drop(&mut self) {
+            if let Some(window) = web_sys::window() {
+                let _ = window.cancel_animation_frame(self.handle.get());
+            }
+            // 閉包持有自己的 Rc，必須手動打破循環
+            self.callback.borrow_mut().take();
+        }
+    }
+}
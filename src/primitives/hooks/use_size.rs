@@ -0,0 +1,89 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// The border-box size of an element, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Track the size of `element` with a `ResizeObserver`.
+///
+/// The size is `None` while the element is not mounted, and always `None`
+/// outside the `web` feature, where sizes cannot be observed.
+pub fn use_size(element: Signal<Option<Rc<MountedData>>>) -> ReadOnlySignal<Option<Size>> {
+    let size = use_signal(|| None);
+
+    #[cfg(feature = "web")]
+    {
+        let observer = use_hook(|| Rc::new(std::cell::RefCell::new(None::<web::Observer>)));
+        use_effect(move || {
+            let mut size = size;
+            let node = element.read().clone();
+            // 換成新的元素時，舊的 observer 會在 drop 時斷開
+            let next = node
+                .as_deref()
+                .and_then(|node| web::Observer::new(node, size));
+            if next.is_none() {
+                size.set(None);
+            }
+            observer.replace(next);
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = element;
+
+    size.into()
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::Size;
+    use crate::primitives::dom::{in_current_runtime, web_element};
+    use dioxus::prelude::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    pub(super) struct Observer {
+        observer: web_sys::ResizeObserver,
+        _callback: Closure<dyn FnMut(wasm_bindgen::JsValue)>,
+    }
+
+    fn measure(element: &web_sys::Element) -> Option<Size> {
+        let element = element.dyn_ref::<web_sys::HtmlElement>()?;
+        Some(Size {
+            width: element.offset_width() as f64,
+            height: element.offset_height() as f64,
+        })
+    }
+
+    impl Observer {
+        pub(super) fn new(node: &MountedData, mut size: Signal<Option<Size>>) -> Option<Self> {
+            let element = web_element(node)?;
+
+            let callback = Closure::<dyn FnMut(wasm_bindgen::JsValue)>::new(in_current_runtime({
+                let element = element.clone();
+                move |_| {
+                    let next = measure(&element);
+                    if *size.peek() != next {
+                        size.set(next);
+                    }
+                }
+            }));
+            let observer = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+            observer.observe(&element);
+
+            Some(Observer {
+                observer,
+                _callback: callback,
+            })
+        }
+    }
+
+    impl Drop for Observer {
+        fn drop(&mut self) {
+            self.observer.disconnect();
+        }
+    }
+}
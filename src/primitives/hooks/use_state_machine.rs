@@ -0,0 +1,183 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+type Guard<S, E> = Rc<dyn Fn(&S, &E) -> bool>;
+type Action<E> = Rc<dyn Fn(&E)>;
+
+struct Transition<S, E> {
+    // None 表示任何狀態都適用
+    from: Option<S>,
+    event: E,
+    to: S,
+    guard: Option<Guard<S, E>>,
+}
+
+/// The states, events and transitions of a [`StateMachine`], passed to
+/// [`use_state_machine`].
+///
+/// Transitions are tried in the order they were added; the first one whose
+/// state and event match and whose guard passes is taken.
+pub struct MachineConfig<S, E> {
+    initial: S,
+    transitions: Vec<Transition<S, E>>,
+    on_enter: Vec<(S, Action<E>)>,
+    on_exit: Vec<(S, Action<E>)>,
+}
+
+impl<S: Clone + PartialEq + 'static, E: PartialEq + 'static> MachineConfig<S, E> {
+    /// A machine starting in `initial`, without transitions.
+    pub fn new(initial: S) -> Self {
+        MachineConfig {
+            initial,
+            transitions: Vec::new(),
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+
+    /// Move from `from` to `to` on `event`.
+    pub fn on(mut self, from: S, event: E, to: S) -> Self {
+        self.transitions.push(Transition {
+            from: Some(from),
+            event,
+            to,
+            guard: None,
+        });
+        self
+    }
+
+    /// Move from `from` to `to` on `event` if `guard` returns true for the
+    /// current state and the event.
+    pub fn on_if(
+        mut self,
+        from: S,
+        event: E,
+        to: S,
+        guard: impl Fn(&S, &E) -> bool + 'static,
+    ) -> Self {
+        self.transitions.push(Transition {
+            from: Some(from),
+            event,
+            to,
+            guard: Some(Rc::new(guard)),
+        });
+        self
+    }
+
+    /// Move to `to` on `event` from any state without a more specific
+    /// transition added before.
+    pub fn on_any(mut self, event: E, to: S) -> Self {
+        self.transitions.push(Transition {
+            from: None,
+            event,
+            to,
+            guard: None,
+        });
+        self
+    }
+
+    /// Call `action` with the triggering event whenever `state` is entered.
+    pub fn enter(mut self, state: S, action: impl Fn(&E) + 'static) -> Self {
+        self.on_enter.push((state, Rc::new(action)));
+        self
+    }
+
+    /// Call `action` with the triggering event whenever `state` is left.
+    pub fn exit(mut self, state: S, action: impl Fn(&E) + 'static) -> Self {
+        self.on_exit.push((state, Rc::new(action)));
+        self
+    }
+
+    fn target(&self, state: &S, event: &E) -> Option<S> {
+        self.transitions
+            .iter()
+            .filter(|transition| transition.from.as_ref().is_none_or(|from| from == state))
+            .filter(|transition| transition.event == *event)
+            .find(|transition| {
+                transition
+                    .guard
+                    .as_ref()
+                    .is_none_or(|guard| guard(state, event))
+            })
+            .map(|transition| transition.to.clone())
+    }
+}
+
+/// A handle to a state machine created by [`use_state_machine`].
+pub struct StateMachine<S: 'static, E: 'static> {
+    state: Signal<S>,
+    config: Signal<Rc<MachineConfig<S, E>>>,
+}
+
+impl<S: 'static, E: 'static> Clone for StateMachine<S, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: 'static, E: 'static> Copy for StateMachine<S, E> {}
+
+impl<S: Clone + PartialEq + 'static, E: PartialEq + 'static> StateMachine<S, E> {
+    /// The current state. Reading it subscribes the caller to transitions.
+    pub fn state(&self) -> S {
+        self.state.read().clone()
+    }
+
+    /// The current state, without subscribing.
+    pub fn peek(&self) -> S {
+        self.state.peek().clone()
+    }
+
+    /// Whether the machine is in `state`.
+    pub fn matches(&self, state: &S) -> bool {
+        *self.state.read() == *state
+    }
+
+    /// Whether `event` would cause a transition in the current state.
+    pub fn can_send(&self, event: &E) -> bool {
+        let config = self.config.peek().clone();
+        config.target(&self.state.peek(), event).is_some()
+    }
+
+    /// Apply `event` to the machine. Returns whether a transition was taken.
+    ///
+    /// The exit actions of the old state run before the state changes and
+    /// the entry actions of the new state after, so actions may send further
+    /// events. A transition to the same state runs both.
+    pub fn send(&mut self, event: E) -> bool {
+        let config = self.config.peek().clone();
+        let from = self.state.peek().clone();
+        let Some(to) = config.target(&from, &event) else {
+            return false;
+        };
+
+        for (state, action) in &config.on_exit {
+            if *state == from {
+                action(&event);
+            }
+        }
+        self.state.set(to.clone());
+        for (state, action) in &config.on_enter {
+            if *state == to {
+                action(&event);
+            }
+        }
+        true
+    }
+}
+
+/// A typed finite state machine, for primitives whose behavior is easier to
+/// describe as states and events than as a set of flags, e.g. a tooltip that
+/// is closed, waiting to open, open or closing.
+///
+/// `config` is called once; actions and guards capture what they need when
+/// the machine is created, so pass them signals or other `Copy` handles
+/// rather than prop values.
+pub fn use_state_machine<S: Clone + PartialEq + 'static, E: PartialEq + 'static>(
+    config: impl FnOnce() -> MachineConfig<S, E>,
+) -> StateMachine<S, E> {
+    let config = use_signal(|| Rc::new(config()));
+    let state = use_signal(|| config.peek().initial.clone());
+
+    StateMachine { state, config }
+}
@@ -0,0 +1,69 @@
+//! Signals persisted to web storage. Requires the `web` feature.
+
+use super::use_global_event::use_window_event;
+use crate::primitives::context::create_persistent_context::{StorageKind, load, save, web_storage};
+use dioxus::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+fn use_storage<T>(storage: StorageKind, key: &str, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: 'static + Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    // 鍵值只在第一次渲染時讀取
+    let key = use_hook(|| key.to_string());
+    let default = use_hook(init);
+    let mut signal = use_signal({
+        let key = key.clone();
+        let default = default.clone();
+        move || load(storage, &key).unwrap_or(default)
+    });
+
+    use_effect({
+        let key = key.clone();
+        move || save(storage, &key, &*signal.read())
+    });
+
+    // 其他分頁修改同一個鍵時同步；清除時回到預設值
+    use_window_event("storage", false, move |event: web_sys::StorageEvent| {
+        if event.storage_area() != web_storage(storage) {
+            return;
+        }
+        let next = match event.key() {
+            Some(changed) if changed == key => event
+                .new_value()
+                .and_then(|raw| serde_json::from_str(&raw).ok())
+                .unwrap_or_else(|| default.clone()),
+            Some(_) => return,
+            // key 為 null 表示整個 storage 被清除
+            None => default.clone(),
+        };
+        if *signal.peek() != next {
+            signal.set(next);
+        }
+    });
+
+    signal
+}
+
+/// A signal saved to `localStorage` under `key` as JSON, shared with other
+/// tabs of the same origin.
+///
+/// The stored value is loaded on mount, falling back to `init` when nothing
+/// (or nothing deserializable) is stored. Every change is written back, and
+/// changes made in other tabs update the signal.
+pub fn use_local_storage<T>(key: &str, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: 'static + Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    use_storage(StorageKind::Local, key, init)
+}
+
+/// Like [`use_local_storage`], saved to `sessionStorage`, which is cleared
+/// when the tab closes.
+pub fn use_session_storage<T>(key: &str, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: 'static + Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    use_storage(StorageKind::Session, key, init)
+}
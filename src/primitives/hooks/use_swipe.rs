@@ -0,0 +1,213 @@
+use super::use_event_callback::use_event_callback;
+use crate::primitives::time::now;
+use dioxus::html::geometry::ClientPoint;
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+
+/// How far, in pixels, the pointer must move before a swipe starts, so taps
+/// and clicks are not reported as swipes.
+const SLOP: f64 = 5.0;
+
+/// Velocities older than this are treated as zero: the pointer stopped
+/// before it was released.
+const VELOCITY_WINDOW_MS: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The stage of a swipe reported by a [`SwipeEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipePhase {
+    /// The pointer moved past a few pixels after being pressed.
+    Start,
+    /// The pointer moved.
+    Move,
+    /// The pointer was released after travelling past the threshold or
+    /// moving fast enough.
+    End,
+    /// The pointer was released without completing the swipe, or the
+    /// browser cancelled it.
+    Cancel,
+}
+
+/// Reported by [`use_swipe`] while a pointer is dragged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwipeEvent {
+    pub phase: SwipePhase,
+    /// The dominant direction of the movement so far.
+    pub direction: SwipeDirection,
+    /// Movement since the pointer was pressed, in pixels.
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// Current velocity, in pixels per millisecond.
+    pub velocity_x: f64,
+    pub velocity_y: f64,
+    /// `"mouse"`, `"pen"` or `"touch"`.
+    pub pointer_type: String,
+}
+
+/// Configures [`use_swipe`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwipeOptions {
+    /// How far, in pixels, the pointer must travel along the swipe direction
+    /// to complete the swipe.
+    pub threshold: f64,
+    /// A release at this velocity, in pixels per millisecond, completes the
+    /// swipe even if it is shorter than the threshold.
+    pub min_velocity: f64,
+}
+
+impl Default for SwipeOptions {
+    fn default() -> Self {
+        SwipeOptions {
+            threshold: 50.0,
+            min_velocity: 0.5,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Gesture {
+    pointer_id: i32,
+    pointer_type: String,
+    origin: ClientPoint,
+    last: ClientPoint,
+    last_time: f64,
+    velocity: (f64, f64),
+    started: bool,
+}
+
+impl Gesture {
+    fn event(&self, phase: SwipePhase) -> SwipeEvent {
+        let delta = self.last - self.origin;
+        let direction = if delta.x.abs() >= delta.y.abs() {
+            if delta.x < 0.0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            }
+        } else if delta.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        };
+        SwipeEvent {
+            phase,
+            direction,
+            delta_x: delta.x,
+            delta_y: delta.y,
+            velocity_x: self.velocity.0,
+            velocity_y: self.velocity.1,
+            pointer_type: self.pointer_type.clone(),
+        }
+    }
+}
+
+/// Detect swipes and drags of a pointer pressed on an element, e.g. to
+/// dismiss a toast or close a drawer.
+///
+/// Spread the returned attributes on the swiped element. `on_swipe` is
+/// called for every [`SwipePhase`], so the element can follow the pointer
+/// while it moves. Give the element `touch-action: none` (or `pan-y` for
+/// horizontal swipes) so touch input is not taken over by scrolling.
+pub fn use_swipe(
+    options: SwipeOptions,
+    on_swipe: impl FnMut(SwipeEvent) + 'static,
+) -> Vec<Attribute> {
+    let on_swipe = use_event_callback(on_swipe);
+    let mut gesture = use_signal(|| None::<Gesture>);
+
+    let mut finish = move |event: PointerEvent, cancelled: bool| {
+        let other_pointer = gesture
+            .peek()
+            .as_ref()
+            .is_none_or(|current| current.pointer_id != event.pointer_id());
+        if other_pointer {
+            return;
+        }
+        let Some(mut current) = gesture.take() else {
+            return;
+        };
+        if !current.started {
+            return;
+        }
+        if now() - current.last_time > VELOCITY_WINDOW_MS {
+            current.velocity = (0.0, 0.0);
+        }
+        let swipe = current.event(SwipePhase::End);
+        let (distance, velocity) = match swipe.direction {
+            SwipeDirection::Left | SwipeDirection::Right => (swipe.delta_x, swipe.velocity_x),
+            SwipeDirection::Up | SwipeDirection::Down => (swipe.delta_y, swipe.velocity_y),
+        };
+        let completed =
+            distance.abs() >= options.threshold || velocity.abs() >= options.min_velocity;
+        let phase = if completed && !cancelled {
+            SwipePhase::End
+        } else {
+            SwipePhase::Cancel
+        };
+        on_swipe.call(SwipeEvent { phase, ..swipe });
+    };
+
+    vec![
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            let is_mouse = event.pointer_type() == "mouse";
+            if is_mouse && event.trigger_button() != Some(MouseButton::Primary) {
+                return;
+            }
+            #[cfg(feature = "web")]
+            crate::primitives::dom::capture_pointer(&event);
+
+            let point = event.client_coordinates();
+            gesture.set(Some(Gesture {
+                pointer_id: event.pointer_id(),
+                pointer_type: event.pointer_type(),
+                origin: point,
+                last: point,
+                last_time: now(),
+                velocity: (0.0, 0.0),
+                started: false,
+            }));
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            // 先放開寫入鎖再呼叫處理函式，處理函式可能會讀取其他 signal
+            let swipe = {
+                let mut guard = gesture.write();
+                let Some(current) = guard.as_mut() else {
+                    return;
+                };
+                if current.pointer_id != event.pointer_id() {
+                    return;
+                }
+
+                let point = event.client_coordinates();
+                let time = now();
+                let elapsed = time - current.last_time;
+                if elapsed > 0.0 {
+                    let step = point - current.last;
+                    current.velocity = (step.x / elapsed, step.y / elapsed);
+                }
+                current.last = point;
+                current.last_time = time;
+
+                let phase = if current.started {
+                    SwipePhase::Move
+                } else if (point - current.origin).length() > SLOP {
+                    current.started = true;
+                    SwipePhase::Start
+                } else {
+                    return;
+                };
+                current.event(phase)
+            };
+            on_swipe.call(swipe);
+        }),
+        dioxus_elements::events::onpointerup(move |event: PointerEvent| finish(event, false)),
+        dioxus_elements::events::onpointercancel(move |event: PointerEvent| finish(event, true)),
+    ]
+}
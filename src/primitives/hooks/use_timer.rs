@@ -0,0 +1,138 @@
+use super::use_event_callback::use_event_callback;
+use crate::primitives::context::create_context::use_synced_signal;
+use crate::primitives::time::{now, sleep};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+#[derive(Default)]
+struct TimerState {
+    task: Option<Task>,
+    // 目前這一輪的長度與開始時間，用來計算暫停時剩下的時間
+    period: Duration,
+    started_at: f64,
+    // 暫停時剩下的時間；None 表示下一次從完整的長度開始
+    remaining: Option<Duration>,
+}
+
+/// A handle to a timer created by [`use_timeout`] or [`use_interval`].
+///
+/// The timer runs in the scope of the component that created it and is
+/// cancelled when that component unmounts, whichever component calls the
+/// methods.
+#[derive(Clone, Copy)]
+pub struct Timer {
+    scope: ScopeId,
+    duration: Signal<Duration>,
+    callback: Callback<()>,
+    repeat: bool,
+    state: Signal<TimerState>,
+}
+
+impl Timer {
+    /// Start the timer from its full duration, restarting it if it is
+    /// running or paused.
+    pub fn start(&mut self) {
+        self.state.write().remaining = None;
+        let duration = *self.duration.peek();
+        self.run(duration);
+    }
+
+    /// Stop the timer, keeping the time left for [`resume`](Self::resume).
+    pub fn pause(&mut self) {
+        let mut state = self.state.write();
+        let Some(task) = state.task.take() else {
+            return;
+        };
+        task.cancel();
+        let elapsed = Duration::from_secs_f64(((now() - state.started_at) / 1000.0).max(0.0));
+        state.remaining = Some(state.period.saturating_sub(elapsed));
+    }
+
+    /// Continue a paused timer with the time it had left.
+    pub fn resume(&mut self) {
+        let remaining = {
+            let state = self.state.peek();
+            if state.task.is_some() {
+                return;
+            }
+            state.remaining
+        };
+        if let Some(remaining) = remaining {
+            self.run(remaining);
+        }
+    }
+
+    /// Stop the timer and forget any time left, so the next
+    /// [`resume`](Self::resume) does nothing and [`start`](Self::start)
+    /// begins from the full duration.
+    pub fn reset(&mut self) {
+        let mut state = self.state.write();
+        if let Some(task) = state.task.take() {
+            task.cancel();
+        }
+        state.remaining = None;
+    }
+
+    /// Whether the timer is counting down.
+    pub fn is_running(&self) -> bool {
+        self.state.read().task.is_some()
+    }
+
+    /// Whether the timer is stopped with time left.
+    pub fn is_paused(&self) -> bool {
+        let state = self.state.read();
+        state.task.is_none() && state.remaining.is_some()
+    }
+
+    fn run(&mut self, delay: Duration) {
+        let mut timer = *self;
+        let task = self.scope.in_runtime(|| {
+            spawn(async move {
+                sleep(delay).await;
+                {
+                    let mut state = timer.state.write();
+                    state.task = None;
+                    state.remaining = None;
+                }
+                // 先排好下一輪再呼叫，讓回呼可以暫停或重設 interval
+                if timer.repeat {
+                    timer.start();
+                }
+                timer.callback.call(());
+            })
+        });
+
+        let mut state = self.state.write();
+        if let Some(previous) = state.task.replace(task) {
+            previous.cancel();
+        }
+        state.period = delay;
+        state.started_at = now();
+        state.remaining = None;
+    }
+}
+
+fn use_timer(duration: Duration, repeat: bool, mut callback: impl FnMut() + 'static) -> Timer {
+    let callback = use_event_callback(move |()| callback());
+    Timer {
+        scope: use_hook(|| current_scope_id().expect("timers must be created in a component")),
+        duration: use_synced_signal(duration),
+        callback,
+        repeat,
+        state: use_signal(TimerState::default),
+    }
+}
+
+/// A timer that calls `callback` once, `duration` after it is started.
+///
+/// The timer does not start on its own; call [`Timer::start`], e.g. from an
+/// effect to start it on mount.
+pub fn use_timeout(duration: Duration, callback: impl FnMut() + 'static) -> Timer {
+    use_timer(duration, false, callback)
+}
+
+/// A timer that calls `callback` every `duration` once started, until it is
+/// paused or reset.
+pub fn use_interval(duration: Duration, callback: impl FnMut() + 'static) -> Timer {
+    use_timer(duration, true, callback)
+}
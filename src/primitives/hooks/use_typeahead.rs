@@ -0,0 +1,122 @@
+use crate::primitives::time::sleep;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// How long typed characters are kept before a new search starts.
+const RESET_AFTER: Duration = Duration::from_secs(1);
+
+/// Buffers the characters typed into a list to jump to the matching item.
+///
+/// Returned by [`use_typeahead`].
+#[derive(Clone, Copy)]
+pub struct Typeahead {
+    search: Signal<String>,
+    // 用來判斷重設計時器是否仍屬於最後一次輸入
+    sequence: Signal<u64>,
+}
+
+impl Typeahead {
+    /// Add the key of a `keydown` event to the search and return the index
+    /// of the item to move to.
+    ///
+    /// `labels` are the text values of the items in order and `current` is
+    /// the index of the focused or highlighted item. Returns `None` when
+    /// `key` is not a printable character or no other item matches; the
+    /// caller should prevent the key's default action only when this returns
+    /// `Some`.
+    pub fn type_key<S: AsRef<str>>(
+        &mut self,
+        key: &str,
+        labels: &[S],
+        current: Option<usize>,
+    ) -> Option<usize> {
+        let search = self.push(key)?;
+        let labels: Vec<&str> = labels.iter().map(AsRef::as_ref).collect();
+        find_next_match(&labels, &search, current)
+    }
+
+    /// Add `key` to the search and return the whole search, or `None` if
+    /// `key` is not a printable character.
+    pub fn push(&mut self, key: &str) -> Option<String> {
+        let mut characters = key.chars();
+        let character = characters.next()?;
+        if characters.next().is_some() || character.is_control() {
+            return None;
+        }
+        // 空白鍵在還沒開始搜尋時保留給選取
+        if character == ' ' && self.search.peek().is_empty() {
+            return None;
+        }
+
+        let search = format!("{}{character}", self.search.peek());
+        self.search.set(search.clone());
+
+        let sequence = *self.sequence.peek() + 1;
+        self.sequence.set(sequence);
+        let mut typeahead = *self;
+        spawn(async move {
+            sleep(RESET_AFTER).await;
+            if *typeahead.sequence.peek() == sequence {
+                typeahead.search.set(String::new());
+            }
+        });
+
+        Some(search)
+    }
+
+    /// The characters typed since the last reset.
+    pub fn search(&self) -> String {
+        self.search.peek().clone()
+    }
+
+    /// Drop the typed characters, e.g. when the list closes.
+    pub fn reset(&mut self) {
+        self.search.set(String::new());
+    }
+}
+
+/// A typeahead buffer for keyboard navigation in menus, selects and
+/// listboxes. Typed characters are collected until no key is pressed for
+/// one second.
+pub fn use_typeahead() -> Typeahead {
+    Typeahead {
+        search: use_signal(String::new),
+        sequence: use_signal(|| 0),
+    }
+}
+
+/// Find the item `search` should move to among `labels`, starting after
+/// `current` and wrapping around. Matching is a case-insensitive prefix
+/// match.
+///
+/// Repeating one character (e.g. "aaa") cycles through the items starting
+/// with it. Returns `None` when nothing matches or the only match is
+/// `current` itself.
+pub fn find_next_match(labels: &[&str], search: &str, current: Option<usize>) -> Option<usize> {
+    let mut characters = search.chars();
+    let first = characters.next()?;
+    let repeated = characters.all(|character| character == first);
+    let search = if repeated {
+        first.to_lowercase().collect::<String>()
+    } else {
+        search.to_lowercase()
+    };
+
+    let current = current.filter(|current| *current < labels.len());
+    let start = current.map_or(0, |current| current + 1);
+    let candidates = (0..labels.len()).map(|offset| (start + offset) % labels.len());
+    // 單一字元時從下一個項目開始找，才能在相同開頭的項目間輪替；
+    // 多個字元時目前的項目若仍符合就留在原地
+    let found = if search.chars().count() == 1 {
+        candidates
+            .filter(|index| Some(*index) != current)
+            .find(|index| labels[*index].to_lowercase().starts_with(&search))
+    } else {
+        current
+            .into_iter()
+            .chain(candidates)
+            .find(|index| labels[*index].to_lowercase().starts_with(&search))
+    };
+
+    found.filter(|index| Some(*index) != current)
+}
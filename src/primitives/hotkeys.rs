@@ -0,0 +1,451 @@
+//! Hotkeys Primitive
+//!
+//! A registry of keyboard shortcuts. A [`HotkeyProvider`] near the root
+//! listens for key presses and runs the matching shortcut registered with
+//! [`use_hotkey`]; [`HotkeyScope`]s group shortcuts so a whole section of the
+//! app, e.g. a closed panel, can turn its shortcuts off at once.
+//!
+//! Shortcuts are written like `"mod+k"` or `"shift+alt+arrowup"`. `mod` is
+//! Command on Apple platforms and Control elsewhere. When several enabled
+//! shortcuts match, the most recently registered one runs, so an open dialog
+//! can take over a shortcut of the page behind it.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_event_callback::use_event_callback;
+use dioxus::prelude::*;
+use std::error::Error;
+use std::fmt;
+
+/// A parsed keyboard shortcut.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    /// The key, lowercased as in `KeyboardEvent.key`, e.g. `"k"` or
+    /// `"arrowup"`.
+    pub key: String,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub meta: bool,
+}
+
+/// Returned by [`Hotkey::parse`] for an invalid shortcut.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyParseError {
+    /// The shortcut has only modifiers, e.g. `"ctrl+shift"`.
+    MissingKey(String),
+    /// The shortcut has more than one non-modifier key, e.g. `"a+b"`.
+    MultipleKeys(String),
+}
+
+impl fmt::Display for HotkeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyParseError::MissingKey(shortcut) => {
+                write!(f, "`{}` has no key besides its modifiers", shortcut)
+            }
+            HotkeyParseError::MultipleKeys(shortcut) => {
+                write!(f, "`{}` has more than one key", shortcut)
+            }
+        }
+    }
+}
+
+impl Error for HotkeyParseError {}
+
+/// Whether the app runs on an Apple platform, where `mod` means Command.
+pub fn is_apple_platform() -> bool {
+    #[cfg(feature = "web")]
+    {
+        web_sys::window()
+            .and_then(|window| window.navigator().platform().ok())
+            .is_some_and(|platform| {
+                ["Mac", "iPhone", "iPad", "iPod"]
+                    .iter()
+                    .any(|name| platform.contains(name))
+            })
+    }
+    #[cfg(not(feature = "web"))]
+    {
+        cfg!(any(target_os = "macos", target_os = "ios"))
+    }
+}
+
+impl Hotkey {
+    /// Parse a shortcut such as `"mod+shift+p"`.
+    ///
+    /// Modifiers are `ctrl`, `alt` (or `option`), `shift`, `meta` (or
+    /// `cmd`) and `mod`. Key names are matched case-insensitively against
+    /// `KeyboardEvent.key`; `esc`, `space` and `plus` are accepted as
+    /// aliases.
+    pub fn parse(shortcut: &str) -> Result<Hotkey, HotkeyParseError> {
+        let mut hotkey = Hotkey {
+            key: String::new(),
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        };
+        let apple = is_apple_platform();
+
+        for part in shortcut.split('+') {
+            let part = part.trim().to_lowercase();
+            match part.as_str() {
+                "ctrl" | "control" => hotkey.ctrl = true,
+                "alt" | "option" => hotkey.alt = true,
+                "shift" => hotkey.shift = true,
+                "meta" | "cmd" | "command" => hotkey.meta = true,
+                "mod" if apple => hotkey.meta = true,
+                "mod" => hotkey.ctrl = true,
+                // 空字串只會出現在 "a+" 之類的寫法中，忽略
+                "" => {}
+                key => {
+                    if !hotkey.key.is_empty() {
+                        return Err(HotkeyParseError::MultipleKeys(shortcut.to_string()));
+                    }
+                    hotkey.key = match key {
+                        "esc" => "escape".to_string(),
+                        "space" => " ".to_string(),
+                        "plus" => "+".to_string(),
+                        "up" | "down" | "left" | "right" => format!("arrow{}", key),
+                        _ => key.to_string(),
+                    };
+                }
+            }
+        }
+
+        if hotkey.key.is_empty() {
+            return Err(HotkeyParseError::MissingKey(shortcut.to_string()));
+        }
+        Ok(hotkey)
+    }
+
+    /// Whether a key press matches this shortcut exactly, modifiers
+    /// included.
+    pub fn matches(&self, key: &str, ctrl: bool, alt: bool, shift: bool, meta: bool) -> bool {
+        self.key == key.to_lowercase()
+            && self.ctrl == ctrl
+            && self.alt == alt
+            && self.shift == shift
+            && self.meta == meta
+    }
+}
+
+/// Formats the shortcut for display, e.g. `Ctrl+Shift+P`, or `⇧⌘P` on Apple
+/// platforms.
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self.key.as_str() {
+            " " => "Space".to_string(),
+            "arrowup" => "↑".to_string(),
+            "arrowdown" => "↓".to_string(),
+            "arrowleft" => "←".to_string(),
+            "arrowright" => "→".to_string(),
+            key => {
+                let mut characters = key.chars();
+                characters
+                    .next()
+                    .map(|first| first.to_uppercase().chain(characters).collect())
+                    .unwrap_or_default()
+            }
+        };
+
+        if is_apple_platform() {
+            let modifiers = [
+                (self.ctrl, "⌃"),
+                (self.alt, "⌥"),
+                (self.shift, "⇧"),
+                (self.meta, "⌘"),
+            ];
+            for (_, symbol) in modifiers.iter().filter(|(held, _)| *held) {
+                f.write_str(symbol)?;
+            }
+            f.write_str(&key)
+        } else {
+            let modifiers = [
+                (self.ctrl, "Ctrl"),
+                (self.alt, "Alt"),
+                (self.shift, "Shift"),
+                (self.meta, "Meta"),
+            ];
+            for (_, name) in modifiers.iter().filter(|(held, _)| *held) {
+                write!(f, "{}+", name)?;
+            }
+            f.write_str(&key)
+        }
+    }
+}
+
+/// Two enabled shortcuts of the same [`HotkeyScope`] with the same keys,
+/// reported by [`HotkeyProvider`]'s `on_conflict`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyConflict {
+    pub hotkey: Hotkey,
+}
+
+/// Configures [`use_hotkey_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotkeyOptions {
+    /// Turn the shortcut off without unregistering it.
+    pub enabled: bool,
+    /// Also run while typing in a text field or editable element.
+    pub allow_in_editable: bool,
+    /// Prevent the browser's default action for the key press.
+    pub prevent_default: bool,
+}
+
+impl Default for HotkeyOptions {
+    fn default() -> Self {
+        HotkeyOptions {
+            enabled: true,
+            allow_in_editable: false,
+            prevent_default: true,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Registration {
+    id: usize,
+    hotkey: Hotkey,
+    scope: Option<usize>,
+    scope_enabled: Option<Memo<bool>>,
+    options: Signal<HotkeyOptions>,
+    callback: Callback<()>,
+}
+
+impl Registration {
+    fn is_enabled(&self) -> bool {
+        self.options.peek().enabled && self.scope_enabled.is_none_or(|enabled| *enabled.peek())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct HotkeyContext {
+    registrations: Signal<Vec<Registration>>,
+    next_id: Signal<usize>,
+    on_conflict: Signal<Option<EventHandler<HotkeyConflict>>>,
+}
+
+impl HotkeyContext {
+    fn next_id(&mut self) -> usize {
+        let id = *self.next_id.peek() + 1;
+        self.next_id.set(id);
+        id
+    }
+
+    /// Run the newest enabled shortcut matching the key press. Returns the
+    /// options of the shortcut that ran.
+    fn dispatch(&self, press: &KeyPress) -> Option<HotkeyOptions> {
+        let registration = self
+            .registrations
+            .peek()
+            .iter()
+            .rev()
+            .filter(|registration| registration.is_enabled())
+            .filter(|registration| {
+                !press.in_editable || registration.options.peek().allow_in_editable
+            })
+            .find(|registration| {
+                registration.hotkey.matches(
+                    &press.key,
+                    press.ctrl,
+                    press.alt,
+                    press.shift,
+                    press.meta,
+                )
+            })
+            .cloned()?;
+        registration.callback.call(());
+        Some(*registration.options.peek())
+    }
+}
+
+struct KeyPress {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+    in_editable: bool,
+}
+
+#[derive(Clone, Copy)]
+struct HotkeyScopeContext {
+    id: usize,
+    enabled: Memo<bool>,
+}
+
+/// Listens for the shortcuts registered with [`use_hotkey`] below it.
+///
+/// Place it once near the root of the app. With the `web` feature it
+/// listens on the whole document; elsewhere only key presses inside its
+/// children are seen.
+#[component]
+pub fn HotkeyProvider(
+    /// Called when a shortcut is registered while another enabled shortcut of
+    /// the same scope already uses the same keys.
+    on_conflict: Option<EventHandler<HotkeyConflict>>,
+    children: Element,
+) -> Element {
+    let on_conflict_signal = use_synced_signal(on_conflict);
+    let ctx = use_context_provider(|| HotkeyContext {
+        registrations: Signal::new(Vec::new()),
+        next_id: Signal::new(0),
+        on_conflict: on_conflict_signal,
+    });
+
+    #[cfg(feature = "web")]
+    {
+        super::hooks::use_global_event::use_document_event(
+            "keydown",
+            false,
+            move |event: web_sys::KeyboardEvent| {
+                if event.default_prevented() {
+                    return;
+                }
+                let press = KeyPress {
+                    key: event.key(),
+                    ctrl: event.ctrl_key(),
+                    alt: event.alt_key(),
+                    shift: event.shift_key(),
+                    meta: event.meta_key(),
+                    in_editable: web::is_editable(event.target()),
+                };
+                if ctx
+                    .dispatch(&press)
+                    .is_some_and(|options| options.prevent_default)
+                {
+                    event.prevent_default();
+                }
+            },
+        );
+
+        rsx! { {children} }
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        rsx! {
+        div {
+            style: "display: contents",
+            onkeydown: move |event: KeyboardEvent| {
+                let modifiers = event.modifiers();
+                let press = KeyPress {
+                    key: event.key().to_string(),
+                    ctrl: modifiers.ctrl(),
+                    alt: modifiers.alt(),
+                    shift: modifiers.shift(),
+                    meta: modifiers.meta(),
+                    in_editable: false,
+                };
+                if ctx.dispatch(&press).is_some_and(|options| options.prevent_default) {
+                    event.prevent_default();
+                }
+            },
+            {children}
+        }
+        }
+    }
+}
+
+/// Groups the shortcuts registered below it, e.g. those of one panel, so
+/// they can be turned off together.
+///
+/// A disabled scope also disables the scopes nested in it.
+#[component]
+pub fn HotkeyScope(#[props(default = true)] enabled: bool, children: Element) -> Element {
+    let parent = try_use_context::<HotkeyScopeContext>();
+    let id = use_hook(|| {
+        try_use_context::<HotkeyContext>()
+            .map(|mut ctx| ctx.next_id())
+            .unwrap_or_default()
+    });
+    let enabled = use_synced_signal(enabled);
+    let enabled = use_memo(move || enabled() && parent.is_none_or(|parent| (parent.enabled)()));
+    use_context_provider(|| HotkeyScopeContext { id, enabled });
+
+    rsx! { {children} }
+}
+
+/// Run `callback` when `shortcut` is pressed, e.g. `"mod+k"`, while the
+/// calling component is mounted.
+///
+/// Does nothing without a [`HotkeyProvider`] above the caller.
+///
+/// # Panics
+///
+/// Panics if `shortcut` is not a valid shortcut; see [`Hotkey::parse`].
+pub fn use_hotkey(shortcut: &str, callback: impl FnMut() + 'static) {
+    use_hotkey_with(shortcut, HotkeyOptions::default(), callback);
+}
+
+/// Like [`use_hotkey`], with [`HotkeyOptions`].
+pub fn use_hotkey_with(
+    shortcut: &str,
+    options: HotkeyOptions,
+    mut callback: impl FnMut() + 'static,
+) {
+    let ctx = try_use_context::<HotkeyContext>();
+    let scope = try_use_context::<HotkeyScopeContext>();
+    let callback = use_event_callback(move |()| callback());
+    let options = use_synced_signal(options);
+    let hotkey = use_hook(|| match Hotkey::parse(shortcut) {
+        Ok(hotkey) => hotkey,
+        Err(error) => panic!("invalid hotkey: {}", error),
+    });
+
+    let id = use_hook(move || {
+        let mut ctx = ctx?;
+        let registration = Registration {
+            id: ctx.next_id(),
+            hotkey: hotkey.clone(),
+            scope: scope.map(|scope| scope.id),
+            scope_enabled: scope.map(|scope| scope.enabled),
+            options,
+            callback,
+        };
+
+        let conflict = registration.is_enabled()
+            && ctx.registrations.peek().iter().any(|existing| {
+                existing.scope == registration.scope
+                    && existing.hotkey == registration.hotkey
+                    && existing.is_enabled()
+            });
+        if conflict {
+            if let Some(on_conflict) = *ctx.on_conflict.peek() {
+                on_conflict.call(HotkeyConflict { hotkey });
+            }
+        }
+
+        let id = registration.id;
+        ctx.registrations.write().push(registration);
+        Some(id)
+    });
+
+    use_drop(move || {
+        if let (Some(mut ctx), Some(id)) = (ctx, id) {
+            ctx.registrations
+                .write()
+                .retain(|registration| registration.id != id);
+        }
+    });
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use wasm_bindgen::JsCast;
+
+    // 在文字欄位中輸入時不觸發快捷鍵
+    pub(super) fn is_editable(target: Option<web_sys::EventTarget>) -> bool {
+        let Some(element) = target.and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        else {
+            return false;
+        };
+        matches!(
+            element.tag_name().to_lowercase().as_str(),
+            "input" | "textarea" | "select"
+        ) || element
+            .dyn_ref::<web_sys::HtmlElement>()
+            .is_some_and(|element| element.is_content_editable())
+    }
+}
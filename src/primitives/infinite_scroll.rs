@@ -0,0 +1,168 @@
+//! InfiniteScroll Primitive
+//!
+//! A sentinel placed at the end of a scrolling list that asks for more
+//! items once it comes within `distance` of the visible area of its
+//! scrolling ancestor, using an `IntersectionObserver`.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     div { style: "overflow-y: auto; height: 400px",
+//!         for message in messages() { Message { message } }
+//!         InfiniteScroll {
+//!             loading: loading(),
+//!             exhausted: !has_more(),
+//!             on_load_more: move |_| fetch_next_page(),
+//!             "Loading…"
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! For a chat, put a second sentinel with `edge: ScrollEdge::Start` before
+//! the first item to load older messages; the scroll position is kept when
+//! they are inserted above the visible ones.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_intersection_observer::{IntersectionOptions, use_intersection_observer};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// The end of a list an [`InfiniteScroll`] loads items at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollEdge {
+    /// Before the first item, e.g. older messages in a chat.
+    Start,
+    /// After the last item.
+    #[default]
+    End,
+}
+
+impl ScrollEdge {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ScrollEdge::Start => "start",
+            ScrollEdge::End => "end",
+        }
+    }
+}
+
+/// Calls `on_load_more` while the sentinel is near the visible area.
+///
+/// Set `loading` while the items are fetched: the sentinel asks again once
+/// loading ends if it is still in range, e.g. when the new items did not
+/// fill the list. Set `exhausted` once there is nothing left to load.
+/// `children` are rendered inside the sentinel, e.g. a spinner.
+#[component]
+pub fn InfiniteScroll(
+    on_load_more: EventHandler<ScrollEdge>,
+    #[props(default)] edge: ScrollEdge,
+    #[props(default)] loading: bool,
+    #[props(default)] exhausted: bool,
+    /// How close the sentinel must be to the visible area, in CSS `margin`
+    /// syntax.
+    #[props(default = String::from("200px"))]
+    distance: String,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let intersection = use_intersection_observer(
+        node,
+        IntersectionOptions {
+            root_margin: distance,
+            scroll_parent: true,
+            ..Default::default()
+        },
+    );
+    let idle = use_synced_signal(!loading && !exhausted && !disabled);
+    let edge_signal = use_synced_signal(edge);
+
+    use_effect(move || {
+        let near = intersection().is_some_and(|intersection| intersection.is_intersecting);
+        if near && idle() {
+            on_load_more.call(*edge_signal.peek());
+        }
+    });
+
+    // 在上方插入項目後，維持畫面上的內容不動
+    #[cfg(feature = "web")]
+    {
+        let mut previous = use_signal(|| None::<(f64, f64)>);
+        use_effect(use_reactive!(|(loading, edge)| {
+            let Some(node) = node.peek().clone() else {
+                return;
+            };
+            if edge != ScrollEdge::Start {
+                return;
+            }
+            if loading {
+                previous.set(web::scroll_position(&node));
+            } else if let Some(previous) = previous.take() {
+                web::keep_position(&node, previous);
+            }
+        }));
+    }
+
+    let state = if exhausted {
+        "exhausted"
+    } else if loading {
+        "loading"
+    } else {
+        "idle"
+    };
+    let own_attributes = vec![
+        Attribute::new("data-state", state, None, false),
+        Attribute::new("data-edge", edge.as_str(), None, false),
+        Attribute::new("aria-busy", loading.then_some("true"), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use crate::primitives::dom::{document, scroll_parent, web_element};
+    use dioxus::prelude::*;
+
+    fn scroller(node: &MountedData) -> Option<web_sys::Element> {
+        let element = web_element(node)?;
+        scroll_parent(&element).or_else(|| document()?.scrolling_element())
+    }
+
+    /// The scroll height and offset of the scrolling ancestor.
+    pub(super) fn scroll_position(node: &MountedData) -> Option<(f64, f64)> {
+        scroller(node).map(|scroller| {
+            (
+                scroller.scroll_height() as f64,
+                scroller.scroll_top() as f64,
+            )
+        })
+    }
+
+    // 以絕對位置設定，瀏覽器已自行調整（scroll anchoring）時不會重複加上
+    pub(super) fn keep_position(node: &MountedData, (height, top): (f64, f64)) {
+        if let Some(scroller) = scroller(node) {
+            let added = scroller.scroll_height() as f64 - height;
+            if added > 0.0 {
+                scroller.set_scroll_top((top + added) as i32);
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! LayerStack Primitive
+//!
+//! A single, app-wide stack of the overlays that are currently open, such as
+//! dialogs, menus and toasts. Layers are ordered by when they opened, the last
+//! one being on top; overlays use the stack to decide which of them reacts to
+//! Escape or outside clicks and which z-index to use.
+//!
+//! Layers opened from inside another layer, e.g. a dialog opened from a
+//! dialog, record it as their parent, so overlays can tell how deeply they
+//! are nested.
+//!
+//! While a modal layer is open, everything outside it and the layers above
+//! it is hidden from assistive technology and made `inert`. Managing the
+//! document requires the `web` feature; elsewhere only the stack is kept.
+
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The z-index of the bottom layer; each layer above adds one.
+pub const BASE_Z_INDEX: usize = 1000;
+
+/// Identifies a layer in the [`LayerStack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayerId(usize);
+
+/// Configures [`use_layer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LayerOptions {
+    /// Hide and disable everything outside this layer and the layers above
+    /// it, e.g. for dialogs.
+    pub modal: bool,
+    /// Block pointer events on everything below this layer.
+    pub disable_outside_pointer_events: bool,
+}
+
+/// An open layer, as listed by [`LayerStack::layers`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layer {
+    pub id: LayerId,
+    /// The layer this one was opened from, if it is still open.
+    pub parent: Option<LayerId>,
+    pub options: LayerOptions,
+    node: Signal<Option<Rc<MountedData>>>,
+}
+
+static NEXT_LAYER_ID: AtomicUsize = AtomicUsize::new(0);
+
+// 提供給在圖層內開啟的圖層，記錄巢狀關係
+#[derive(Clone, Copy)]
+struct ParentLayer(LayerId);
+
+// 目前開啟的圖層，依開啟順序排列，最後一個在最上層
+static LAYERS: GlobalSignal<Vec<Layer>> = Signal::global(Vec::new);
+
+/// Queries the stack of open layers.
+///
+/// Reading the stack from a component subscribes it to layers opening and
+/// closing.
+pub struct LayerStack;
+
+impl LayerStack {
+    /// The open layers, from the bottom to the top.
+    pub fn layers() -> Vec<Layer> {
+        LAYERS.read().clone()
+    }
+
+    /// The layer on top of the stack.
+    pub fn topmost() -> Option<LayerId> {
+        LAYERS.read().last().map(|layer| layer.id)
+    }
+
+    /// Whether `id` is the layer on top of the stack.
+    pub fn is_topmost(id: LayerId) -> bool {
+        LAYERS.read().last().is_some_and(|layer| layer.id == id)
+    }
+
+    /// The position of `id` from the bottom of the stack, if it is open.
+    pub fn index(id: LayerId) -> Option<usize> {
+        LAYERS.read().iter().position(|layer| layer.id == id)
+    }
+
+    /// The z-index for `id`, so layers opened later are drawn on top.
+    pub fn z_index(id: LayerId) -> Option<usize> {
+        Self::index(id).map(|index| BASE_Z_INDEX + index)
+    }
+
+    /// How many open layers `id` is nested in: 0 for a layer opened from the
+    /// page, 1 for a layer opened from it, and so on.
+    pub fn depth(id: LayerId) -> Option<usize> {
+        let layers = LAYERS.read();
+        let find = |id: LayerId| layers.iter().find(|layer| layer.id == id);
+        let mut parent = find(id)?.parent;
+        let mut depth = 0;
+        while let Some(layer) = parent.and_then(find) {
+            depth += 1;
+            parent = layer.parent;
+        }
+        Some(depth)
+    }
+
+    /// The open layers opened from inside `id`.
+    pub fn nested(id: LayerId) -> Vec<LayerId> {
+        LAYERS
+            .read()
+            .iter()
+            .filter(|layer| layer.parent == Some(id))
+            .map(|layer| layer.id)
+            .collect()
+    }
+
+    /// Whether any modal layer is open.
+    pub fn has_modal() -> bool {
+        LAYERS.read().iter().any(|layer| layer.options.modal)
+    }
+
+    /// Whether `id` may receive pointer events: only layers at or above the
+    /// highest layer that disables outside pointer events can.
+    pub fn pointer_events_enabled(id: LayerId) -> bool {
+        let layers = LAYERS.read();
+        let highest_disabling = layers
+            .iter()
+            .rposition(|layer| layer.options.disable_outside_pointer_events);
+        let position = layers.iter().position(|layer| layer.id == id);
+
+        match (highest_disabling, position) {
+            (Some(highest), Some(position)) => position >= highest,
+            _ => true,
+        }
+    }
+}
+
+/// Push a layer rendered in `node` on the stack while the calling component
+/// is mounted.
+///
+/// The layer keeps its position while its options change; it moves to the
+/// top only when it is mounted again. Layers pushed by descendants of the
+/// calling component are nested in it.
+pub fn use_layer(node: Signal<Option<Rc<MountedData>>>, options: LayerOptions) -> LayerId {
+    let id = use_hook(|| LayerId(NEXT_LAYER_ID.fetch_add(1, Ordering::Relaxed)));
+    let parent = try_use_context::<ParentLayer>().map(|ParentLayer(parent)| parent);
+    use_context_provider(|| ParentLayer(id));
+
+    use_effect(use_reactive!(|(options,)| {
+        let layer = Layer {
+            id,
+            parent,
+            options,
+            node,
+        };
+        let mut layers = LAYERS.write();
+        match layers.iter_mut().find(|layer| layer.id == id) {
+            Some(existing) => *existing = layer,
+            None => layers.push(layer),
+        }
+    }));
+
+    // 圖層或其元素改變時重新設定背景內容
+    #[cfg(feature = "web")]
+    use_effect(move || {
+        let layers = LAYERS.read();
+        for layer in layers.iter() {
+            let _ = layer.node.read();
+        }
+        web::sync_document(&layers);
+    });
+
+    use_drop(move || {
+        let mut layers = LAYERS.write();
+        layers.retain(|layer| layer.id != id);
+        #[cfg(feature = "web")]
+        web::sync_document(&layers);
+    });
+
+    id
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::Layer;
+    use crate::primitives::dom::{document, web_element};
+    use std::cell::RefCell;
+
+    struct Hidden {
+        element: web_sys::Element,
+        aria_hidden: Option<String>,
+        inert: bool,
+    }
+
+    thread_local! {
+        // 第一個停用外部指標事件的圖層出現前，body 原本的 pointer-events
+        static ORIGINAL_BODY_POINTER_EVENTS: RefCell<Option<String>> = const { RefCell::new(None) };
+        // 被隱藏的背景元素與其原本的屬性
+        static HIDDEN: RefCell<Vec<Hidden>> = const { RefCell::new(Vec::new()) };
+    }
+
+    pub(super) fn sync_document(layers: &[Layer]) {
+        let Some(body) = document().and_then(|document| document.body()) else {
+            return;
+        };
+        sync_body_pointer_events(&body, layers);
+        sync_background(&body, layers);
+    }
+
+    fn sync_body_pointer_events(body: &web_sys::HtmlElement, layers: &[Layer]) {
+        let style = body.style();
+        let disabled = layers
+            .iter()
+            .any(|layer| layer.options.disable_outside_pointer_events);
+
+        ORIGINAL_BODY_POINTER_EVENTS.with(|original| {
+            let mut original = original.borrow_mut();
+            if disabled && original.is_none() {
+                *original = Some(
+                    style
+                        .get_property_value("pointer-events")
+                        .unwrap_or_default(),
+                );
+                let _ = style.set_property("pointer-events", "none");
+            } else if !disabled && let Some(value) = original.take() {
+                let _ = style.set_property("pointer-events", &value);
+            }
+        });
+    }
+
+    // 每次都先還原，再隱藏最上層 modal 之下的其他元素
+    fn sync_background(body: &web_sys::HtmlElement, layers: &[Layer]) {
+        HIDDEN.with(|hidden| {
+            for entry in hidden.borrow_mut().drain(..) {
+                match entry.aria_hidden {
+                    Some(value) => {
+                        let _ = entry.element.set_attribute("aria-hidden", &value);
+                    }
+                    None => {
+                        let _ = entry.element.remove_attribute("aria-hidden");
+                    }
+                }
+                if !entry.inert {
+                    let _ = entry.element.remove_attribute("inert");
+                }
+            }
+        });
+
+        let Some(modal) = layers.iter().rposition(|layer| layer.options.modal) else {
+            return;
+        };
+        let visible: Vec<web_sys::Element> = layers[modal..]
+            .iter()
+            .filter_map(|layer| layer.node.peek().as_deref().and_then(web_element))
+            .collect();
+        if visible.is_empty() {
+            return;
+        }
+
+        let mut hidden = Vec::new();
+        hide_outside(body, &visible, &mut hidden);
+        HIDDEN.with(|entries| *entries.borrow_mut() = hidden);
+    }
+
+    // 隱藏 container 中不含可見圖層的子元素，並往下處理含有可見圖層的子元素，
+    // 讓共用同一個 portal 容器的上層對話框也被隱藏
+    fn hide_outside(
+        container: &web_sys::Element,
+        visible: &[web_sys::Element],
+        hidden: &mut Vec<Hidden>,
+    ) {
+        let children = container.children();
+        for child in (0..children.length()).filter_map(|index| children.item(index)) {
+            if visible.contains(&child) {
+                continue;
+            }
+            if visible.iter().any(|element| child.contains(Some(element))) {
+                hide_outside(&child, visible, hidden);
+                continue;
+            }
+            // 保留焦點守衛、即時播報區域與腳本
+            let keep = child.has_attribute("data-primitive-focus-guard")
+                || child.has_attribute("aria-live")
+                || matches!(child.tag_name().as_str(), "SCRIPT" | "STYLE" | "LINK");
+            if keep {
+                continue;
+            }
+            let entry = Hidden {
+                aria_hidden: child.get_attribute("aria-hidden"),
+                inert: child.has_attribute("inert"),
+                element: child,
+            };
+            let _ = entry.element.set_attribute("aria-hidden", "true");
+            let _ = entry.element.set_attribute("inert", "");
+            hidden.push(entry);
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! Listbox Primitive
+//!
+//! An always visible list of options with single or multiple selection.
+//! The list takes focus as a whole and highlights one option at a time,
+//! announced with `aria-activedescendant`:
+//!
+//! - the arrow keys, Home and End move the highlight, and typing jumps to
+//!   the option starting with the typed text;
+//! - Space, Enter or a click select the highlighted option;
+//! - in multiple mode they toggle it instead, Shift with an arrow key,
+//!   Space or a click selects the range from the last selected option, and
+//!   Ctrl+A selects every option.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_typeahead::use_typeahead;
+use super::roving_focus::Orientation;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// How many options a [`Listbox`] lets the user select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    #[default]
+    Single,
+    Multiple,
+}
+
+#[derive(Clone)]
+struct ListboxItemEntry {
+    value: String,
+    text_value: String,
+    disabled: bool,
+    id: String,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Clone, Copy)]
+struct ListboxContext {
+    value: Memo<Vec<String>>,
+    set_value: Callback<Vec<String>>,
+    selection_mode: Signal<SelectionMode>,
+    disabled: Signal<bool>,
+    // 依掛載順序排列的選項
+    items: Signal<Vec<ListboxItemEntry>>,
+    highlighted: Signal<Option<String>>,
+    // 範圍選取的起點：最後一次單獨選取或切換的選項
+    anchor: Signal<Option<String>>,
+}
+
+impl ListboxContext {
+    fn enabled_items(&self) -> Vec<ListboxItemEntry> {
+        self.items
+            .peek()
+            .iter()
+            .filter(|item| !item.disabled)
+            .cloned()
+            .collect()
+    }
+
+    fn highlight(&mut self, value: String) {
+        let node = self
+            .items
+            .peek()
+            .iter()
+            .find(|item| item.value == value)
+            .and_then(|item| item.node.clone());
+        self.highlighted.set(Some(value));
+        if let Some(node) = node {
+            spawn(async move {
+                let _ = node.scroll_to(ScrollBehavior::Instant).await;
+            });
+        }
+    }
+
+    // 單選時取代選取，多選時切換
+    fn activate(&mut self, value: String) {
+        let mut selected = self.value.peek().clone();
+        match *self.selection_mode.peek() {
+            SelectionMode::Single => selected = vec![value.clone()],
+            SelectionMode::Multiple => {
+                if let Some(index) = selected.iter().position(|other| *other == value) {
+                    selected.remove(index);
+                } else {
+                    selected.push(value.clone());
+                }
+            }
+        }
+        self.anchor.set(Some(value));
+        self.set_value.call(selected);
+    }
+
+    // 選取從 anchor 到 value 之間的所有選項，加入已選取的選項中
+    fn select_range(&mut self, value: String) {
+        let items = self.enabled_items();
+        let position = |target: &str| items.iter().position(|item| item.value == target);
+        let Some(end) = position(&value) else {
+            return;
+        };
+        let start = self
+            .anchor
+            .peek()
+            .as_deref()
+            .and_then(position)
+            .unwrap_or(end);
+        let (from, to) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        let mut selected = self.value.peek().clone();
+        for item in &items[from..=to] {
+            if !selected.contains(&item.value) {
+                selected.push(item.value.clone());
+            }
+        }
+        self.set_value.call(selected);
+    }
+}
+
+/// A list of selectable options.
+///
+/// Pass `value` and `on_value_change` to control the selection, or
+/// `default_value` to let the listbox manage it. In single mode the value
+/// holds at most one option.
+#[component]
+pub fn Listbox(
+    value: Option<Vec<String>>,
+    #[props(default)] default_value: Vec<String>,
+    on_value_change: Option<EventHandler<Vec<String>>>,
+    #[props(default)] selection_mode: SelectionMode,
+    #[props(default = Orientation::Vertical)] orientation: Orientation,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let selection_mode_signal = use_synced_signal(selection_mode);
+    let disabled_signal = use_synced_signal(disabled);
+    let mut ctx = use_context_provider(|| ListboxContext {
+        value,
+        set_value,
+        selection_mode: selection_mode_signal,
+        disabled: disabled_signal,
+        items: Signal::new(Vec::new()),
+        highlighted: Signal::new(None),
+        anchor: Signal::new(None),
+    });
+    let mut typeahead = use_typeahead();
+
+    let highlighted_id = ctx.highlighted.read().as_ref().and_then(|value| {
+        ctx.items
+            .read()
+            .iter()
+            .find(|item| item.value == *value)
+            .map(|item| item.id.clone())
+    });
+    let multiple = selection_mode == SelectionMode::Multiple;
+
+    let own_attributes = vec![
+        Attribute::new("role", "listbox", None, false),
+        Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+        Attribute::new(
+            "aria-multiselectable",
+            multiple.then_some("true"),
+            None,
+            false,
+        ),
+        Attribute::new("aria-orientation", orientation.as_str(), None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("aria-activedescendant", highlighted_id, None, false),
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            // 取得焦點時標示第一個已選取的選項，否則標示第一個選項
+            if ctx.highlighted.peek().is_some() {
+                return;
+            }
+            let items = ctx.enabled_items();
+            let selected = ctx.value.peek().clone();
+            let target = items
+                .iter()
+                .find(|item| selected.contains(&item.value))
+                .or(items.first())
+                .map(|item| item.value.clone());
+            if let Some(target) = target {
+                ctx.highlight(target);
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            let items = ctx.enabled_items();
+            if items.is_empty() {
+                return;
+            }
+            let multiple = *ctx.selection_mode.peek() == SelectionMode::Multiple;
+            let modifiers = event.modifiers();
+            let current = ctx
+                .highlighted
+                .peek()
+                .as_ref()
+                .and_then(|value| items.iter().position(|item| item.value == *value));
+            let last = items.len() - 1;
+            let (previous_key, next_key) = match orientation {
+                Orientation::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
+                Orientation::Vertical => (Key::ArrowUp, Key::ArrowDown),
+            };
+
+            let key = event.key();
+            let target = if key == next_key {
+                Some(current.map_or(0, |current| (current + 1).min(last)))
+            } else if key == previous_key {
+                Some(current.map_or(last, |current| current.saturating_sub(1)))
+            } else if key == Key::Home {
+                Some(0)
+            } else if key == Key::End {
+                Some(last)
+            } else {
+                None
+            };
+            if let Some(target) = target {
+                event.prevent_default();
+                let value = items[target].value.clone();
+                ctx.highlight(value.clone());
+                if multiple && modifiers.shift() {
+                    ctx.select_range(value);
+                }
+                return;
+            }
+
+            let is_space = matches!(&key, Key::Character(character) if character == " ");
+            if key == Key::Enter || (is_space && typeahead.search().is_empty()) {
+                event.prevent_default();
+                let Some(current) = current else {
+                    return;
+                };
+                let value = items[current].value.clone();
+                if multiple && modifiers.shift() {
+                    ctx.select_range(value);
+                } else {
+                    ctx.activate(value);
+                }
+                return;
+            }
+
+            if multiple
+                && (modifiers.ctrl() || modifiers.meta())
+                && matches!(&key, Key::Character(character) if character.eq_ignore_ascii_case("a"))
+            {
+                event.prevent_default();
+                ctx.set_value
+                    .call(items.iter().map(|item| item.value.clone()).collect());
+                return;
+            }
+
+            if modifiers.ctrl() || modifiers.alt() || modifiers.meta() {
+                return;
+            }
+            let labels: Vec<&str> = items.iter().map(|item| item.text_value.as_str()).collect();
+            if let Some(index) = typeahead.type_key(&key.to_string(), &labels, current) {
+                event.prevent_default();
+                ctx.highlight(items[index].value.clone());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An option of the listbox.
+#[component]
+pub fn ListboxItem(
+    value: String,
+    /// The text used for typeahead. Defaults to `value`.
+    text_value: Option<String>,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<ListboxContext>();
+    let id = use_id();
+    let entry = ListboxItemEntry {
+        value: value.clone(),
+        text_value: text_value.unwrap_or_else(|| value.clone()),
+        disabled,
+        id: id.clone(),
+        node: None,
+    };
+
+    // 註冊或更新選項，保留已掛載的節點
+    use_effect(move || {
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|item| item.id == entry.id) {
+            Some(item) => {
+                let node = item.node.take();
+                *item = ListboxItemEntry {
+                    node,
+                    ..entry.clone()
+                };
+            }
+            None => items.push(entry.clone()),
+        }
+    });
+    {
+        let id = id.clone();
+        use_drop(move || ctx.items.write().retain(|item| item.id != id));
+    }
+
+    let selected = ctx.value.read().contains(&value);
+    let highlighted = ctx.highlighted.read().as_ref() == Some(&value);
+    let disabled = disabled || (ctx.disabled)();
+    let mount_id = id.clone();
+    let hover_value = value.clone();
+    let own_attributes = vec![
+        Attribute::new("role", "option", None, false),
+        Attribute::new("id", id, None, false),
+        Attribute::new("aria-selected", selected, None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new(
+            "data-state",
+            if selected { "checked" } else { "unchecked" },
+            None,
+            false,
+        ),
+        Attribute::new("data-highlighted", highlighted.then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            if let Some(item) = ctx
+                .items
+                .write()
+                .iter_mut()
+                .find(|item| item.id == mount_id)
+            {
+                item.node = Some(event.data());
+            }
+        }),
+        dioxus_elements::events::onpointermove(move |_: PointerEvent| {
+            if !disabled && *ctx.highlighted.peek() != Some(hover_value.clone()) {
+                ctx.highlighted.set(Some(hover_value.clone()));
+            }
+        }),
+        dioxus_elements::events::onclick(move |event: MouseEvent| {
+            if disabled {
+                return;
+            }
+            ctx.highlighted.set(Some(value.clone()));
+            let multiple = *ctx.selection_mode.peek() == SelectionMode::Multiple;
+            if multiple && event.modifiers().shift() {
+                ctx.select_range(value.clone());
+            } else {
+                ctx.activate(value.clone());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Groups related options; name it with `aria-label` or
+/// `aria-labelledby`.
+#[component]
+pub fn ListboxGroup(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let own_attributes = vec![Attribute::new("role", "group", None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Marquee Primitive
+//!
+//! Scrolls content that does not fit its container in a continuous loop,
+//! like a news ticker. Content that fits is shown as is. The loop pauses
+//! while the pointer is over it or focus is inside it, and does not run
+//! when the user prefers reduced motion; the content can then be scrolled
+//! by hand instead.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Marquee { speed: 40.0, gap: 32.0,
+//!         for headline in headlines() { span { {headline} } }
+//!     }
+//! }
+//! ```
+//!
+//! A copy of the content follows it for a seamless loop. The copy is
+//! hidden from assistive technology and cannot be focused.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_focus_within::use_focus_within;
+use super::hooks::use_prefers_reduced_motion::use_prefers_reduced_motion;
+use super::hooks::use_size::{Size, use_size};
+use super::slot::merge_attributes;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// The direction a [`Marquee`]'s content moves in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarqueeDirection {
+    #[default]
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl MarqueeDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MarqueeDirection::Left => "left",
+            MarqueeDirection::Right => "right",
+            MarqueeDirection::Up => "up",
+            MarqueeDirection::Down => "down",
+        }
+    }
+
+    fn vertical(&self) -> bool {
+        matches!(self, MarqueeDirection::Up | MarqueeDirection::Down)
+    }
+}
+
+/// A container scrolling its overflowing content in a loop.
+///
+/// `speed` is in pixels per second, and `gap` separates the end of the
+/// content from the start of its copy, in pixels. Set `paused` to stop the
+/// loop from outside, e.g. with a pause button.
+#[component]
+pub fn Marquee(
+    #[props(default = 50.0)] speed: f64,
+    #[props(default)] direction: MarqueeDirection,
+    #[props(default)] gap: f64,
+    #[props(default)] paused: bool,
+    #[props(default = true)] pause_on_hover: bool,
+    #[props(default = true)] pause_on_focus: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    let mut content = use_signal(|| None::<Rc<MountedData>>);
+    let mut track = use_signal(|| None::<Rc<MountedData>>);
+    let container_size = use_size(container);
+    let content_size = use_size(content);
+    let reduced_motion = use_prefers_reduced_motion();
+    let focus_within = use_focus_within();
+    let mut hovered = use_signal(|| false);
+
+    let vertical = direction.vertical();
+    let length = |size: Option<Size>| {
+        size.map_or(0.0, |size| if vertical { size.height } else { size.width })
+    };
+    let overflowing = length(content_size()) > length(container_size());
+    let animated = overflowing && !reduced_motion();
+    let is_paused = paused
+        || (pause_on_hover && hovered())
+        || (pause_on_focus && (focus_within.focus_within)());
+
+    // 內容加上間距的長度，移動這段距離後畫面與起點相同
+    let distance = length(content_size()) + gap;
+    let animation = use_synced_signal((animated, distance, speed, direction));
+    let is_paused_signal = use_synced_signal(is_paused);
+
+    #[cfg(feature = "web")]
+    {
+        let running = use_hook(|| Rc::new(std::cell::RefCell::new(None::<web::Loop>)));
+        let loop_for_pause = running.clone();
+        use_effect(move || {
+            let (animated, distance, speed, direction) = animation();
+            let node = track();
+            // 換成新的動畫時，舊的會在 drop 時取消
+            let next = match (animated && speed > 0.0, node) {
+                (true, Some(node)) => web::Loop::new(&node, distance, speed, direction),
+                _ => None,
+            };
+            if let Some(next) = &next {
+                next.set_paused(*is_paused_signal.peek());
+            }
+            running.replace(next);
+        });
+        use_effect(move || {
+            let paused = is_paused_signal();
+            if let Some(running) = loop_for_pause.borrow().as_ref() {
+                running.set_paused(paused);
+            }
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (animation, is_paused_signal, track);
+
+    let overflow = match (animated, vertical) {
+        (true, _) => "overflow: hidden;",
+        (false, true) => "overflow-y: auto;",
+        (false, false) => "overflow-x: auto;",
+    };
+    let mut own_attributes = focus_within.attributes;
+    own_attributes.extend([
+        Attribute::new("style", overflow, None, false),
+        Attribute::new("data-direction", direction.as_str(), None, false),
+        Attribute::new("data-overflowing", overflowing.then_some(""), None, false),
+        Attribute::new(
+            "data-paused",
+            (animated && is_paused).then_some(""),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            container.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onpointerenter(move |_: PointerEvent| hovered.set(true)),
+        dioxus_elements::events::onpointerleave(move |_: PointerEvent| hovered.set(false)),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    // 橫向時讓軌道依內容撐開，不受容器寬度壓縮
+    let (flex_direction, track_size) = if vertical {
+        ("column", "")
+    } else {
+        ("row", " width: max-content;")
+    };
+    let track_style = format!(
+        "display: flex; flex-direction: {flex_direction}; gap: {gap}px;{track_size} \
+         will-change: transform;"
+    );
+    let group_style = format!("display: flex; flex-direction: {flex_direction}; flex-shrink: 0;");
+
+    rsx! {
+        div {
+            ..attributes,
+            div {
+                style: track_style,
+                onmounted: move |event: MountedEvent| track.set(Some(event.data())),
+                div {
+                    style: group_style.clone(),
+                    "data-marquee-content": "",
+                    onmounted: move |event: MountedEvent| content.set(Some(event.data())),
+                    {children.clone()}
+                }
+                if animated {
+                    div {
+                        style: group_style,
+                        "data-marquee-content": "",
+                        aria_hidden: "true",
+                        "inert": "",
+                        {children}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use super::MarqueeDirection;
+    use crate::primitives::dom::web_element;
+    use dioxus::prelude::*;
+    use js_sys::{Array, Function, Object, Reflect};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    /// A looping Web Animation of the track, cancelled on drop.
+    pub(super) struct Loop {
+        animation: Object,
+    }
+
+    fn call(target: &JsValue, method: &str) {
+        if let Some(function) = Reflect::get(target, &method.into())
+            .ok()
+            .and_then(|function| function.dyn_into::<Function>().ok())
+        {
+            let _ = function.call0(target);
+        }
+    }
+
+    impl Loop {
+        pub(super) fn new(
+            track: &MountedData,
+            distance: f64,
+            speed: f64,
+            direction: MarqueeDirection,
+        ) -> Option<Self> {
+            let element = web_element(track)?;
+            let animate = Reflect::get(&element, &"animate".into())
+                .ok()?
+                .dyn_into::<Function>()
+                .ok()?;
+
+            let axis = if direction.vertical() { "Y" } else { "X" };
+            let keyframes = Array::new();
+            for offset in [0.0, -distance] {
+                let frame = Object::new();
+                Reflect::set(
+                    &frame,
+                    &"transform".into(),
+                    &format!("translate{axis}({offset}px)").into(),
+                )
+                .ok()?;
+                keyframes.push(&frame);
+            }
+            // 向右與向下時反向播放
+            let reverse = matches!(direction, MarqueeDirection::Right | MarqueeDirection::Down);
+            let options = Object::new();
+            let fields: [(&str, JsValue); 4] = [
+                ("duration", (distance / speed * 1000.0).into()),
+                ("iterations", f64::INFINITY.into()),
+                ("easing", "linear".into()),
+                (
+                    "direction",
+                    if reverse { "reverse" } else { "normal" }.into(),
+                ),
+            ];
+            for (key, value) in fields {
+                Reflect::set(&options, &key.into(), &value).ok()?;
+            }
+
+            let animation = animate
+                .call2(&element, &keyframes, &options)
+                .ok()?
+                .dyn_into::<Object>()
+                .ok()?;
+            Some(Loop { animation })
+        }
+
+        pub(super) fn set_paused(&self, paused: bool) {
+            call(&self.animation, if paused { "pause" } else { "play" });
+        }
+    }
+
+    impl Drop for Loop {
+        fn drop(&mut self) {
+            call(&self.animation, "cancel");
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Masonry Primitive
+//!
+//! Lays its items out in columns of equal width, placing each item, in
+//! order, at the bottom of the shortest column so the columns end at
+//! similar heights. Items are measured and laid out again when their size
+//! or the container's width changes.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Masonry { columns: 2, breakpoints: vec![(640.0, 3), (1024.0, 4)], gap: 16.0,
+//!         for photo in photos() {
+//!             MasonryItem { key: "{photo.id}", img { src: photo.url } }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Until every item has been measured, and during server-side rendering,
+//! the items flow through CSS columns instead, which need no measurement.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_size::use_size;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct MasonryEntry {
+    id: usize,
+    height: Option<f64>,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    column: usize,
+    top: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct MasonryLayout {
+    // 所有項目都量測完成前為 None
+    placements: Option<HashMap<usize, Placement>>,
+    height: f64,
+}
+
+// 依序把項目放進目前最矮的欄
+fn balance(items: &[MasonryEntry], columns: usize, gap: f64) -> MasonryLayout {
+    if items.is_empty() {
+        return MasonryLayout::default();
+    }
+    let mut heights = vec![0.0_f64; columns.max(1)];
+    let mut placements = HashMap::new();
+    for item in items {
+        let Some(height) = item.height else {
+            return MasonryLayout::default();
+        };
+        let column = heights
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(column, _)| column);
+        let top = heights[column];
+        placements.insert(item.id, Placement { column, top });
+        heights[column] = top + height + gap;
+    }
+    let height = heights.iter().copied().fold(0.0, f64::max);
+    MasonryLayout {
+        placements: Some(placements),
+        height: (height - gap).max(0.0),
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MasonryContext {
+    columns: Memo<usize>,
+    gap: Signal<f64>,
+    items: Signal<Vec<MasonryEntry>>,
+    layout: Memo<MasonryLayout>,
+}
+
+/// The container of a masonry layout.
+///
+/// `columns` is the number of columns, replaced by the last of the
+/// `breakpoints` whose minimum width, in pixels, the container reaches.
+/// `gap` separates the columns and the items in a column, in pixels.
+#[component]
+pub fn Masonry(
+    #[props(default = 3)] columns: usize,
+    /// `(minimum width, columns)` pairs, from the narrowest.
+    #[props(default)]
+    breakpoints: Vec<(f64, usize)>,
+    #[props(default)] gap: f64,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut node = use_signal(|| None);
+    let size = use_size(node);
+    let responsive = use_synced_signal((columns, breakpoints));
+    let columns = use_memo(move || {
+        let (columns, breakpoints) = responsive();
+        let width = size().map(|size| size.width);
+        breakpoints
+            .iter()
+            .rev()
+            .find(|(min_width, _)| width.is_some_and(|width| width >= *min_width))
+            .map_or(columns, |(_, columns)| *columns)
+            .max(1)
+    });
+    let gap = use_synced_signal(gap);
+    let items = use_signal(Vec::<MasonryEntry>::new);
+    let layout = use_memo(move || {
+        let items = items.read().clone();
+        #[cfg(feature = "web")]
+        let items = {
+            let mut items = items;
+            items.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => super::dom::document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            items
+        };
+        balance(&items, columns(), gap())
+    });
+    use_context_provider(|| MasonryContext {
+        columns,
+        gap,
+        items,
+        layout,
+    });
+
+    let count = columns();
+    let gap = gap();
+    let measured = layout.read().placements.is_some();
+    let style = if measured {
+        format!("position: relative; height: {}px;", layout.read().height)
+    } else {
+        format!("column-count: {count}; column-gap: {gap}px;")
+    };
+    let own_attributes = vec![
+        Attribute::new("style", style, None, false),
+        Attribute::new("data-columns", count.to_string(), None, false),
+        Attribute::new("data-measured", measured.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An item of a [`Masonry`] layout, positioned in its column.
+#[component]
+pub fn MasonryItem(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MasonryContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let size = use_size(node);
+
+    use_effect(move || {
+        let entry = MasonryEntry {
+            id,
+            height: size().map(|size| size.height),
+            node: node(),
+        };
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|item| item.id == id) {
+            Some(item) => *item = entry,
+            None => items.push(entry),
+        }
+    });
+    use_drop(move || ctx.items.write().retain(|item| item.id != id));
+
+    let columns = (ctx.columns)();
+    let gap = (ctx.gap)();
+    let placement = ctx
+        .layout
+        .read()
+        .placements
+        .as_ref()
+        .and_then(|placements| placements.get(&id).copied());
+    // 欄寬扣掉欄與欄之間的間距
+    let width = format!(
+        "calc((100% - {}px) / {columns})",
+        gap * (columns - 1) as f64
+    );
+    let style = match placement {
+        Some(Placement { column, top }) => format!(
+            "position: absolute; top: {top}px; width: {width}; \
+             left: calc(({width} + {gap}px) * {column});"
+        ),
+        None => format!("break-inside: avoid; margin-bottom: {gap}px;"),
+    };
+
+    let own_attributes = vec![
+        Attribute::new("style", style, None, false),
+        Attribute::new(
+            "data-column",
+            placement.map(|placement| placement.column.to_string()),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,1132 @@
+//! Menu Primitive
+//!
+//! The machinery shared by dropdown menus, context menus and menubars:
+//! floating content with arrow-key navigation and typeahead over its
+//! items, checkbox and radio items, and nested submenus.
+//!
+//! A [`Menu`] has no trigger of its own. Wrap the element it opens from in
+//! a [`MenuAnchor`], and open it with `open`/`on_open_change` or
+//! [`use_menu`], so each widget can decide how it opens: on click, on right
+//! click at the pointer, or when moving along a menubar.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Menu { open: open(), on_open_change: move |next| open.set(next),
+//!         MenuAnchor {
+//!             as_child: move |anchor: Vec<Attribute>| rsx! {
+//!                 button { aria_haspopup: "menu", onclick: move |_| open.toggle(), ..anchor, "Edit" }
+//!             },
+//!         }
+//!         MenuContent {
+//!             MenuItem { on_select: move |_| undo(), "Undo" }
+//!             MenuCheckboxItem { checked: wrap(), on_checked_change: move |state| wrap.set(state),
+//!                 MenuItemIndicator { "✓" }
+//!                 "Word wrap"
+//!             }
+//!             MenuSeparator {}
+//!             MenuSub {
+//!                 MenuSubTrigger { "Find" }
+//!                 MenuSubContent {
+//!                     MenuItem { on_select: move |_| find(), "Find…" }
+//!                     MenuItem { on_select: move |_| replace(), "Replace…" }
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Items are focused as the pointer moves over them. Choosing an item closes
+//! the whole menu; ArrowRight opens a submenu and ArrowLeft or Escape closes
+//! it again, mirrored in right-to-left layouts.
+
+pub use super::popper::PopperArrow as MenuArrow;
+
+use super::checkbox::CheckedState;
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::focus_guards::use_focus_guards;
+use super::focus_scope::{AutoFocusEvent, FocusScope};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_typeahead::{Typeahead, use_typeahead};
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, PositionOptions, Side};
+use super::presence::Presence;
+use super::scroll_lock::use_scroll_lock;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Where focus goes when a menu's content opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuFocus {
+    /// The content itself, so the arrow keys start from either end.
+    #[default]
+    Content,
+    First,
+    Last,
+}
+
+/// Passed to `on_select` when an item is chosen.
+///
+/// Call [`prevent_default`](Self::prevent_default) to keep the menu open.
+#[derive(Debug, Clone, Default)]
+pub struct MenuSelectEvent {
+    prevented: Rc<Cell<bool>>,
+}
+
+impl MenuSelectEvent {
+    pub fn prevent_default(&self) {
+        self.prevented.set(true);
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevented.get()
+    }
+}
+
+fn state_str(open: bool) -> &'static str {
+    if open { "open" } else { "closed" }
+}
+
+// 整個選單（含子選單）共用
+#[derive(Clone, Copy)]
+struct MenuRootContext {
+    close: Callback<()>,
+    modal: Signal<bool>,
+    dir: Signal<Direction>,
+}
+
+// 每一層選單各自的開關狀態
+#[derive(Clone, Copy)]
+struct MenuContext {
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    initial_focus: Signal<MenuFocus>,
+    // 主選單為 MenuAnchor，子選單為 MenuSubTrigger
+    anchor: Signal<Option<Rc<MountedData>>>,
+    content_id: Signal<String>,
+}
+
+impl MenuContext {
+    fn focus_anchor(&self) {
+        let anchor = self.anchor.peek().clone();
+        if let Some(anchor) = anchor {
+            spawn(async move {
+                let _ = anchor.set_focus(true).await;
+            });
+        }
+    }
+}
+
+/// Opens and closes the nearest [`Menu`] or [`MenuSub`].
+///
+/// Returned by [`use_menu`].
+#[derive(Clone, Copy)]
+pub struct MenuHandle {
+    ctx: MenuContext,
+}
+
+impl MenuHandle {
+    pub fn is_open(&self) -> bool {
+        (self.ctx.open)()
+    }
+
+    pub fn set_open(&self, open: bool) {
+        self.ctx.set_open.call(open);
+    }
+
+    /// Open the menu and move focus to `focus` once its items are mounted,
+    /// e.g. the first item when a trigger is activated with ArrowDown.
+    pub fn open_with_focus(&mut self, focus: MenuFocus) {
+        self.ctx.initial_focus.set(focus);
+        self.ctx.set_open.call(true);
+    }
+}
+
+/// The [`MenuHandle`] of the enclosing [`Menu`] or [`MenuSub`], for
+/// triggers of your own.
+pub fn use_menu() -> MenuHandle {
+    MenuHandle {
+        ctx: use_context::<MenuContext>(),
+    }
+}
+
+#[derive(Clone)]
+struct MenuItemEntry {
+    id: usize,
+    node: Option<Rc<MountedData>>,
+    text_value: Option<String>,
+    disabled: bool,
+}
+
+impl MenuItemEntry {
+    fn label(&self) -> String {
+        if let Some(text_value) = &self.text_value {
+            return text_value.clone();
+        }
+        #[cfg(feature = "web")]
+        if let Some(text) = self.node.as_deref().and_then(super::dom::text_content) {
+            return text;
+        }
+        String::new()
+    }
+}
+
+// 每個選單內容的項目與鍵盤操作
+#[derive(Clone, Copy)]
+struct MenuContentContext {
+    items: Signal<Vec<MenuItemEntry>>,
+    highlighted: Signal<Option<usize>>,
+    node: Signal<Option<Rc<MountedData>>>,
+    typeahead: Typeahead,
+    loop_focus: Signal<bool>,
+}
+
+impl MenuContentContext {
+    // 可聚焦的項目，依文件順序排列
+    fn enabled_items(&self) -> Vec<MenuItemEntry> {
+        let items: Vec<MenuItemEntry> = self
+            .items
+            .peek()
+            .iter()
+            .filter(|item| !item.disabled && item.node.is_some())
+            .cloned()
+            .collect();
+        #[cfg(feature = "web")]
+        let items = {
+            let mut items = items;
+            items.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => super::dom::document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            items
+        };
+        items
+    }
+
+    fn focus(node: Option<Rc<MountedData>>) {
+        if let Some(node) = node {
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        }
+    }
+
+    fn focus_content(&self) {
+        Self::focus(self.node.peek().clone());
+    }
+
+    /// Moves focus for the navigation and typeahead keys. Returns whether
+    /// the key was used.
+    fn navigate(&mut self, event: &KeyboardEvent) -> bool {
+        let items = self.enabled_items();
+        let len = items.len();
+        let current = self
+            .highlighted
+            .peek()
+            .and_then(|id| items.iter().position(|item| item.id == id));
+        let loop_focus = *self.loop_focus.peek();
+
+        let target = match event.key() {
+            Key::ArrowDown => match current {
+                Some(index) if index + 1 < len => Some(index + 1),
+                Some(_) if !loop_focus => None,
+                _ => (len > 0).then_some(0),
+            },
+            Key::ArrowUp => match current {
+                Some(index) if index > 0 => Some(index - 1),
+                Some(_) if !loop_focus => None,
+                _ => len.checked_sub(1),
+            },
+            Key::Home | Key::PageUp => (len > 0).then_some(0),
+            Key::End | Key::PageDown => len.checked_sub(1),
+            Key::Character(character) => {
+                let modifiers = event.modifiers();
+                if modifiers.ctrl() || modifiers.alt() || modifiers.meta() {
+                    return false;
+                }
+                let labels: Vec<String> = items.iter().map(MenuItemEntry::label).collect();
+                let target = self.typeahead.type_key(&character, &labels, current);
+                if target.is_none() {
+                    return false;
+                }
+                target
+            }
+            _ => return false,
+        };
+
+        if let Some(item) = target.and_then(|index| items.get(index)) {
+            Self::focus(item.node.clone());
+        }
+        true
+    }
+}
+
+// 主選單與子選單的內容共用的項目註冊與鍵盤操作
+fn use_menu_content(loop_focus: bool) -> Vec<Attribute> {
+    let root = use_context::<MenuRootContext>();
+    let ctx = use_context::<MenuContext>();
+    let typeahead = use_typeahead();
+    let loop_focus = use_synced_signal(loop_focus);
+    let mut content = use_context_provider(|| MenuContentContext {
+        items: Signal::new(Vec::new()),
+        highlighted: Signal::new(None),
+        node: Signal::new(None),
+        typeahead,
+        loop_focus,
+    });
+
+    // 項目掛載後才能移動焦點
+    let mut initial_focus = ctx.initial_focus;
+    use_effect(move || {
+        let focus = initial_focus();
+        let _ = content.items.read();
+        let items = content.enabled_items();
+        let item = match focus {
+            MenuFocus::Content => return,
+            MenuFocus::First => items.first(),
+            MenuFocus::Last => items.last(),
+        };
+        if let Some(item) = item {
+            MenuContentContext::focus(item.node.clone());
+            initial_focus.set(MenuFocus::Content);
+        }
+    });
+
+    vec![
+        Attribute::new("role", "menu", None, false),
+        Attribute::new("id", ctx.content_id.read().clone(), None, false),
+        Attribute::new("aria-orientation", "vertical", None, false),
+        Attribute::new("dir", root.dir.read().as_str(), None, false),
+        Attribute::new("data-state", state_str((ctx.open)()), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            content.node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if content.navigate(&event) {
+                event.prevent_default();
+                // 子選單處理過的按鍵不再交給上層選單
+                event.stop_propagation();
+            } else if event.key() == Key::Tab {
+                event.prevent_default();
+            }
+        }),
+    ]
+}
+
+// 所有項目共用的註冊、聚焦與選取行為
+fn use_menu_item(
+    disabled: bool,
+    text_value: Option<String>,
+    select: Option<Callback<()>>,
+    // 項目開啟的子選單；開啟時滑鼠離開項目仍保留焦點，讓滑鼠能移入子選單
+    submenu_open: Option<Memo<bool>>,
+) -> (usize, Vec<Attribute>) {
+    let mut content = use_context::<MenuContentContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let entry = use_synced_signal((text_value, disabled));
+
+    use_effect(move || {
+        let (text_value, disabled) = entry();
+        let entry = MenuItemEntry {
+            id,
+            node: node(),
+            text_value,
+            disabled,
+        };
+        let mut items = content.items.write();
+        match items.iter_mut().find(|item| item.id == id) {
+            Some(item) => *item = entry,
+            None => items.push(entry),
+        }
+    });
+    use_drop(move || content.items.write().retain(|item| item.id != id));
+
+    let highlighted = *content.highlighted.read() == Some(id);
+    let attributes = vec![
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-highlighted", highlighted.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            content.highlighted.set(Some(id));
+        }),
+        dioxus_elements::events::onblur(move |_: FocusEvent| {
+            if *content.highlighted.peek() == Some(id) {
+                content.highlighted.set(None);
+            }
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            if event.pointer_type() != "mouse" {
+                return;
+            }
+            if disabled {
+                content.focus_content();
+            } else if *content.highlighted.peek() != Some(id) {
+                MenuContentContext::focus(node.peek().clone());
+            }
+        }),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            let keep = submenu_open.is_some_and(|open| *open.peek());
+            if event.pointer_type() == "mouse" && !keep && *content.highlighted.peek() == Some(id) {
+                content.focus_content();
+            }
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if let (false, Some(select)) = (disabled, select) {
+                select.call(());
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            // 輸入搜尋文字時，空白鍵屬於搜尋
+            let is_space = matches!(event.key(), Key::Character(character) if character == " ")
+                && content.typeahead.search().is_empty();
+            if event.key() != Key::Enter && !is_space {
+                return;
+            }
+            event.prevent_default();
+            if let (false, Some(select)) = (disabled, select) {
+                select.call(());
+            }
+        }),
+    ];
+    (id, attributes)
+}
+
+fn select_item(on_select: Option<EventHandler<MenuSelectEvent>>, root: MenuRootContext) {
+    let event = MenuSelectEvent::default();
+    if let Some(handler) = on_select {
+        handler.call(event.clone());
+    }
+    if !event.is_default_prevented() {
+        root.close.call(());
+    }
+}
+
+fn render_part(
+    as_child: Option<AsChild>,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The root of a menu, owning its open state.
+///
+/// Pass `open` and `on_open_change` to control the state, or
+/// `default_open` to let the menu manage it.
+#[component]
+pub fn Menu(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Block pointer events outside the content, trap focus in it, lock
+    /// scrolling and hide the rest of the page from assistive technology
+    /// while open.
+    #[props(default = true)]
+    modal: bool,
+    /// Defaults to the direction from [`use_direction`].
+    dir: Option<Direction>,
+    children: Element,
+) -> Element {
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let inherited_dir = use_direction();
+    let dir = use_synced_signal(dir.unwrap_or(inherited_dir));
+    let modal = use_synced_signal(modal);
+    let close = use_callback(move |()| set_open.call(false));
+    let content_id = use_id();
+    use_context_provider(|| MenuRootContext { close, modal, dir });
+    use_context_provider(|| MenuContext {
+        open,
+        set_open,
+        initial_focus: Signal::new(MenuFocus::Content),
+        anchor: Signal::new(None),
+        content_id: Signal::new(content_id),
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The element the content is positioned against, usually the trigger.
+///
+/// Pressing or focusing it does not dismiss the menu, so a trigger can
+/// toggle it. The trigger's own ARIA attributes are left to the widget,
+/// since a context menu's region is not a button.
+#[component]
+pub fn MenuAnchor(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MenuContext>();
+
+    let own_attributes = vec![
+        Attribute::new("data-state", state_str((ctx.open)()), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.anchor.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor { as_child, ..attributes, {children} }
+    }
+}
+
+/// The floating list of items, rendered while the menu is open and until
+/// its exit animation finishes.
+#[component]
+pub fn MenuContent(
+    #[props(default)] side: Side,
+    #[props(default)] side_offset: f64,
+    #[props(default = Align::Start)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Wrap around from the last item to the first and vice versa.
+    #[props(default)]
+    loop_focus: bool,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired when focus moves into the content on open. Prevent it to focus
+    /// another element yourself.
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired when focus returns to the previously focused element on close.
+    /// Prevent it to focus another element yourself.
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on Escape; prevent it to keep the menu open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the content and the anchor; prevent
+    /// it to keep the menu open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired when focus moves outside the content; prevent it to keep the
+    /// menu open.
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired for any interaction outside the content; prevent it to keep the
+    /// menu open.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<MenuContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                MenuContentImpl {
+                    position,
+                    loop_focus,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_open_auto_focus,
+                    on_close_auto_focus,
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    on_focus_outside,
+                    on_interact_outside,
+                    as_child,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+// 只在內容掛載時存在，讓焦點管理與圖層跟著 Presence 一起掛載與卸載
+#[component]
+fn MenuContentImpl(
+    position: PositionOptions,
+    loop_focus: bool,
+    element_attributes: Vec<Attribute>,
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    as_child: Option<AsChild>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let ctx = use_context::<MenuContext>();
+    let open = (ctx.open)();
+    let modal = open && (root.modal)();
+    use_scroll_lock(modal);
+    use_focus_guards();
+
+    let own_attributes = use_menu_content(loop_focus);
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            FocusScope {
+                trapped: modal,
+                on_mount_auto_focus: move |event: AutoFocusEvent| {
+                    if let Some(handler) = on_open_auto_focus {
+                        handler.call(event.clone());
+                    }
+                    // 要聚焦第一個或最後一個項目時，交給內容的 effect 處理
+                    if *ctx.initial_focus.peek() != MenuFocus::Content {
+                        event.prevent_default();
+                    }
+                },
+                on_unmount_auto_focus: on_close_auto_focus,
+                as_child: move |scope: Vec<Attribute>| {
+                    let element_attributes = merge_attributes(scope, attributes.clone());
+                    let children = children.clone();
+                    rsx! {
+                        DismissableLayer {
+                            disable_outside_pointer_events: modal,
+                            modal,
+                            inside_elements: vec![ctx.anchor],
+                            on_escape_key_down,
+                            on_pointer_down_outside,
+                            on_focus_outside: move |event: DismissEvent| {
+                                if let Some(handler) = on_focus_outside {
+                                    handler.call(event.clone());
+                                }
+                                // 焦點被困住時移到外部只會是暫時的
+                                if modal {
+                                    event.prevent_default();
+                                }
+                            },
+                            on_interact_outside,
+                            on_dismiss: move |_| root.close.call(()),
+                            as_child: move |layer: Vec<Attribute>| {
+                                let attributes = merge_attributes(layer, element_attributes.clone());
+                                render_part(as_child, attributes, children.clone())
+                            },
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// An item that runs an action when chosen.
+#[component]
+pub fn MenuItem(
+    /// Fired when the item is chosen with a click, Enter or Space. The menu
+    /// closes afterwards unless the event is prevented.
+    on_select: Option<EventHandler<MenuSelectEvent>>,
+    #[props(default)] disabled: bool,
+    /// The text used for typeahead. Defaults to the item's text content on
+    /// the web.
+    text_value: Option<String>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let select = use_callback(move |()| select_item(on_select, root));
+    let (_, item_attributes) = use_menu_item(disabled, text_value, Some(select), None);
+
+    let mut own_attributes = vec![Attribute::new("role", "menuitem", None, false)];
+    own_attributes.extend(item_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    render_part(as_child, attributes, children)
+}
+
+#[derive(Clone, Copy)]
+struct ItemIndicatorContext {
+    state: Signal<CheckedState>,
+}
+
+/// An item toggling a checked state, owning it unless controlled.
+///
+/// Pass `checked` and `on_checked_change` to control the state, or
+/// `default_checked` to let the item manage it.
+#[component]
+pub fn MenuCheckboxItem(
+    checked: Option<CheckedState>,
+    #[props(default)] default_checked: CheckedState,
+    on_checked_change: Option<EventHandler<CheckedState>>,
+    /// Fired after the state changes. The menu closes afterwards unless the
+    /// event is prevented.
+    on_select: Option<EventHandler<MenuSelectEvent>>,
+    #[props(default)] disabled: bool,
+    /// The text used for typeahead. Defaults to the item's text content on
+    /// the web.
+    text_value: Option<String>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let (checked, set_checked) =
+        use_controllable_state(checked, || default_checked, on_checked_change);
+    let select = use_callback(move |()| {
+        let next = checked.peek().toggled();
+        set_checked.call(next);
+        select_item(on_select, root);
+    });
+    let (_, item_attributes) = use_menu_item(disabled, text_value, Some(select), None);
+    let state = checked();
+    let state_signal = use_synced_signal(state);
+    use_context_provider(|| ItemIndicatorContext {
+        state: state_signal,
+    });
+
+    let mut own_attributes = vec![
+        Attribute::new("role", "menuitemcheckbox", None, false),
+        Attribute::new("aria-checked", state.aria_checked(), None, false),
+        Attribute::new("data-state", state.as_str(), None, false),
+    ];
+    own_attributes.extend(item_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    render_part(as_child, attributes, children)
+}
+
+#[derive(Clone, Copy)]
+struct RadioGroupContext {
+    value: Memo<String>,
+    set_value: Callback<String>,
+}
+
+/// A group of [`MenuRadioItem`]s of which one is checked, owning the
+/// checked value.
+///
+/// Pass `value` and `on_value_change` to control the value, or
+/// `default_value` to let the group manage it.
+#[component]
+pub fn MenuRadioGroup(
+    value: Option<String>,
+    #[props(default)] default_value: String,
+    on_value_change: Option<EventHandler<String>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    use_context_provider(|| RadioGroupContext { value, set_value });
+
+    rsx! {
+        div {
+            role: "group",
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An item of a [`MenuRadioGroup`], checking its value when chosen.
+#[component]
+pub fn MenuRadioItem(
+    value: String,
+    /// Fired after the value is checked. The menu closes afterwards unless
+    /// the event is prevented.
+    on_select: Option<EventHandler<MenuSelectEvent>>,
+    #[props(default)] disabled: bool,
+    /// The text used for typeahead. Defaults to the item's text content on
+    /// the web.
+    text_value: Option<String>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let group = use_context::<RadioGroupContext>();
+    let value_signal = use_synced_signal(value.clone());
+    let select = use_callback(move |()| {
+        group.set_value.call(value_signal.peek().clone());
+        select_item(on_select, root);
+    });
+    let (_, item_attributes) = use_menu_item(disabled, text_value, Some(select), None);
+    let state = CheckedState::from(*group.value.read() == value);
+    let state_signal = use_synced_signal(state);
+    use_context_provider(|| ItemIndicatorContext {
+        state: state_signal,
+    });
+
+    let mut own_attributes = vec![
+        Attribute::new("role", "menuitemradio", None, false),
+        Attribute::new("aria-checked", state.aria_checked(), None, false),
+        Attribute::new("data-state", state.as_str(), None, false),
+    ];
+    own_attributes.extend(item_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    render_part(as_child, attributes, children)
+}
+
+/// Rendered while its [`MenuCheckboxItem`] or [`MenuRadioItem`] is
+/// checked, e.g. a check mark.
+#[component]
+pub fn MenuItemIndicator(
+    /// Keep the indicator mounted while unchecked, for animation libraries;
+    /// see [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ItemIndicatorContext>();
+    let state = (ctx.state)();
+
+    let own_attributes = vec![Attribute::new("data-state", state.as_str(), None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: state != CheckedState::Unchecked,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| {
+                let attributes = merge_attributes(presence, attributes.clone());
+                if let Some(as_child) = as_child {
+                    return as_child.call(attributes);
+                }
+                rsx! {
+                    span {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Groups related items.
+#[component]
+pub fn MenuGroup(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        div {
+            role: "group",
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A heading for a group of items. It cannot be focused.
+#[component]
+pub fn MenuLabel(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A line between groups of items.
+#[component]
+pub fn MenuSeparator(#[props(extends = GlobalAttributes)] attributes: Vec<Attribute>) -> Element {
+    rsx! {
+        div {
+            role: "separator",
+            aria_orientation: "horizontal",
+            ..attributes
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SubContext {
+    trigger_id: Signal<Option<usize>>,
+}
+
+/// A nested menu, opened from its [`MenuSubTrigger`], owning its open
+/// state.
+///
+/// Pass `open` and `on_open_change` to control the state, or
+/// `default_open` to let the submenu manage it.
+#[component]
+pub fn MenuSub(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    children: Element,
+) -> Element {
+    let parent_content = use_context::<MenuContentContext>();
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let trigger_id = use_signal(|| None::<usize>);
+    let content_id = use_id();
+    use_context_provider(|| MenuContext {
+        open,
+        set_open,
+        initial_focus: Signal::new(MenuFocus::Content),
+        anchor: Signal::new(None),
+        content_id: Signal::new(content_id),
+    });
+    use_context_provider(|| SubContext { trigger_id });
+
+    // 上層的其他項目被聚焦時關閉
+    use_effect(move || {
+        let highlighted = (parent_content.highlighted)();
+        if highlighted.is_some() && highlighted != *trigger_id.peek() && *open.peek() {
+            set_open.call(false);
+        }
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The item opening its [`MenuSub`], on hover, click, Enter, Space or
+/// ArrowRight.
+#[component]
+pub fn MenuSubTrigger(
+    #[props(default)] disabled: bool,
+    /// The text used for typeahead. Defaults to the item's text content on
+    /// the web.
+    text_value: Option<String>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let mut ctx = use_context::<MenuContext>();
+    let sub = use_context::<SubContext>();
+    let content = use_context::<MenuContentContext>();
+    let (id, item_attributes) = use_menu_item(disabled, text_value, None, Some(ctx.open));
+    let mut trigger_id = sub.trigger_id;
+    use_effect(move || trigger_id.set(Some(id)));
+
+    let open = (ctx.open)();
+    let open_key = match (root.dir)() {
+        Direction::Ltr => Key::ArrowRight,
+        Direction::Rtl => Key::ArrowLeft,
+    };
+    let mut own_attributes = vec![
+        Attribute::new("role", "menuitem", None, false),
+        Attribute::new("aria-haspopup", "menu", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new(
+            "aria-controls",
+            open.then(|| ctx.content_id.read().clone()),
+            None,
+            false,
+        ),
+        Attribute::new("data-state", state_str(open), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.anchor.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onpointermove(move |event: PointerEvent| {
+            if event.pointer_type() == "mouse" && !disabled && !*ctx.open.peek() {
+                ctx.set_open.call(true);
+            }
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !disabled {
+                ctx.set_open.call(true);
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let is_space = matches!(event.key(), Key::Character(character) if character == " ")
+                && content.typeahead.search().is_empty();
+            if disabled || !(event.key() == open_key || event.key() == Key::Enter || is_space) {
+                return;
+            }
+            event.prevent_default();
+            event.stop_propagation();
+            ctx.initial_focus.set(MenuFocus::First);
+            ctx.set_open.call(true);
+        }),
+    ];
+    own_attributes.extend(item_attributes);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| {
+                let attributes = merge_attributes(anchor, attributes.clone());
+                render_part(as_child, attributes, children.clone())
+            },
+        }
+    }
+}
+
+/// The floating list of a [`MenuSub`], placed beside its trigger.
+#[component]
+pub fn MenuSubContent(
+    #[props(default)] side_offset: f64,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Wrap around from the last item to the first and vice versa.
+    #[props(default)]
+    loop_focus: bool,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired on Escape; prevent it to keep the submenu open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the submenu and its trigger; prevent
+    /// it to keep the whole menu open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired when focus moves outside the submenu and its trigger; prevent
+    /// it to keep the submenu open.
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired for any interaction outside the submenu; prevent it to keep it
+    /// open.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let ctx = use_context::<MenuContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side: match (root.dir)() {
+            Direction::Ltr => Side::Right,
+            Direction::Rtl => Side::Left,
+        },
+        side_offset,
+        align: Align::Start,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                MenuSubContentImpl {
+                    position,
+                    loop_focus,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    on_focus_outside,
+                    on_interact_outside,
+                    as_child,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn MenuSubContentImpl(
+    position: PositionOptions,
+    loop_focus: bool,
+    element_attributes: Vec<Attribute>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    as_child: Option<AsChild>,
+    children: Element,
+) -> Element {
+    let root = use_context::<MenuRootContext>();
+    let ctx = use_context::<MenuContext>();
+    let close_key = match (root.dir)() {
+        Direction::Ltr => Key::ArrowLeft,
+        Direction::Rtl => Key::ArrowRight,
+    };
+
+    let mut own_attributes = use_menu_content(loop_focus);
+    own_attributes.push(dioxus_elements::events::onkeydown(
+        move |event: KeyboardEvent| {
+            if event.key() == close_key {
+                event.prevent_default();
+                event.stop_propagation();
+                ctx.set_open.call(false);
+                ctx.focus_anchor();
+            }
+        },
+    ));
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            DismissableLayer {
+                inside_elements: vec![ctx.anchor],
+                on_escape_key_down: move |event: DismissEvent| {
+                    if let Some(handler) = on_escape_key_down {
+                        handler.call(event.clone());
+                    }
+                    if !event.is_default_prevented() {
+                        ctx.focus_anchor();
+                    }
+                },
+                on_pointer_down_outside: move |event: DismissEvent| {
+                    if let Some(handler) = on_pointer_down_outside {
+                        handler.call(event.clone());
+                    }
+                    // 子選單在最上層時，上層選單收不到外部的點擊，由這裡關閉整個選單
+                    if !event.is_default_prevented() {
+                        root.close.call(());
+                    }
+                },
+                on_focus_outside,
+                on_interact_outside,
+                on_dismiss: move |_| ctx.set_open.call(false),
+                as_child: move |layer: Vec<Attribute>| {
+                    let attributes = merge_attributes(layer, attributes.clone());
+                    render_part(as_child, attributes, children.clone())
+                },
+            }
+        }
+    }
+}
@@ -2,4 +2,58 @@
 //!
 //! This module contains primitive components and utilities for Dioxus applications.
 
+pub mod accessible_icon;
+pub mod alert_dialog;
+pub mod announcer;
+pub mod aspect_ratio;
+pub mod avatar;
+pub mod checkbox;
+pub mod collapsible;
+pub mod color_picker;
+pub mod combobox;
+pub mod command;
 pub mod context;
+pub mod data_table;
+pub mod date_range_picker;
+pub mod direction;
+pub mod dialog;
+pub mod dismissable_layer;
+pub mod file_upload;
+pub mod focus_guards;
+pub mod focus_scope;
+pub mod form;
+pub mod hooks;
+pub mod hotkeys;
+pub mod infinite_scroll;
+pub mod layer_stack;
+pub mod listbox;
+pub mod marquee;
+pub mod masonry;
+pub mod menu;
+pub mod multi_select;
+pub mod navigation_menu;
+pub mod number_input;
+pub mod pin_input;
+pub mod popover;
+pub mod popper;
+pub mod portal;
+pub mod presence;
+pub mod resizable;
+pub mod root;
+pub mod roving_focus;
+pub mod scroll_lock;
+pub mod segmented_control;
+pub mod slot;
+pub mod tabs;
+pub mod time_field;
+pub mod timeline;
+pub mod toast;
+pub mod toggle;
+pub mod tooltip;
+pub mod transfer_list;
+pub mod virtual_list;
+pub mod visually_hidden;
+
+mod dom;
+mod segment;
+mod time;
@@ -0,0 +1,715 @@
+//! MultiSelect Primitive
+//!
+//! A button showing the selected values as removable tags, opening a
+//! searchable [`Listbox`] in multiple-selection mode positioned against it.
+//! The value is a `Vec<T>` of any type displaying as text.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     MultiSelect { value: toppings(), on_value_change: move |next| toppings.set(next),
+//!         MultiSelectTrigger {
+//!             MultiSelectTags { MultiSelectTagRemove { "×" } }
+//!         }
+//!         MultiSelectContent {
+//!             MultiSelectSearch { placeholder: "Search toppings" }
+//!             MultiSelectSelectAll { "Select all" }
+//!             MultiSelectList {
+//!                 for topping in Topping::ALL {
+//!                     MultiSelectOption { value: topping,
+//!                         MultiSelectOptionIndicator {}
+//!                         "{topping}"
+//!                     }
+//!                 }
+//!             }
+//!             MultiSelectEmpty { "No toppings found" }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Backspace on the trigger removes the last tag, and ArrowDown in the
+//! search moves focus to the list.
+
+use super::checkbox::{Checkbox, CheckedState};
+use super::context::create_context::use_synced_signal;
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::listbox::{Listbox, ListboxItem, SelectionMode};
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, PositionOptions, Side};
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct OptionEntry {
+    key: String,
+    label: String,
+    disabled: bool,
+}
+
+#[derive(Clone, Copy)]
+struct MultiSelectContext {
+    // 已選取且已註冊的選項，供 Listbox 使用
+    selected_keys: Memo<Vec<String>>,
+    set_keys: Callback<Vec<String>>,
+    tags: Memo<Vec<String>>,
+    remove: Callback<usize>,
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    search: Signal<String>,
+    disabled: Signal<bool>,
+    options: Signal<Vec<OptionEntry>>,
+    trigger: Signal<Option<Rc<MountedData>>>,
+    search_input: Signal<Option<Rc<MountedData>>>,
+    list: Signal<Option<Rc<MountedData>>>,
+    content_id: Signal<String>,
+}
+
+impl MultiSelectContext {
+    fn matches(&self, label: &str) -> bool {
+        label
+            .to_lowercase()
+            .contains(&self.search.read().trim().to_lowercase())
+    }
+
+    /// The keys of the enabled options matching the search.
+    fn visible_keys(&self) -> Vec<String> {
+        self.options
+            .read()
+            .iter()
+            .filter(|option| !option.disabled && self.matches(&option.label))
+            .map(|option| option.key.clone())
+            .collect()
+    }
+
+    fn close(&mut self) {
+        self.set_open.call(false);
+        let trigger = self.trigger.peek().clone();
+        if let Some(trigger) = trigger {
+            spawn(async move {
+                let _ = trigger.set_focus(true).await;
+            });
+        }
+    }
+}
+
+// 泛型的部分只有選項與值之間的對應
+struct ValueContext<T: 'static> {
+    registry: Signal<Vec<(String, T)>>,
+}
+
+impl<T: 'static> Clone for ValueContext<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for ValueContext<T> {}
+
+/// The root of a multi-select, owning the selected values and whether the
+/// list is open. Each can be controlled with its prop and change handler,
+/// or left to the multi-select.
+///
+/// Values keep the order they were selected in, and the tags show each
+/// value's [`Display`] text.
+#[component]
+pub fn MultiSelect<T: Clone + PartialEq + Display + 'static>(
+    value: Option<Vec<T>>,
+    #[props(default)] default_value: Vec<T>,
+    on_value_change: Option<EventHandler<Vec<T>>>,
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    #[props(default)] disabled: bool,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let registry = use_signal(Vec::<(String, T)>::new);
+    use_context_provider(|| ValueContext { registry });
+
+    let selected_keys = use_memo(move || {
+        let registry = registry.read();
+        value
+            .read()
+            .iter()
+            .filter_map(|value| {
+                registry
+                    .iter()
+                    .find(|(_, option)| option == value)
+                    .map(|(key, _)| key.clone())
+            })
+            .collect::<Vec<_>>()
+    });
+    // 保留未註冊的值與原本的順序，新選取的值加在最後
+    let set_keys = use_callback(move |keys: Vec<String>| {
+        let registry = registry.peek();
+        let key_of = |value: &T| {
+            registry
+                .iter()
+                .find(|(_, option)| option == value)
+                .map(|(key, _)| key.clone())
+        };
+        let mut next: Vec<T> = value
+            .peek()
+            .iter()
+            .filter(|value| key_of(value).is_none_or(|key| keys.contains(&key)))
+            .cloned()
+            .collect();
+        for key in &keys {
+            if let Some((_, option)) = registry.iter().find(|(other, _)| other == key) {
+                if !next.contains(option) {
+                    next.push(option.clone());
+                }
+            }
+        }
+        drop(registry);
+        set_value.call(next);
+    });
+    let tags = use_memo(move || value.read().iter().map(|value| value.to_string()).collect());
+    let remove = use_callback(move |index: usize| {
+        let mut next = value.peek().clone();
+        if index < next.len() {
+            next.remove(index);
+            set_value.call(next);
+        }
+    });
+    let disabled = use_synced_signal(disabled);
+    let content_id = use_id();
+    use_context_provider(|| MultiSelectContext {
+        selected_keys,
+        set_keys,
+        tags,
+        remove,
+        open,
+        set_open,
+        search: Signal::new(String::new()),
+        disabled,
+        options: Signal::new(Vec::new()),
+        trigger: Signal::new(None),
+        search_input: Signal::new(None),
+        list: Signal::new(None),
+        content_id: Signal::new(content_id),
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The control showing the selected values, toggling the list when
+/// pressed. The list is positioned against it.
+#[component]
+pub fn MultiSelectTrigger(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+    let open = (ctx.open)();
+    let disabled = (ctx.disabled)();
+
+    let own_attributes = vec![
+        Attribute::new("role", "combobox", None, false),
+        Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+        Attribute::new("aria-haspopup", "listbox", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", (ctx.content_id)(), None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new(
+            "data-placeholder",
+            ctx.tags.read().is_empty().then_some(""),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.trigger.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !*ctx.disabled.peek() {
+                let open = *ctx.open.peek();
+                ctx.set_open.call(!open);
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if *ctx.disabled.peek() {
+                return;
+            }
+            let is_space = matches!(event.key(), Key::Character(character) if character == " ");
+            match event.key() {
+                Key::Enter | Key::ArrowDown => {}
+                Key::Backspace => {
+                    event.prevent_default();
+                    let last = ctx.tags.peek().len().checked_sub(1);
+                    if let Some(last) = last {
+                        ctx.remove.call(last);
+                    }
+                    return;
+                }
+                _ if is_space => {}
+                _ => return,
+            }
+            event.prevent_default();
+            ctx.set_open.call(true);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| {
+                let attributes = merge_attributes(anchor, attributes.clone());
+                rsx! {
+                    div {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TagContext {
+    index: Signal<usize>,
+    label: Signal<String>,
+}
+
+/// One `span` per selected value, with its text followed by `children`,
+/// e.g. a [`MultiSelectTagRemove`].
+#[component]
+pub fn MultiSelectTags(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<MultiSelectContext>();
+    let tags = (ctx.tags)();
+
+    rsx! {
+        for (index, label) in tags.into_iter().enumerate() {
+            MultiSelectTag {
+                key: "{index}-{label}",
+                index,
+                label,
+                attributes: attributes.clone(),
+                {children.clone()}
+            }
+        }
+    }
+}
+
+#[component]
+fn MultiSelectTag(
+    index: usize,
+    label: String,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let index = use_synced_signal(index);
+    let label_signal = use_synced_signal(label.clone());
+    use_context_provider(|| TagContext {
+        index,
+        label: label_signal,
+    });
+
+    rsx! {
+        span {
+            "data-tag": "",
+            ..attributes,
+            {label}
+            {children}
+        }
+    }
+}
+
+/// A button removing its tag's value. It cannot be focused, since
+/// Backspace on the trigger removes tags from the keyboard.
+#[component]
+pub fn MultiSelectTagRemove(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<MultiSelectContext>();
+    let tag = use_context::<TagContext>();
+    let disabled = (ctx.disabled)();
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new(
+            "aria-label",
+            format!("Remove {}", (tag.label)()),
+            None,
+            false,
+        ),
+        Attribute::new("disabled", disabled, None, false),
+        dioxus_elements::events::onclick(move |event: MouseEvent| {
+            // 不讓點擊同時開關清單
+            event.stop_propagation();
+            ctx.remove.call(*tag.index.peek());
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The floating content with the search and the list, rendered while open
+/// and until its exit animation finishes.
+#[component]
+pub fn MultiSelectContent(
+    #[props(default = Side::Bottom)] side: Side,
+    #[props(default = 4.0)] side_offset: f64,
+    #[props(default = Align::Start)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired on Escape; prevent it to keep the list open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the trigger and the content;
+    /// prevent it to keep the list open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    // 關閉時清除搜尋文字
+    use_effect(move || {
+        if !(ctx.open)() && !ctx.search.peek().is_empty() {
+            ctx.search.set(String::new());
+        }
+    });
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                MultiSelectContentImpl {
+                    position,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn MultiSelectContentImpl(
+    position: PositionOptions,
+    element_attributes: Vec<Attribute>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+
+    let own_attributes = vec![Attribute::new(
+        "id",
+        ctx.content_id.read().clone(),
+        None,
+        false,
+    )];
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            DismissableLayer {
+                inside_elements: vec![ctx.trigger],
+                on_escape_key_down,
+                on_pointer_down_outside,
+                on_dismiss: move |_| ctx.close(),
+                as_child: move |layer: Vec<Attribute>| {
+                    let attributes = merge_attributes(layer, attributes.clone());
+                    rsx! {
+                        div {
+                            ..attributes,
+                            {children.clone()}
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// A text input filtering the options by their text. It takes focus when
+/// the content opens.
+#[component]
+pub fn MultiSelectSearch(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+
+    let own_attributes = vec![
+        Attribute::new("type", "text", None, false),
+        Attribute::new("autocomplete", "off", None, false),
+        Attribute::new("aria-controls", (ctx.content_id)(), None, false),
+        Attribute::new("value", (ctx.search)(), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            let data = event.data();
+            ctx.search_input.set(Some(data.clone()));
+            spawn(async move {
+                let _ = data.set_focus(true).await;
+            });
+        }),
+        dioxus_elements::events::oninput(move |event: FormEvent| ctx.search.set(event.value())),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if event.key() != Key::ArrowDown {
+                return;
+            }
+            event.prevent_default();
+            let list = ctx.list.peek().clone();
+            if let Some(list) = list {
+                spawn(async move {
+                    let _ = list.set_focus(true).await;
+                });
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+/// The options, a multiple-selection [`Listbox`]. It takes focus when the
+/// content opens without a [`MultiSelectSearch`].
+#[component]
+pub fn MultiSelectList(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+
+    let own_attributes = vec![dioxus_elements::events::onmounted(
+        move |event: MountedEvent| {
+            let data = event.data();
+            ctx.list.set(Some(data.clone()));
+            if ctx.search_input.peek().is_none() {
+                spawn(async move {
+                    let _ = data.set_focus(true).await;
+                });
+            }
+        },
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Listbox {
+            value: (ctx.selected_keys)(),
+            on_value_change: move |keys| ctx.set_keys.call(keys),
+            selection_mode: SelectionMode::Multiple,
+            disabled: (ctx.disabled)(),
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OptionContext {
+    selected: Memo<bool>,
+}
+
+/// An option of the list. Hidden while its text does not match the search.
+#[component]
+pub fn MultiSelectOption<T: Clone + PartialEq + Display + 'static>(
+    value: T,
+    /// The text matched against the search and used for typeahead.
+    /// Defaults to the value's [`Display`] text.
+    label: Option<String>,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<MultiSelectContext>();
+    let values = use_context::<ValueContext<T>>();
+    let key = use_id();
+    let label = label.unwrap_or_else(|| value.to_string());
+    let entry = use_synced_signal((value, label.clone(), disabled));
+
+    {
+        let key = key.clone();
+        use_effect(move || {
+            let (value, label, disabled) = entry();
+            let mut registry = values.registry;
+            let mut entries = registry.write();
+            match entries.iter_mut().find(|(other, _)| *other == key) {
+                Some(existing) => existing.1 = value,
+                None => entries.push((key.clone(), value)),
+            }
+            drop(entries);
+            let option = OptionEntry {
+                key: key.clone(),
+                label,
+                disabled,
+            };
+            let mut options = ctx.options.write();
+            match options.iter_mut().find(|other| other.key == key) {
+                Some(existing) => *existing = option,
+                None => options.push(option),
+            }
+        });
+    }
+    {
+        let key = key.clone();
+        use_drop(move || {
+            let mut registry = values.registry;
+            registry.write().retain(|(other, _)| *other != key);
+            ctx.options.write().retain(|option| option.key != key);
+        });
+    }
+    let selected = {
+        let key = key.clone();
+        use_memo(move || (ctx.selected_keys)().contains(&key))
+    };
+    use_context_provider(|| OptionContext { selected });
+
+    if !ctx.matches(&label) {
+        return rsx! {};
+    }
+
+    rsx! {
+        ListboxItem {
+            value: key,
+            text_value: label,
+            disabled,
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A checkbox drawn inside an option, reflecting whether it is selected.
+/// It is only visual: the option itself is announced as selected.
+#[component]
+pub fn MultiSelectOptionIndicator(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let option = use_context::<OptionContext>();
+    let state = CheckedState::from((option.selected)());
+
+    rsx! {
+        span {
+            aria_hidden: "true",
+            "data-state": state.as_str(),
+            ..attributes,
+            if (option.selected)() {
+                {children}
+            }
+        }
+    }
+}
+
+/// A [`Checkbox`] selecting every option matching the search, or clearing
+/// them once they are all selected. It is indeterminate while only some
+/// are.
+#[component]
+pub fn MultiSelectSelectAll(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<MultiSelectContext>();
+    let keys = ctx.visible_keys();
+    let selected = (ctx.selected_keys)();
+    let count = keys.iter().filter(|key| selected.contains(key)).count();
+    let state = match count {
+        0 => CheckedState::Unchecked,
+        count if count == keys.len() => CheckedState::Checked,
+        _ => CheckedState::Indeterminate,
+    };
+
+    rsx! {
+        Checkbox {
+            checked: state,
+            on_checked_change: move |_| {
+                let keys = ctx.visible_keys();
+                let mut next = ctx.selected_keys.peek().clone();
+                if state == CheckedState::Checked {
+                    next.retain(|key| !keys.contains(key));
+                } else {
+                    next.extend(keys.into_iter().filter(|key| !selected.contains(key)));
+                }
+                ctx.set_keys.call(next);
+            },
+            disabled: (ctx.disabled)() || keys.is_empty(),
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// Rendered while no option matches the search.
+#[component]
+pub fn MultiSelectEmpty(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<MultiSelectContext>();
+    let any_visible = ctx
+        .options
+        .read()
+        .iter()
+        .any(|option| ctx.matches(&option.label));
+    if any_visible {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            role: "presentation",
+            ..attributes,
+            {children}
+        }
+    }
+}
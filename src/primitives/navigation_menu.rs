@@ -0,0 +1,720 @@
+//! NavigationMenu Primitive
+//!
+//! Site navigation: a list of links and of triggers that open panels of
+//! further links. Panels open when a trigger is hovered for a moment or
+//! clicked; once one is open, hovering another trigger switches panels
+//! right away. The arrow keys move between the items of the list.
+//!
+//! When a [`NavigationMenuViewport`] is rendered, the open panel is moved
+//! into it, so all panels share one animated container. The viewport
+//! exposes the size of the active panel as
+//! `--primitive-navigation-menu-viewport-width` and
+//! `--primitive-navigation-menu-viewport-height`, and each panel has a
+//! `data-motion` of `from-start`, `from-end`, `to-start` or `to-end`
+//! describing from which side it enters or towards which side it leaves.
+
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_rect::use_rect;
+use super::hooks::use_size::use_size;
+use super::hooks::use_timer::{Timer, use_timeout};
+use super::portal::{Portal, PortalTarget};
+use super::presence::Presence;
+use super::roving_focus::Orientation;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+// 指標離開觸發按鈕或面板後，等待這段時間再關閉，讓指標能在兩者之間移動
+const CLOSE_DELAY: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Copy)]
+struct NavigationMenuContext {
+    value: Memo<String>,
+    set_value: Callback<String>,
+    // 上一個開啟的項目，用來決定 data-motion 的方向
+    previous_value: Signal<String>,
+    orientation: Signal<Orientation>,
+    dir: Signal<Direction>,
+    // 已註冊的項目值，依掛載順序排列
+    items: Signal<Vec<String>>,
+    pending: Signal<Option<String>>,
+    open_timer: Timer,
+    close_timer: Timer,
+    open_delayed: Signal<bool>,
+    viewport: Signal<Option<String>>,
+    viewport_size: Signal<Option<(f64, f64)>>,
+    active_trigger: Signal<Option<Rc<MountedData>>>,
+    list: Signal<Option<Rc<MountedData>>>,
+    // 清單中可用方向鍵切換的觸發按鈕與連結
+    focusables: Signal<Vec<(usize, Rc<MountedData>)>>,
+    // 以鍵盤開啟時，面板掛載後把焦點移進去
+    focus_on_open: Signal<Option<String>>,
+}
+
+// 切換開啟的項目，並記下上一個項目
+fn select(
+    value: Memo<String>,
+    set_value: Callback<String>,
+    mut previous_value: Signal<String>,
+    next: String,
+) {
+    let current = value.peek().clone();
+    if current == next {
+        return;
+    }
+    previous_value.set(current);
+    set_value.call(next);
+}
+
+impl NavigationMenuContext {
+    fn select(&mut self, value: String) {
+        select(self.value, self.set_value, self.previous_value, value);
+    }
+
+    fn on_trigger_enter(&mut self, value: String) {
+        self.close_timer.reset();
+        if !self.value.peek().is_empty() || !*self.open_delayed.peek() {
+            self.open_timer.reset();
+            self.select(value);
+        } else {
+            self.pending.set(Some(value));
+            self.open_timer.start();
+        }
+    }
+
+    fn on_trigger_leave(&mut self) {
+        self.open_timer.reset();
+        self.pending.set(None);
+        self.close_timer.start();
+    }
+
+    fn index_of(&self, value: &str) -> Option<usize> {
+        self.items.peek().iter().position(|item| item == value)
+    }
+}
+
+/// The root of a navigation menu. Its value is the value of the item whose
+/// panel is open, or empty while all are closed.
+#[component]
+pub fn NavigationMenu(
+    value: Option<String>,
+    #[props(default)] default_value: String,
+    on_value_change: Option<EventHandler<String>>,
+    /// How long a trigger must be hovered before its panel opens.
+    #[props(default = Duration::from_millis(200))]
+    delay_duration: Duration,
+    /// How long after closing hovering a trigger still opens its panel
+    /// without delay.
+    #[props(default = Duration::from_millis(300))]
+    skip_delay_duration: Duration,
+    #[props(default)] orientation: Orientation,
+    /// Defaults to the direction from [`use_direction`].
+    dir: Option<Direction>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let inherited_dir = use_direction();
+    let dir = dir.unwrap_or(inherited_dir);
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let orientation_signal = use_synced_signal(orientation);
+    let dir_signal = use_synced_signal(dir);
+    let mut previous_value = use_signal(String::new);
+    let mut pending = use_signal(|| None::<String>);
+    let mut open_delayed = use_signal(|| true);
+
+    let open_timer = use_timeout(delay_duration, move || {
+        if let Some(next) = pending.take() {
+            select(value, set_value, previous_value, next);
+        }
+    });
+    let close_timer = use_timeout(CLOSE_DELAY, move || {
+        select(value, set_value, previous_value, String::new())
+    });
+    let mut skip_delay_timer = use_timeout(skip_delay_duration, move || open_delayed.set(true));
+
+    use_context_provider(|| NavigationMenuContext {
+        value,
+        set_value,
+        previous_value,
+        orientation: orientation_signal,
+        dir: dir_signal,
+        items: Signal::new(Vec::new()),
+        pending,
+        open_timer,
+        close_timer,
+        open_delayed,
+        viewport: Signal::new(None),
+        viewport_size: Signal::new(None),
+        active_trigger: Signal::new(None),
+        list: Signal::new(None),
+        focusables: Signal::new(Vec::new()),
+        focus_on_open: Signal::new(None),
+    });
+
+    // 全部關閉後的一段時間內，再次懸停可立即開啟
+    // 全部關閉時也清除上一個項目，重新開啟時不套用 data-motion
+    use_effect(move || {
+        if value().is_empty() {
+            skip_delay_timer.start();
+            previous_value.set(String::new());
+        } else {
+            skip_delay_timer.reset();
+            open_delayed.set(false);
+        }
+    });
+
+    rsx! {
+        nav {
+            dir: dir.as_str(),
+            "data-orientation": orientation.as_str(),
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The list of top-level items.
+#[component]
+pub fn NavigationMenuList(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<NavigationMenuContext>();
+    let mut list = ctx.list;
+    let orientation = (ctx.orientation)();
+
+    let own_attributes = vec![
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        // 讓 NavigationMenuIndicator 以清單為定位基準
+        Attribute::new("position", "relative", Some("style"), false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| list.set(Some(event.data()))),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let orientation = *ctx.orientation.peek();
+            let rtl = *ctx.dir.peek() == Direction::Rtl;
+            let step = match (event.key(), orientation) {
+                (Key::ArrowRight, Orientation::Horizontal) => Some(if rtl { -1 } else { 1 }),
+                (Key::ArrowLeft, Orientation::Horizontal) => Some(if rtl { 1 } else { -1 }),
+                (Key::ArrowDown, Orientation::Vertical) => Some(1),
+                (Key::ArrowUp, Orientation::Vertical) => Some(-1),
+                (Key::Home, _) => Some(isize::MIN),
+                (Key::End, _) => Some(isize::MAX),
+                _ => None,
+            };
+            let Some(step) = step else {
+                return;
+            };
+
+            let focusables = ctx.focusables.peek().clone();
+            let current = focused_index(&focusables);
+            let last = focusables.len().saturating_sub(1) as isize;
+            let target = match (step, current) {
+                (isize::MIN, _) => 0,
+                (isize::MAX, _) => last,
+                (step, Some(current)) => current as isize + step,
+                // 焦點在面板中時不處理
+                (_, None) => return,
+            };
+            if !(0..=last).contains(&target) {
+                return;
+            }
+            event.prevent_default();
+            let node = focusables[target as usize].1.clone();
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        ul {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+fn focused_index(focusables: &[(usize, Rc<MountedData>)]) -> Option<usize> {
+    let active = super::dom::active_element()?;
+    focusables.iter().position(|(_, node)| {
+        super::dom::web_element(node).is_some_and(|element| element == active)
+    })
+}
+
+// 沒有 web 功能時無法得知焦點所在，方向鍵不移動焦點
+#[cfg(not(feature = "web"))]
+fn focused_index(_focusables: &[(usize, Rc<MountedData>)]) -> Option<usize> {
+    None
+}
+
+static NEXT_FOCUSABLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// 讓觸發按鈕與清單層級的連結可以用方向鍵切換
+fn use_list_focusable() -> impl FnMut(Rc<MountedData>) + Copy {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let in_content = try_use_context::<ContentMarker>().is_some();
+    let id = use_hook(|| NEXT_FOCUSABLE_ID.fetch_add(1, Ordering::Relaxed));
+
+    use_drop(move || ctx.focusables.write().retain(|(item, _)| *item != id));
+
+    move |node: Rc<MountedData>| {
+        if !in_content {
+            ctx.focusables.write().push((id, node));
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ItemContext {
+    value: String,
+    trigger_id: String,
+    content_id: String,
+    trigger: Signal<Option<Rc<MountedData>>>,
+}
+
+/// A top-level item, holding a link or a trigger with its content.
+#[component]
+pub fn NavigationMenuItem(
+    /// Identifies the item in the menu's value. Generated when not given.
+    value: Option<String>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let generated = use_id();
+    let value = use_hook(|| value.unwrap_or(generated));
+    let trigger_id = use_id();
+    let content_id = use_id();
+
+    use_effect({
+        let value = value.clone();
+        move || ctx.items.write().push(value.clone())
+    });
+    use_drop({
+        let value = value.clone();
+        move || ctx.items.write().retain(|item| *item != value)
+    });
+
+    use_context_provider(|| ItemContext {
+        value,
+        trigger_id,
+        content_id,
+        trigger: Signal::new(None),
+    });
+
+    rsx! {
+        li {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The button that opens the item's [`NavigationMenuContent`].
+#[component]
+pub fn NavigationMenuTrigger(
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let item = use_context::<ItemContext>();
+    let mut register = use_list_focusable();
+    let mut trigger = item.trigger;
+    let open = *ctx.value.read() == item.value;
+
+    use_effect(use_reactive!(|(open,)| {
+        if open {
+            ctx.active_trigger.set(trigger.peek().clone());
+        }
+    }));
+
+    let enter_value = item.value.clone();
+    let click_value = item.value.clone();
+    let key_value = item.value.clone();
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("id", item.trigger_id.clone(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", item.content_id.clone(), None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            let node = event.data();
+            trigger.set(Some(node.clone()));
+            register(node);
+        }),
+        dioxus_elements::events::onpointerenter(move |event: PointerEvent| {
+            if !disabled && event.pointer_type() != "touch" {
+                ctx.on_trigger_enter(enter_value.clone());
+            }
+        }),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            if !disabled && event.pointer_type() != "touch" {
+                ctx.on_trigger_leave();
+            }
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if disabled {
+                return;
+            }
+            ctx.open_timer.reset();
+            ctx.close_timer.reset();
+            let next = if *ctx.value.peek() == click_value {
+                String::new()
+            } else {
+                click_value.clone()
+            };
+            ctx.select(next);
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let opens = match (*ctx.orientation.peek(), *ctx.dir.peek()) {
+                (Orientation::Horizontal, _) => event.key() == Key::ArrowDown,
+                (Orientation::Vertical, Direction::Ltr) => event.key() == Key::ArrowRight,
+                (Orientation::Vertical, Direction::Rtl) => event.key() == Key::ArrowLeft,
+            };
+            if opens && !disabled {
+                event.prevent_default();
+                event.stop_propagation();
+                ctx.focus_on_open.set(Some(key_value.clone()));
+                ctx.select(key_value.clone());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 面板中的連結不加入清單的方向鍵導覽
+#[derive(Clone, Copy)]
+struct ContentMarker;
+
+/// The panel of links opened by the item's trigger.
+///
+/// Rendered inside the [`NavigationMenuViewport`] when there is one, and in
+/// place otherwise.
+#[component]
+pub fn NavigationMenuContent(
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired on Escape; prevent it to keep the panel open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the panel; prevent it to keep the
+    /// panel open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    use_context_provider(|| ContentMarker);
+    let ctx = use_context::<NavigationMenuContext>();
+    let item = use_context::<ItemContext>();
+    let open = *ctx.value.read() == item.value;
+
+    let content = rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                NavigationMenuContentImpl {
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    {children.clone()}
+                }
+            },
+        }
+    };
+
+    match (ctx.viewport)() {
+        Some(viewport) => rsx! {
+            Portal { target: PortalTarget::Id(viewport), {content} }
+        },
+        None => content,
+    }
+}
+
+#[component]
+fn NavigationMenuContentImpl(
+    element_attributes: Vec<Attribute>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let item = use_context::<ItemContext>();
+    let value = ctx.value.read().clone();
+    let open = value == item.value;
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let size = use_size(node);
+
+    // 開啟中的面板把大小提供給 viewport
+    use_effect(use_reactive!(|(open,)| {
+        if !open {
+            return;
+        }
+        if let Some(size) = size() {
+            ctx.viewport_size.set(Some((size.width, size.height)));
+        }
+    }));
+
+    // 進入時與上一個面板比較，離開時與下一個面板比較
+    let motion = {
+        let previous = ctx.previous_value.read().clone();
+        let (other, entering) = if open {
+            (previous, true)
+        } else {
+            (value, false)
+        };
+        match (ctx.index_of(&item.value), ctx.index_of(&other)) {
+            (Some(own), Some(other)) if other != own => Some(match (entering, other < own) {
+                (true, true) => "from-end",
+                (true, false) => "from-start",
+                (false, true) => "to-end",
+                (false, false) => "to-start",
+            }),
+            _ => None,
+        }
+    };
+
+    let value_for_focus = item.value.clone();
+    let own_attributes = vec![
+        Attribute::new("id", item.content_id.clone(), None, false),
+        Attribute::new("aria-labelledby", item.trigger_id.clone(), None, false),
+        Attribute::new("data-motion", motion, None, false),
+        Attribute::new(
+            "data-orientation",
+            ctx.orientation.read().as_str(),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            let data = event.data();
+            node.set(Some(data.clone()));
+            if *ctx.focus_on_open.peek() == Some(value_for_focus.clone()) {
+                ctx.focus_on_open.set(None);
+                #[cfg(feature = "web")]
+                if let Some(first) = super::dom::web_element(&data)
+                    .and_then(|element| super::dom::tabbable_elements(&element).into_iter().next())
+                {
+                    super::dom::focus(&first);
+                }
+            }
+        }),
+        dioxus_elements::events::onpointerenter(move |_: PointerEvent| ctx.close_timer.reset()),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            if event.pointer_type() != "touch" {
+                ctx.on_trigger_leave();
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, element_attributes);
+    let trigger = item.trigger;
+
+    rsx! {
+        DismissableLayer {
+            inside_elements: vec![trigger],
+            on_escape_key_down: move |event: DismissEvent| {
+                if let Some(handler) = on_escape_key_down {
+                    handler.call(event.clone());
+                }
+                // 以 Escape 關閉時讓焦點回到觸發按鈕
+                if event.is_default_prevented() {
+                    return;
+                }
+                if let Some(trigger) = trigger.peek().clone() {
+                    spawn(async move {
+                        let _ = trigger.set_focus(true).await;
+                    });
+                }
+            },
+            on_pointer_down_outside,
+            on_focus_outside: move |event: DismissEvent| event.prevent_default(),
+            on_dismiss: move |_| {
+                let mut ctx = ctx;
+                ctx.select(String::new());
+            },
+            as_child: move |layer: Vec<Attribute>| {
+                let attributes = merge_attributes(layer, attributes.clone());
+                rsx! {
+                    div {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// A navigation link, at the top level of the list or inside a panel.
+/// Activating it closes the menu.
+#[component]
+pub fn NavigationMenuLink(
+    /// Marks the link to the current page with `aria-current="page"` and
+    /// `data-active`.
+    #[props(default)]
+    active: bool,
+    /// Render your own element instead of an `a`, e.g. a router link; see
+    /// [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let mut register = use_list_focusable();
+
+    let own_attributes = vec![
+        Attribute::new("aria-current", active.then_some("page"), None, false),
+        Attribute::new("data-active", active.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| register(event.data())),
+        dioxus_elements::events::onclick(move |_: MouseEvent| ctx.select(String::new())),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        a {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The shared container the open panel is rendered into. Sized to the
+/// active panel through CSS variables.
+///
+/// It stays mounted so the panels always have a place to render into; style
+/// its closed state with `data-state="closed"`.
+#[component]
+pub fn NavigationMenuViewport(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<NavigationMenuContext>();
+    let id = use_id();
+    let open = !ctx.value.read().is_empty();
+
+    use_effect({
+        let id = id.clone();
+        move || ctx.viewport.set(Some(id.clone()))
+    });
+    use_drop(move || ctx.viewport.set(None));
+
+    let mut style = String::new();
+    if let Some((width, height)) = (ctx.viewport_size)() {
+        style = format!(
+            "--primitive-navigation-menu-viewport-width: {width}px; \
+             --primitive-navigation-menu-viewport-height: {height}px;"
+        );
+    }
+    let own_attributes = vec![
+        Attribute::new("id", id, None, false),
+        Attribute::new("style", style, None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        Attribute::new(
+            "data-orientation",
+            ctx.orientation.read().as_str(),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onpointerenter(move |_: PointerEvent| ctx.close_timer.reset()),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            if event.pointer_type() != "touch" {
+                ctx.on_trigger_leave();
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div { ..attributes }
+    }
+}
+
+/// Highlights the trigger of the open panel. Render it inside
+/// [`NavigationMenuList`]; it is positioned under the active trigger with
+/// `position: absolute`.
+#[component]
+pub fn NavigationMenuIndicator(
+    /// Keep the indicator mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<NavigationMenuContext>();
+    let open = !ctx.value.read().is_empty();
+    let trigger = use_rect(ctx.active_trigger);
+    let list = use_rect(ctx.list);
+
+    let style = match (trigger(), list()) {
+        (Some(trigger), Some(list)) => match (ctx.orientation)() {
+            Orientation::Horizontal => format!(
+                "position: absolute; left: 0; width: {}px; transform: translateX({}px);",
+                trigger.width,
+                trigger.x - list.x
+            ),
+            Orientation::Vertical => format!(
+                "position: absolute; top: 0; height: {}px; transform: translateY({}px);",
+                trigger.height,
+                trigger.y - list.y
+            ),
+        },
+        _ => String::from("position: absolute;"),
+    };
+    let own_attributes = vec![
+        Attribute::new("aria-hidden", "true", None, false),
+        Attribute::new("style", style, None, false),
+        Attribute::new(
+            "data-orientation",
+            ctx.orientation.read().as_str(),
+            None,
+            false,
+        ),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! NumberInput Primitive
+//!
+//! A text field for numbers following the WAI-ARIA spinbutton pattern. The
+//! arrow keys step the value, Shift or Page Up/Down by a larger step, and
+//! Home/End jump to the bounds. The increment and decrement triggers repeat
+//! while held, and a scrubber changes the value by dragging sideways.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     NumberInput { min: 0.0, max: 10.0, step: 0.5,
+//!         NumberInputScrubber { "Quantity" }
+//!         NumberInputDecrementTrigger { "−" }
+//!         NumberInputField {}
+//!         NumberInputIncrementTrigger { "+" }
+//!     }
+//! }
+//! ```
+//!
+//! A value outside `min` and `max`, or text that is not a number, marks the
+//! parts with `data-invalid` and the field with `aria-invalid`.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_move::{MovePhase, use_move};
+use super::hooks::use_timer::{use_interval, use_timeout};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+// 按住按鈕後開始重複前的延遲，以及重複的間隔
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+const REPEAT_INTERVAL: Duration = Duration::from_millis(60);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Config {
+    min: Option<f64>,
+    max: Option<f64>,
+    step: f64,
+    large_step: f64,
+    clamp_value_on_blur: bool,
+    allow_wheel: bool,
+    disabled: bool,
+    read_only: bool,
+}
+
+impl Config {
+    fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+
+    fn out_of_range(&self, value: f64) -> bool {
+        self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max)
+    }
+
+    // 小數位數跟隨 step，避免 0.1 + 0.2 之類的浮點誤差出現在畫面上
+    fn decimals(&self) -> usize {
+        let step = self.step.to_string();
+        step.split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len())
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        let factor = 10f64.powi(self.decimals() as i32);
+        (value * factor).round() / factor
+    }
+
+    // 輸入的值比 step 精細時照原樣顯示，不要四捨五入掉
+    fn format(&self, value: f64) -> String {
+        if self.round(value) == value {
+            format!("{:.*}", self.decimals(), value)
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn editable(&self) -> bool {
+        !self.disabled && !self.read_only
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NumberInputContext {
+    value: Memo<Option<f64>>,
+    set_value: Callback<Option<f64>>,
+    config: Signal<Config>,
+    // 使用者正在輸入、尚未套用的文字
+    draft: Signal<Option<String>>,
+    field: Signal<Option<Rc<MountedData>>>,
+    field_id: Signal<String>,
+}
+
+impl NumberInputContext {
+    fn set(&self, value: Option<f64>) {
+        if value != *self.value.peek() {
+            self.set_value.call(value);
+        }
+    }
+
+    /// Step the value by `amount`; an empty field starts from the bound it
+    /// moves away from, or from zero.
+    fn step_by(&mut self, amount: f64) {
+        let config = *self.config.peek();
+        if !config.editable() {
+            return;
+        }
+        self.commit_draft();
+        let next = match *self.value.peek() {
+            Some(value) => value + amount,
+            None if amount > 0.0 => config.min.unwrap_or(0.0),
+            None => config.max.unwrap_or(0.0),
+        };
+        self.set(Some(config.clamp(config.round(next))));
+    }
+
+    fn commit_draft(&mut self) {
+        let Some(text) = self.draft.peek().clone() else {
+            return;
+        };
+        self.draft.set(None);
+        let text = text.trim();
+        if text.is_empty() {
+            self.set(None);
+        } else if let Ok(parsed) = text.parse::<f64>() {
+            self.set(Some(parsed));
+        } else {
+            // 無法解析時保留文字並標示為無效
+            self.draft.set(Some(text.to_string()));
+        }
+    }
+
+    fn invalid(&self) -> bool {
+        let config = (self.config)();
+        if let Some(text) = (self.draft)() {
+            let text = text.trim();
+            return !text.is_empty() && text.parse::<f64>().is_err();
+        }
+        (self.value)().is_some_and(|value| config.out_of_range(value))
+    }
+}
+
+/// The root of a number input, owning its value.
+///
+/// Pass `value` and `on_value_change` to control the number, or
+/// `default_value` to let the input manage it. The value is `None` while
+/// the field is empty.
+#[component]
+pub fn NumberInput(
+    value: Option<Option<f64>>,
+    #[props(default)] default_value: Option<f64>,
+    on_value_change: Option<EventHandler<Option<f64>>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    #[props(default = 1.0)] step: f64,
+    /// Used with Shift and Page Up/Down.
+    #[props(default = 10.0)]
+    large_step: f64,
+    /// Clamp an out-of-range value to `min` and `max` when the field loses
+    /// focus, instead of leaving it invalid.
+    #[props(default = true)]
+    clamp_value_on_blur: bool,
+    /// Step the value with the mouse wheel while the field is focused.
+    #[props(default)]
+    allow_wheel: bool,
+    #[props(default)] disabled: bool,
+    #[props(default)] read_only: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let config = use_synced_signal(Config {
+        min,
+        max,
+        step,
+        large_step,
+        clamp_value_on_blur,
+        allow_wheel,
+        disabled,
+        read_only,
+    });
+    let field_id = use_id();
+    let ctx = use_context_provider(|| NumberInputContext {
+        value,
+        set_value,
+        config,
+        draft: Signal::new(None),
+        field: Signal::new(None),
+        field_id: Signal::new(field_id),
+    });
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-readonly", read_only.then_some(""), None, false),
+        Attribute::new("data-invalid", ctx.invalid().then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The text field, with `role="spinbutton"`.
+///
+/// Typed text is applied when the field changes (on Enter or blur).
+#[component]
+pub fn NumberInputField(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<NumberInputContext>();
+    let mut focused = use_signal(|| false);
+    let config = (ctx.config)();
+    let value = (ctx.value)();
+    let text = (ctx.draft)()
+        .unwrap_or_else(|| value.map(|value| config.format(value)).unwrap_or_default());
+    let invalid = ctx.invalid();
+    let mut draft = ctx.draft;
+    let mut field = ctx.field;
+
+    let own_attributes = vec![
+        Attribute::new("id", (ctx.field_id)(), None, false),
+        Attribute::new("type", "text", None, false),
+        Attribute::new("role", "spinbutton", None, false),
+        Attribute::new(
+            "inputmode",
+            if config.step.fract() == 0.0 {
+                "numeric"
+            } else {
+                "decimal"
+            },
+            None,
+            false,
+        ),
+        Attribute::new("autocomplete", "off", None, false),
+        Attribute::new("autocorrect", "off", None, false),
+        Attribute::new("spellcheck", "false", None, false),
+        Attribute::new("value", text.clone(), None, false),
+        Attribute::new("aria-valuemin", config.min, None, false),
+        Attribute::new("aria-valuemax", config.max, None, false),
+        Attribute::new("aria-valuenow", value, None, false),
+        Attribute::new(
+            "aria-valuetext",
+            (!text.is_empty()).then_some(text),
+            None,
+            false,
+        ),
+        Attribute::new("aria-invalid", invalid.then_some("true"), None, false),
+        Attribute::new("disabled", config.disabled, None, false),
+        Attribute::new("readonly", config.read_only, None, false),
+        Attribute::new("data-invalid", invalid.then_some(""), None, false),
+        Attribute::new("data-disabled", config.disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            field.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onfocus(move |_| focused.set(true)),
+        dioxus_elements::events::onblur(move |_| {
+            focused.set(false);
+            ctx.commit_draft();
+            let config = *ctx.config.peek();
+            if config.clamp_value_on_blur {
+                if let Some(value) = *ctx.value.peek() {
+                    ctx.set(Some(config.clamp(value)));
+                }
+            }
+        }),
+        dioxus_elements::events::oninput(move |event: FormEvent| {
+            draft.set(Some(event.value()));
+        }),
+        dioxus_elements::events::onchange(move |_| ctx.commit_draft()),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let config = *ctx.config.peek();
+            let step = if event.modifiers().shift() {
+                config.large_step
+            } else {
+                config.step
+            };
+            match event.key() {
+                Key::ArrowUp => ctx.step_by(step),
+                Key::ArrowDown => ctx.step_by(-step),
+                Key::PageUp => ctx.step_by(config.large_step),
+                Key::PageDown => ctx.step_by(-config.large_step),
+                Key::Home if config.min.is_some() && config.editable() => {
+                    draft.set(None);
+                    ctx.set(config.min);
+                }
+                Key::End if config.max.is_some() && config.editable() => {
+                    draft.set(None);
+                    ctx.set(config.max);
+                }
+                Key::Enter => {
+                    ctx.commit_draft();
+                    return;
+                }
+                _ => return,
+            }
+            event.prevent_default();
+        }),
+        dioxus_elements::events::onwheel(move |event: WheelEvent| {
+            if !ctx.config.peek().allow_wheel || !*focused.peek() {
+                return;
+            }
+            let delta = event.delta().strip_units().y;
+            if delta == 0.0 {
+                return;
+            }
+            event.prevent_default();
+            let step = ctx.config.peek().step;
+            ctx.step_by(if delta < 0.0 { step } else { -step });
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        input { ..attributes }
+    }
+}
+
+// 兩個按鈕共用：按下時先走一步，按住超過延遲後持續重複
+#[component]
+fn StepTrigger(
+    direction: f64,
+    label: &'static str,
+    as_child: Option<AsChild>,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NumberInputContext>();
+    let mut repeat = use_interval(REPEAT_INTERVAL, move || {
+        let step = ctx.config.peek().step;
+        ctx.step_by(direction * step);
+    });
+    let mut delay = use_timeout(REPEAT_DELAY, move || repeat.start());
+    let mut stop = move || {
+        delay.reset();
+        repeat.reset();
+    };
+
+    let config = (ctx.config)();
+    let at_bound = match ((ctx.value)(), direction > 0.0) {
+        (Some(value), true) => config.max.is_some_and(|max| value >= max),
+        (Some(value), false) => config.min.is_some_and(|min| value <= min),
+        (None, _) => false,
+    };
+    let disabled = !config.editable() || at_bound;
+
+    use_effect(use_reactive!(|(disabled,)| {
+        if disabled {
+            stop();
+        }
+    }));
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("tabindex", "-1", None, false),
+        Attribute::new("aria-label", label, None, false),
+        Attribute::new("aria-controls", (ctx.field_id)(), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onpointerdown(move |event: PointerEvent| {
+            if event.pointer_type() == "mouse"
+                && event.trigger_button() != Some(MouseButton::Primary)
+            {
+                return;
+            }
+            // 不讓按鈕搶走焦點，焦點留在輸入框
+            event.prevent_default();
+            if let Some(field) = ctx.field.peek().clone() {
+                spawn(async move {
+                    let _ = field.set_focus(true).await;
+                });
+            }
+            let step = ctx.config.peek().step;
+            ctx.step_by(direction * step);
+            delay.start();
+        }),
+        dioxus_elements::events::onpointerup(move |_| stop()),
+        dioxus_elements::events::onpointerleave(move |_| stop()),
+        dioxus_elements::events::onpointercancel(move |_| stop()),
+        dioxus_elements::events::oncontextmenu(move |event: MouseEvent| {
+            // 觸控長按會開啟選單，打斷重複
+            event.prevent_default();
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button that increases the value by one step, repeating while held.
+/// Disabled at `max`.
+#[component]
+pub fn NumberInputIncrementTrigger(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        StepTrigger { direction: 1.0, label: "Increase", as_child, attributes, {children} }
+    }
+}
+
+/// A button that decreases the value by one step, repeating while held.
+/// Disabled at `min`.
+#[component]
+pub fn NumberInputDecrementTrigger(
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        StepTrigger { direction: -1.0, label: "Decrease", as_child, attributes, {children} }
+    }
+}
+
+/// Changes the value by dragging sideways, one step every
+/// `pixels_per_step` pixels, e.g. on the field's label. Shift drags in
+/// large steps.
+#[component]
+pub fn NumberInputScrubber(
+    #[props(default = 4.0)] pixels_per_step: f64,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<NumberInputContext>();
+    let mut scrubbing = use_signal(|| false);
+    // 不足一步的位移累積到下一次
+    let mut pending = use_signal(|| 0.0);
+    let move_attributes = use_move(move |event| match event.phase {
+        MovePhase::Start => {
+            pending.set(0.0);
+            scrubbing.set(event.pointer_type != "keyboard");
+        }
+        MovePhase::Move => {
+            let config = *ctx.config.peek();
+            let step = if event.shift_key {
+                config.large_step
+            } else {
+                config.step
+            };
+            // 鍵盤每按一次走一步，與指標的像素無關
+            let steps = if event.pointer_type == "keyboard" {
+                event.delta_x - event.delta_y
+            } else {
+                let total = *pending.peek() + event.delta_x / pixels_per_step.max(1.0);
+                pending.set(total.fract());
+                total.trunc()
+            };
+            if steps != 0.0 {
+                ctx.step_by(steps * step);
+            }
+        }
+        MovePhase::End => scrubbing.set(false),
+    });
+
+    let disabled = !(ctx.config)().editable();
+    let mut own_attributes = vec![
+        Attribute::new(
+            "style",
+            "cursor: ew-resize; touch-action: none; user-select: none;",
+            None,
+            false,
+        ),
+        Attribute::new("data-scrubbing", scrubbing().then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    if !disabled {
+        own_attributes.extend(move_attributes);
+    }
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
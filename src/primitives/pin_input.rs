@@ -0,0 +1,285 @@
+//! PinInput Primitive
+//!
+//! One single-character input per character of a PIN or verification code.
+//! Typing a character moves to the next input, Backspace on an empty input
+//! deletes the previous character, and a pasted or autofilled code is spread
+//! across the inputs. `on_complete` fires once every input is filled.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     PinInput { length: 6, otp: true, on_complete: move |code| verify(code) }
+//! }
+//! ```
+
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::segment::{SegmentFocus, use_segment_focus};
+use super::slot::merge_attributes;
+use dioxus::prelude::*;
+
+/// Which characters a [`PinInput`] accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PinInputType {
+    #[default]
+    Numeric,
+    Alphabetic,
+    Alphanumeric,
+}
+
+impl PinInputType {
+    fn accepts(&self, character: char) -> bool {
+        match self {
+            PinInputType::Numeric => character.is_ascii_digit(),
+            PinInputType::Alphabetic => character.is_alphabetic(),
+            PinInputType::Alphanumeric => character.is_alphanumeric(),
+        }
+    }
+}
+
+/// A row of `length` single-character inputs, owning the entered code.
+///
+/// Pass `value` and `on_value_change` to control the code, or
+/// `default_value` to let the input manage it. The inputs are filled from
+/// the start, so focusing an input past the first empty one focuses that
+/// empty one instead. `children` are rendered after the inputs.
+#[component]
+pub fn PinInput(
+    value: Option<String>,
+    #[props(default)] default_value: String,
+    on_value_change: Option<EventHandler<String>>,
+    /// Called with the code once every input is filled by the user.
+    on_complete: Option<EventHandler<String>>,
+    #[props(default = 4)] length: usize,
+    #[props(default)] r#type: PinInputType,
+    /// Hide the characters like a password field.
+    #[props(default)]
+    mask: bool,
+    /// Let the browser autofill a one-time code received by SMS.
+    #[props(default)]
+    otp: bool,
+    /// Shown in each empty input.
+    #[props(default)]
+    placeholder: String,
+    /// Submits the code with a form under this name.
+    name: Option<String>,
+    #[props(default)] disabled: bool,
+    #[props(default)] invalid: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let focus = use_segment_focus();
+
+    let update = use_callback(move |next: String| {
+        let next: String = next.chars().take(length).collect();
+        if next == *value.peek() {
+            return;
+        }
+        set_value.call(next.clone());
+        if next.chars().count() == length {
+            if let Some(on_complete) = on_complete {
+                on_complete.call(next);
+            }
+        }
+    });
+
+    let code = value();
+    let characters: Vec<char> = code.chars().collect();
+    let complete = characters.len() >= length;
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new("data-complete", complete.then_some(""), None, false),
+        Attribute::new("data-invalid", invalid.then_some(""), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div {
+            ..attributes,
+            for index in 0..length {
+                PinInputCell {
+                    key: "{index}",
+                    index,
+                    length,
+                    characters: characters.clone(),
+                    kind: r#type,
+                    mask,
+                    otp,
+                    placeholder: placeholder.clone(),
+                    disabled,
+                    invalid,
+                    focus,
+                    update,
+                }
+            }
+            if let Some(name) = name {
+                input { r#type: "hidden", name, value: code }
+            }
+            {children}
+        }
+    }
+}
+
+#[component]
+fn PinInputCell(
+    index: usize,
+    length: usize,
+    characters: Vec<char>,
+    kind: PinInputType,
+    mask: bool,
+    otp: bool,
+    placeholder: String,
+    disabled: bool,
+    invalid: bool,
+    focus: SegmentFocus,
+    update: Callback<String>,
+) -> Element {
+    let mut node = use_signal(|| None);
+    use_drop(move || {
+        let mut focus = focus;
+        focus.unregister(index);
+    });
+
+    let character = characters.get(index).copied();
+    let filled = characters.len();
+    let text = character.map(String::from).unwrap_or_default();
+
+    // 以 index 之前的字元加上新的字元組成新的值
+    let replace_from = {
+        let characters = characters.clone();
+        move |start: usize, inserted: &[char], keep_rest: bool| {
+            let mut next: Vec<char> = characters[..start.min(characters.len())].to_vec();
+            next.extend_from_slice(inserted);
+            if keep_rest {
+                next.extend(characters.iter().skip(start + inserted.len()));
+            }
+            next.into_iter().collect::<String>()
+        }
+    };
+    let remove_at = {
+        let characters = characters.clone();
+        move |position: usize| {
+            let mut next = characters.clone();
+            if position < next.len() {
+                next.remove(position);
+            }
+            next.into_iter().collect::<String>()
+        }
+    };
+
+    rsx! {
+        input {
+            r#type: if mask { "password" } else { "text" },
+            inputmode: if kind == PinInputType::Numeric { "numeric" } else { "text" },
+            pattern: (kind == PinInputType::Numeric).then_some("[0-9]*"),
+            autocomplete: if otp && index == 0 { "one-time-code" } else { "off" },
+            spellcheck: "false",
+            placeholder,
+            disabled,
+            value: text,
+            aria_label: format!("Character {} of {}", index + 1, length),
+            aria_invalid: invalid.then_some("true"),
+            "data-index": index,
+            "data-filled": character.is_some().then_some(""),
+            onmounted: move |event: MountedEvent| {
+                let data = event.data();
+                node.set(Some(data.clone()));
+                let mut focus = focus;
+                focus.register(index, data);
+            },
+            onfocus: move |_| {
+                // 只能依序填入，跳到第一個空格
+                if index > filled {
+                    focus.focus(filled);
+                    return;
+                }
+                #[cfg(feature = "web")]
+                if let Some(node) = node.peek().as_ref() {
+                    web::select(node);
+                }
+            },
+            oninput: move |event: FormEvent| {
+                let typed: Vec<char> = event
+                    .value()
+                    .chars()
+                    .filter(|c| kind.accepts(*c))
+                    .collect();
+                #[cfg(feature = "web")]
+                if let Some(node) = node.peek().as_ref() {
+                    // 覆寫畫面上的文字，輸入相同字元時 vdom 不會更新
+                    web::set_value(node, &character.map(String::from).unwrap_or_default());
+                }
+                let inserted: Vec<char> = match (typed.len(), character) {
+                    (0, _) => return,
+                    // 在已有字元的格子打字時，留下新打的那個
+                    (2, Some(old)) => vec![if typed[0] == old { typed[1] } else { typed[0] }],
+                    _ => typed,
+                };
+                let pasted = inserted.len() > 1;
+                let next = replace_from(index.min(filled), &inserted, !pasted);
+                let next_index = (index.min(filled) + inserted.len()).min(length - 1);
+                update.call(next);
+                focus.focus(next_index);
+            },
+            onkeydown: move |event: KeyboardEvent| match event.key() {
+                Key::Backspace => {
+                    event.prevent_default();
+                    if character.is_some() {
+                        update.call(remove_at(index));
+                    } else if index > 0 {
+                        update.call(remove_at(index - 1));
+                        focus.focus(index - 1);
+                    }
+                }
+                Key::Delete => {
+                    event.prevent_default();
+                    update.call(remove_at(index));
+                }
+                Key::ArrowLeft if index > 0 => {
+                    event.prevent_default();
+                    focus.focus(index - 1);
+                }
+                Key::ArrowRight if index + 1 < length && index < filled => {
+                    event.prevent_default();
+                    focus.focus(index + 1);
+                }
+                Key::Home => {
+                    event.prevent_default();
+                    focus.focus(0);
+                }
+                Key::End => {
+                    event.prevent_default();
+                    focus.focus(filled.min(length - 1));
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use dioxus::prelude::*;
+    use js_sys::{Function, Reflect};
+    use wasm_bindgen::JsCast;
+
+    // 聚焦時選取內容，讓打字直接取代現有字元
+    pub(super) fn select(node: &MountedData) {
+        let Some(element) = crate::primitives::dom::web_element(node) else {
+            return;
+        };
+        if let Some(select) = Reflect::get(&element, &"select".into())
+            .ok()
+            .and_then(|select| select.dyn_into::<Function>().ok())
+        {
+            let _ = select.call0(&element);
+        }
+    }
+
+    pub(super) fn set_value(node: &MountedData, value: &str) {
+        if let Some(element) = crate::primitives::dom::web_element(node) {
+            let _ = Reflect::set(&element, &"value".into(), &value.into());
+        }
+    }
+}
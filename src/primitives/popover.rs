@@ -0,0 +1,317 @@
+//! Popover Primitive
+//!
+//! Rich content in a floating panel, anchored to the button that opens it.
+//! The panel is positioned with the [`popper`](super::popper) and closes on
+//! Escape or on interaction outside of it. A modal popover also traps focus,
+//! locks scrolling and makes the rest of the page inert, like a dialog.
+
+pub use super::popper::PopperArrow as PopoverArrow;
+
+use super::context::create_context::use_synced_signal;
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::focus_guards::use_focus_guards;
+use super::focus_scope::{AutoFocusEvent, FocusScope};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, PositionOptions, Side};
+use super::presence::Presence;
+use super::scroll_lock::use_scroll_lock;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct PopoverContext {
+    open: Memo<bool>,
+    set_open: Callback<bool>,
+    modal: Signal<bool>,
+    content_id: String,
+    trigger: Signal<Option<Rc<MountedData>>>,
+    // 有 PopoverAnchor 時內容改為對齊它，而不是觸發按鈕
+    has_custom_anchor: Signal<bool>,
+}
+
+fn state_str(open: bool) -> &'static str {
+    if open { "open" } else { "closed" }
+}
+
+/// The root of a popover, owning its open state.
+///
+/// Pass `open` and `on_open_change` to control the state, or
+/// `default_open` to let the popover manage it.
+#[component]
+pub fn Popover(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Trap focus in the content, lock scrolling and make the rest of the
+    /// page inert while open.
+    #[props(default)]
+    modal: bool,
+    children: Element,
+) -> Element {
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let modal = use_synced_signal(modal);
+    let content_id = use_id();
+    use_context_provider(|| PopoverContext {
+        open,
+        set_open,
+        modal,
+        content_id,
+        trigger: Signal::new(None),
+        has_custom_anchor: Signal::new(false),
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The button that toggles the popover. The content is positioned against it
+/// unless a [`PopoverAnchor`] is rendered.
+#[component]
+pub fn PopoverTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopoverContext>();
+    let open = (ctx.open)();
+    let set_open = ctx.set_open;
+    let mut trigger = ctx.trigger;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("aria-haspopup", "dialog", None, false),
+        Attribute::new("aria-expanded", open, None, false),
+        Attribute::new("aria-controls", ctx.content_id.clone(), None, false),
+        Attribute::new("data-state", state_str(open), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            trigger.set(Some(event.data()))
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| set_open.call(!open)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    let render = move |attributes: Vec<Attribute>| {
+        if let Some(as_child) = as_child {
+            return as_child.call(attributes);
+        }
+        rsx! {
+            button {
+                ..attributes,
+                {children.clone()}
+            }
+        }
+    };
+
+    if *ctx.has_custom_anchor.read() {
+        return render(attributes);
+    }
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| render(merge_attributes(anchor, attributes.clone())),
+        }
+    }
+}
+
+/// Positions the content against this element instead of the trigger.
+#[component]
+pub fn PopoverAnchor(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopoverContext>();
+    let mut has_custom_anchor = ctx.has_custom_anchor;
+
+    use_effect(move || has_custom_anchor.set(true));
+    use_drop(move || has_custom_anchor.set(false));
+
+    rsx! {
+        PopperAnchor { as_child, ..attributes, {children} }
+    }
+}
+
+/// The floating panel, rendered while the popover is open and until its exit
+/// animation finishes.
+#[component]
+pub fn PopoverContent(
+    #[props(default)] side: Side,
+    #[props(default)] side_offset: f64,
+    #[props(default)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired when focus moves into the content on open. Prevent it to focus
+    /// another element yourself.
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired when focus returns to the trigger on close. Prevent it to focus
+    /// another element yourself.
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    /// Fired on Escape; prevent it to keep the popover open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the content; prevent it to keep the
+    /// popover open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired when focus moves outside the content; prevent it to keep the
+    /// popover open.
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    /// Fired for any interaction outside the content; prevent it to keep the
+    /// popover open.
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopoverContext>();
+    let open = (ctx.open)();
+    let position = PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    };
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| rsx! {
+                PopoverContentImpl {
+                    position,
+                    element_attributes: merge_attributes(presence, attributes.clone()),
+                    on_open_auto_focus,
+                    on_close_auto_focus,
+                    on_escape_key_down,
+                    on_pointer_down_outside,
+                    on_focus_outside,
+                    on_interact_outside,
+                    as_child,
+                    {children.clone()}
+                }
+            },
+        }
+    }
+}
+
+// 只在內容掛載時存在，讓焦點管理與圖層跟著 Presence 一起掛載與卸載
+#[component]
+fn PopoverContentImpl(
+    position: PositionOptions,
+    element_attributes: Vec<Attribute>,
+    on_open_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_close_auto_focus: Option<EventHandler<AutoFocusEvent>>,
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    on_focus_outside: Option<EventHandler<DismissEvent>>,
+    on_interact_outside: Option<EventHandler<DismissEvent>>,
+    as_child: Option<AsChild>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopoverContext>();
+    let open = (ctx.open)();
+    let modal = (ctx.modal)();
+    let set_open = ctx.set_open;
+    let trigger = ctx.trigger;
+    use_scroll_lock(open && modal);
+    use_focus_guards();
+
+    let own_attributes = vec![
+        Attribute::new("role", "dialog", None, false),
+        Attribute::new("id", ctx.content_id.clone(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, element_attributes);
+
+    rsx! {
+        PopperContent {
+            side: position.side,
+            side_offset: position.side_offset,
+            align: position.align,
+            align_offset: position.align_offset,
+            avoid_collisions: position.avoid_collisions,
+            collision_padding: position.collision_padding,
+            FocusScope {
+                trapped: open && modal,
+                loop_focus: modal,
+                on_mount_auto_focus: on_open_auto_focus,
+                on_unmount_auto_focus: on_close_auto_focus,
+                as_child: move |scope: Vec<Attribute>| {
+                    let element_attributes = merge_attributes(scope, attributes.clone());
+                    let children = children.clone();
+                    rsx! {
+                        DismissableLayer {
+                            disable_outside_pointer_events: open && modal,
+                            modal: open && modal,
+                            // 按下觸發按鈕時由按鈕自己切換，不視為外部互動
+                            inside_elements: vec![trigger],
+                            on_escape_key_down,
+                            on_pointer_down_outside,
+                            on_focus_outside: move |event: DismissEvent| {
+                                if let Some(handler) = on_focus_outside {
+                                    handler.call(event.clone());
+                                }
+                                // 焦點被困住時移到外部只會是暫時的
+                                if modal {
+                                    event.prevent_default();
+                                }
+                            },
+                            on_interact_outside,
+                            on_dismiss: move |_| set_open.call(false),
+                            as_child: move |layer: Vec<Attribute>| {
+                                let attributes = merge_attributes(layer, element_attributes.clone());
+                                if let Some(as_child) = as_child {
+                                    return as_child.call(attributes);
+                                }
+                                rsx! {
+                                    div {
+                                        ..attributes,
+                                        {children.clone()}
+                                    }
+                                }
+                            },
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// A button that closes the popover.
+#[component]
+pub fn PopoverClose(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopoverContext>();
+    let set_open = ctx.set_open;
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        dioxus_elements::events::onclick(move |_: MouseEvent| set_open.call(false)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
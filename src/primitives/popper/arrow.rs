@@ -0,0 +1,84 @@
+use super::{PopperContentContext, Side};
+use crate::primitives::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+
+/// An arrow on the edge of [`PopperContent`](super::PopperContent) that
+/// points at the center of the anchor.
+///
+/// Must be rendered inside a `PopperContent`. The arrow follows the placed
+/// side, stays within the content's edge when the content is shifted, and
+/// its height is added to the content's `side_offset`.
+#[component]
+pub fn PopperArrow(
+    #[props(default = 10.0)] width: f64,
+    #[props(default = 5.0)] height: f64,
+    /// Render your own arrow instead of the default SVG triangle; see
+    /// [`AsChild`]. It should point down, and is rotated for other sides.
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<PopperContentContext>();
+
+    use_effect(use_reactive!(|(height,)| ctx.arrow_height.set(height)));
+    use_drop(move || ctx.arrow_height.set(0.0));
+
+    let measurements = (
+        *ctx.placement.read(),
+        *ctx.anchor_rect.read(),
+        *ctx.content_rect.read(),
+    );
+    let (Some(placement), Some(anchor), Some(content)) = measurements else {
+        return rsx! {};
+    };
+
+    // 箭頭沿著邊緣對準錨點中心，並限制在內容的範圍內
+    let (offset_property, offset) = match placement.side {
+        Side::Top | Side::Bottom => (
+            "left",
+            (anchor.x + anchor.width / 2.0 - placement.x - width / 2.0)
+                .min(content.width - width)
+                .max(0.0),
+        ),
+        Side::Left | Side::Right => (
+            "top",
+            (anchor.y + anchor.height / 2.0 - placement.y - width / 2.0)
+                .min(content.height - width)
+                .max(0.0),
+        ),
+    };
+    let (transform_origin, transform) = match placement.side {
+        Side::Top => ("", "translateY(100%)"),
+        Side::Right => ("0 0", "translateY(50%) rotate(90deg) translateX(-50%)"),
+        Side::Bottom => ("center 0", "rotate(180deg)"),
+        Side::Left => ("100% 0", "translateY(50%) rotate(-90deg) translateX(50%)"),
+    };
+    let base_side = placement.side.opposite().as_str();
+    let wrapper_style = format!(
+        "position: absolute; {base_side}: 0; {offset_property}: {offset}px; \
+         transform-origin: {transform_origin}; transform: {transform};"
+    );
+
+    let own_attributes = vec![
+        Attribute::new("width", width, None, false),
+        Attribute::new("height", height, None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        span {
+            style: wrapper_style,
+            "data-primitive-popper-arrow": "",
+            if let Some(as_child) = as_child {
+                {as_child.call(attributes)}
+            } else {
+                svg {
+                    view_box: "0 0 30 10",
+                    preserve_aspect_ratio: "none",
+                    display: "block",
+                    ..attributes,
+                    polygon { points: "0,0 30,0 15,10" }
+                }
+            }
+        }
+    }
+}
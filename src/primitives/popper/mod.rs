@@ -0,0 +1,208 @@
+//! Popper Primitive
+//!
+//! Positions floating content such as popovers, tooltips, menus and select
+//! lists next to an anchor element. The content is placed on a [`Side`] of
+//! the anchor with an [`Align`]ment, flips and shifts to stay inside the
+//! viewport, and follows the anchor as it moves or changes size.
+//!
+//! The positioned element exposes its measurements as CSS variables:
+//! `--primitive-available-width`, `--primitive-available-height`,
+//! `--primitive-anchor-width`, `--primitive-anchor-height` and
+//! `--primitive-transform-origin`.
+
+mod arrow;
+mod position;
+
+pub use super::hooks::use_rect::Rect;
+pub use arrow::PopperArrow;
+pub use position::{Align, Placement, PositionOptions, Side, compute_position};
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_rect::use_rect;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone, Copy)]
+struct PopperContext {
+    anchor: Signal<Option<Rc<MountedData>>>,
+}
+
+// PopperContent 提供給 PopperArrow 的量測結果
+#[derive(Clone, Copy)]
+struct PopperContentContext {
+    placement: Memo<Option<Placement>>,
+    anchor_rect: ReadOnlySignal<Option<Rect>>,
+    content_rect: ReadOnlySignal<Option<Rect>>,
+    // 箭頭的高度會加到 side_offset 上，讓箭頭尖端碰到錨點
+    arrow_height: Signal<f64>,
+}
+
+/// Groups a [`PopperAnchor`] with the [`PopperContent`] positioned next to it.
+#[component]
+pub fn PopperRoot(children: Element) -> Element {
+    use_context_provider(|| PopperContext {
+        anchor: Signal::new(None),
+    });
+
+    rsx! { {children} }
+}
+
+/// The element [`PopperContent`] is positioned against.
+#[component]
+pub fn PopperAnchor(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<PopperContext>();
+
+    use_drop(move || ctx.anchor.set(None));
+
+    let own_attributes = vec![dioxus_elements::events::onmounted(
+        move |event: MountedEvent| ctx.anchor.set(Some(event.data())),
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 視窗大小只能在 web 上取得，其他平台不處理碰撞
+#[cfg(feature = "web")]
+fn use_viewport() -> ReadOnlySignal<Option<Rect>> {
+    use super::hooks::use_global_event::use_window_event;
+
+    fn measure() -> Option<Rect> {
+        let window = web_sys::window()?;
+        Some(Rect {
+            x: 0.0,
+            y: 0.0,
+            width: window.inner_width().ok()?.as_f64()?,
+            height: window.inner_height().ok()?.as_f64()?,
+        })
+    }
+
+    let mut viewport = use_signal(measure);
+    use_window_event("resize", false, move |_: web_sys::Event| {
+        viewport.set(measure())
+    });
+    viewport.into()
+}
+
+#[cfg(not(feature = "web"))]
+fn use_viewport() -> ReadOnlySignal<Option<Rect>> {
+    use_signal(|| None).into()
+}
+
+/// The floating content, positioned next to the [`PopperAnchor`] of the same
+/// [`PopperRoot`].
+///
+/// Rendered with `position: fixed`, hidden until its first measurement, and
+/// with `data-side`/`data-align` reflecting the final placement.
+#[component]
+pub fn PopperContent(
+    #[props(default)] side: Side,
+    #[props(default)] side_offset: f64,
+    #[props(default)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<PopperContext>();
+    let options = use_synced_signal(PositionOptions {
+        side,
+        side_offset,
+        align,
+        align_offset,
+        avoid_collisions,
+        collision_padding,
+    });
+    let mut content = use_signal(|| None::<Rc<MountedData>>);
+    let anchor_rect = use_rect(ctx.anchor);
+    let content_rect = use_rect(content);
+    let viewport = use_viewport();
+    let arrow_height = use_signal(|| 0.0);
+
+    let placement = use_memo(move || {
+        let (Some(anchor), Some(content)) = (anchor_rect(), content_rect()) else {
+            return None;
+        };
+        let mut options = options();
+        options.side_offset += arrow_height();
+        Some(compute_position(
+            &anchor,
+            content.width,
+            content.height,
+            viewport().as_ref(),
+            &options,
+        ))
+    });
+    use_context_provider(|| PopperContentContext {
+        placement,
+        anchor_rect,
+        content_rect,
+        arrow_height,
+    });
+
+    let placement = placement();
+    let mut style = String::from("position: fixed; left: 0; top: 0; ");
+    match placement {
+        Some(placement) => {
+            style.push_str(&format!(
+                "transform: translate({}px, {}px); ",
+                placement.x, placement.y
+            ));
+            if let Some(width) = placement.available_width {
+                style.push_str(&format!("--primitive-available-width: {width}px; "));
+            }
+            if let Some(height) = placement.available_height {
+                style.push_str(&format!("--primitive-available-height: {height}px; "));
+            }
+            let origin = match placement.side {
+                Side::Top => "bottom",
+                Side::Right => "left",
+                Side::Bottom => "top",
+                Side::Left => "right",
+            };
+            style.push_str(&format!("--primitive-transform-origin: {origin}; "));
+        }
+        // 第一次量測前先隱藏，避免在錯誤的位置閃爍
+        None => style.push_str("visibility: hidden; "),
+    }
+    if let Some(anchor) = anchor_rect() {
+        style.push_str(&format!(
+            "--primitive-anchor-width: {}px; --primitive-anchor-height: {}px;",
+            anchor.width, anchor.height
+        ));
+    }
+
+    let final_side = placement.map_or(side, |placement| placement.side);
+    let own_attributes = vec![
+        Attribute::new("style", style, None, false),
+        Attribute::new("data-side", final_side.as_str(), None, false),
+        Attribute::new("data-align", align.as_str(), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            content.set(Some(event.data()))
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Anchored positioning math.
+//!
+//! Platform independent: the components measure the anchor, the content and
+//! the viewport, and [`compute_position`] decides where the content goes.
+
+use crate::primitives::hooks::use_rect::Rect;
+
+/// The side of the anchor the content is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    Top,
+    Right,
+    #[default]
+    Bottom,
+    Left,
+}
+
+impl Side {
+    /// The value of the `data-side` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Top => "top",
+            Side::Right => "right",
+            Side::Bottom => "bottom",
+            Side::Left => "left",
+        }
+    }
+
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Right => Side::Left,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        matches!(self, Side::Top | Side::Bottom)
+    }
+}
+
+/// How the content is aligned with the anchor along the side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    Start,
+    #[default]
+    Center,
+    End,
+}
+
+impl Align {
+    /// The value of the `data-align` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Align::Start => "start",
+            Align::Center => "center",
+            Align::End => "end",
+        }
+    }
+}
+
+/// Where to place the content relative to its anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionOptions {
+    pub side: Side,
+    /// Distance from the anchor, in pixels.
+    pub side_offset: f64,
+    pub align: Align,
+    /// Offset from the `Start` or `End` alignment, in pixels.
+    pub align_offset: f64,
+    /// Flip to the opposite side and shift along the side to stay inside the
+    /// viewport.
+    pub avoid_collisions: bool,
+    /// Distance to keep from the viewport edges, in pixels.
+    pub collision_padding: f64,
+}
+
+impl Default for PositionOptions {
+    fn default() -> Self {
+        PositionOptions {
+            side: Side::default(),
+            side_offset: 0.0,
+            align: Align::default(),
+            align_offset: 0.0,
+            avoid_collisions: true,
+            collision_padding: 0.0,
+        }
+    }
+}
+
+/// The computed position of the content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub x: f64,
+    pub y: f64,
+    /// The side actually used, after flipping.
+    pub side: Side,
+    pub align: Align,
+    /// Space left for the content between the anchor and the viewport edge,
+    /// when the viewport is known.
+    pub available_width: Option<f64>,
+    pub available_height: Option<f64>,
+}
+
+fn place(
+    anchor: &Rect,
+    width: f64,
+    height: f64,
+    options: &PositionOptions,
+    side: Side,
+) -> (f64, f64) {
+    let main = match side {
+        Side::Top => anchor.y - height - options.side_offset,
+        Side::Bottom => anchor.bottom() + options.side_offset,
+        Side::Left => anchor.x - width - options.side_offset,
+        Side::Right => anchor.right() + options.side_offset,
+    };
+
+    let (start, length, size) = if side.is_vertical() {
+        (anchor.x, anchor.width, width)
+    } else {
+        (anchor.y, anchor.height, height)
+    };
+    let cross = match options.align {
+        Align::Start => start + options.align_offset,
+        Align::Center => start + (length - size) / 2.0,
+        Align::End => start + length - size - options.align_offset,
+    };
+
+    if side.is_vertical() {
+        (cross, main)
+    } else {
+        (main, cross)
+    }
+}
+
+// 內容在主軸方向超出視窗的距離，未超出時為 0
+fn main_axis_overflow(content: &Rect, side: Side, viewport: &Rect, padding: f64) -> f64 {
+    let overflow = match side {
+        Side::Top => viewport.y + padding - content.y,
+        Side::Bottom => content.bottom() - (viewport.bottom() - padding),
+        Side::Left => viewport.x + padding - content.x,
+        Side::Right => content.right() - (viewport.right() - padding),
+    };
+    overflow.max(0.0)
+}
+
+/// Position content of size `width` x `height` next to `anchor`.
+///
+/// Collisions are only avoided when the `viewport` is known.
+pub fn compute_position(
+    anchor: &Rect,
+    width: f64,
+    height: f64,
+    viewport: Option<&Rect>,
+    options: &PositionOptions,
+) -> Placement {
+    let mut side = options.side;
+    let (mut x, mut y) = place(anchor, width, height, options, side);
+
+    let Some(viewport) = viewport.filter(|_| options.avoid_collisions) else {
+        return Placement {
+            x,
+            y,
+            side,
+            align: options.align,
+            available_width: viewport.map(|viewport| viewport.width),
+            available_height: viewport.map(|viewport| viewport.height),
+        };
+    };
+    let padding = options.collision_padding;
+
+    // 放不下時，若另一側超出得比較少就翻轉
+    let content = |x, y| Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+    let overflow = main_axis_overflow(&content(x, y), side, viewport, padding);
+    if overflow > 0.0 {
+        let opposite = side.opposite();
+        let (flipped_x, flipped_y) = place(anchor, width, height, options, opposite);
+        let flipped_overflow =
+            main_axis_overflow(&content(flipped_x, flipped_y), opposite, viewport, padding);
+        if flipped_overflow < overflow {
+            side = opposite;
+            (x, y) = (flipped_x, flipped_y);
+        }
+    }
+
+    // 沿著側邊平移以留在視窗內；內容比視窗大時對齊起點
+    let shift = |value: f64, size: f64, start: f64, end: f64| {
+        value.min(end - padding - size).max(start + padding)
+    };
+    if side.is_vertical() {
+        x = shift(x, width, viewport.x, viewport.right());
+    } else {
+        y = shift(y, height, viewport.y, viewport.bottom());
+    }
+
+    let (available_width, available_height) = match side {
+        Side::Top => (
+            viewport.width - 2.0 * padding,
+            anchor.y - options.side_offset - viewport.y - padding,
+        ),
+        Side::Bottom => (
+            viewport.width - 2.0 * padding,
+            viewport.bottom() - padding - anchor.bottom() - options.side_offset,
+        ),
+        Side::Left => (
+            anchor.x - options.side_offset - viewport.x - padding,
+            viewport.height - 2.0 * padding,
+        ),
+        Side::Right => (
+            viewport.right() - padding - anchor.right() - options.side_offset,
+            viewport.height - 2.0 * padding,
+        ),
+    };
+
+    Placement {
+        x,
+        y,
+        side,
+        align: options.align,
+        available_width: Some(available_width.max(0.0)),
+        available_height: Some(available_height.max(0.0)),
+    }
+}
@@ -0,0 +1,79 @@
+//! Portal Primitive
+//!
+//! Renders its children somewhere else in the document, such as directly
+//! under `document.body`, so overlays escape `overflow: hidden` and stacking
+//! contexts of their ancestors. The children stay in the same place of the
+//! component tree, so contexts keep working inside the portal.
+
+use super::root::RootContext;
+use dioxus::prelude::*;
+
+/// Where a [`Portal`] moves its content.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PortalTarget {
+    /// Append to the portal container of the enclosing
+    /// [`PrimitivesRoot`](super::root::PrimitivesRoot), or to
+    /// `document.body` outside of one.
+    #[default]
+    Root,
+    /// Append to `document.body`.
+    Body,
+    /// Append to the element with this id.
+    Id(String),
+    /// Append to the first element matching this CSS selector.
+    Selector(String),
+}
+
+/// Renders `children` into [`PortalTarget`] instead of inline.
+///
+/// On the web the content's wrapper element is moved into the target once it
+/// is mounted. On other platforms, or when the target does not exist, the
+/// content is rendered inline.
+#[component]
+pub fn Portal(
+    #[props(default)] target: PortalTarget,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let root = try_use_context::<RootContext>();
+    let target = match target {
+        PortalTarget::Root => match root {
+            Some(root) => PortalTarget::Id(root.portal_container_id()),
+            None => PortalTarget::Body,
+        },
+        target => target,
+    };
+
+    rsx! {
+        div {
+            "data-primitive-portal": "",
+            onmounted: move |event: MountedEvent| {
+                #[cfg(feature = "web")]
+                move_into_target(&event.data(), &target);
+                #[cfg(not(feature = "web"))]
+                let _ = (event, &target);
+            },
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+fn move_into_target(node: &MountedData, target: &PortalTarget) {
+    use super::dom::{document, web_element};
+
+    let Some(document) = document() else {
+        return;
+    };
+    let container = match target {
+        PortalTarget::Root | PortalTarget::Body => document.body().map(web_sys::Element::from),
+        PortalTarget::Id(id) => document.get_element_by_id(id),
+        PortalTarget::Selector(selector) => document.query_selector(selector).ok().flatten(),
+    };
+
+    // 找不到目標時保留在原位置
+    if let (Some(container), Some(element)) = (container, web_element(node)) {
+        let _ = container.append_child(&element);
+    }
+}
@@ -0,0 +1,170 @@
+//! Presence Primitive
+//!
+//! Keeps content mounted while it animates out. When `present` flips to
+//! `false` the content switches to `data-state="closed"` and is only
+//! unmounted once its own CSS animation or transition has finished; those of
+//! its children are ignored. When the user prefers reduced motion, the
+//! content is unmounted right away.
+
+use super::hooks::use_prefers_reduced_motion::use_prefers_reduced_motion;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Mounts `children` while `present`, and keeps them mounted until their
+/// exit animation completes.
+///
+/// Style the exit with `[data-state="closed"]`. If the element has no
+/// animation or transition when it closes (or outside the `web` feature, where
+/// styles cannot be inspected), it is unmounted immediately.
+#[component]
+pub fn Presence(
+    present: bool,
+    /// Keep the content mounted even when not present, for animation
+    /// libraries that manage exits themselves.
+    #[props(default)]
+    force_mount: bool,
+    /// Called after the content has been unmounted following an exit.
+    on_exit_complete: Option<EventHandler<()>>,
+    /// Render your own element instead of a `div`; see [`AsChild`]. The
+    /// exit animation is read from that element.
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut is_mounted = use_signal(|| present);
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let reduced_motion = use_prefers_reduced_motion();
+
+    let mut finish_exit = move || {
+        if *is_mounted.peek() {
+            is_mounted.set(false);
+            if let Some(handler) = on_exit_complete {
+                handler.call(());
+            }
+        }
+    };
+
+    use_effect(use_reactive!(|(present,)| {
+        if present {
+            is_mounted.set(true);
+        } else if *is_mounted.peek() {
+            // 使用者偏好減少動態效果時不等待離場動畫
+            let animated = !*reduced_motion.peek() && has_exit_animation(node.peek().as_deref());
+            if !animated {
+                finish_exit();
+            }
+        }
+    }));
+
+    if !present && !is_mounted() && !force_mount {
+        return rsx! {};
+    }
+
+    let state = if present { "open" } else { "closed" };
+    let own_attributes = vec![
+        Attribute::new("data-state", state, None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| node.set(Some(event.data()))),
+        dioxus_elements::events::onanimationend(move |event: AnimationEvent| {
+            #[cfg(feature = "web")]
+            let own = is_own_event(
+                event
+                    .data()
+                    .downcast::<web_sys::AnimationEvent>()
+                    .map(|event| event.as_ref()),
+                node.peek().as_deref(),
+            );
+            #[cfg(not(feature = "web"))]
+            let own = {
+                let _ = event;
+                true
+            };
+            if !present && own {
+                finish_exit();
+            }
+        }),
+        dioxus_elements::events::ontransitionend(move |event: TransitionEvent| {
+            #[cfg(feature = "web")]
+            let own = is_own_event(
+                event
+                    .data()
+                    .downcast::<web_sys::TransitionEvent>()
+                    .map(|event| event.as_ref()),
+                node.peek().as_deref(),
+            );
+            #[cfg(not(feature = "web"))]
+            let own = {
+                let _ = event;
+                true
+            };
+            if !present && own {
+                finish_exit();
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 子元素的動畫或過渡結束時也會冒泡到內容，只有內容自己的事件代表離場結束
+#[cfg(feature = "web")]
+fn is_own_event(event: Option<&web_sys::Event>, node: Option<&MountedData>) -> bool {
+    let target = event.and_then(web_sys::Event::target);
+    let element = node.and_then(super::dom::web_element);
+    match (target, element) {
+        (Some(target), Some(element)) => {
+            wasm_bindgen::JsValue::from(target) == wasm_bindgen::JsValue::from(element)
+        }
+        // 無法判斷時照常結束，避免內容一直不卸載
+        _ => true,
+    }
+}
+
+// 檢查元素目前是否有 CSS 動畫或過渡效果
+#[cfg(feature = "web")]
+fn has_exit_animation(node: Option<&MountedData>) -> bool {
+    let Some(element) = node.and_then(super::dom::web_element) else {
+        return false;
+    };
+    let Some(style) =
+        web_sys::window().and_then(|window| window.get_computed_style(&element).ok().flatten())
+    else {
+        return false;
+    };
+
+    let animation_name = style
+        .get_property_value("animation-name")
+        .unwrap_or_default();
+    let has_animation = !animation_name.is_empty() && animation_name != "none";
+
+    // transition-duration 可能是以逗號分隔的多個值，例如 "0s, 0.2s"
+    let has_transition = style
+        .get_property_value("transition-duration")
+        .unwrap_or_default()
+        .split(',')
+        .any(|duration| {
+            let duration = duration.trim();
+            duration
+                .strip_suffix("ms")
+                .or_else(|| duration.strip_suffix('s'))
+                .and_then(|value| value.parse::<f64>().ok())
+                .is_some_and(|value| value > 0.0)
+        });
+
+    has_animation || has_transition
+}
+
+#[cfg(not(feature = "web"))]
+fn has_exit_animation(_node: Option<&MountedData>) -> bool {
+    false
+}
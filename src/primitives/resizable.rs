@@ -0,0 +1,493 @@
+//! Resizable Primitive
+//!
+//! Panels laid out in a row or column, with handles between them that are
+//! dragged, or moved with the arrow keys, to resize the panels on either
+//! side. Groups nest: a panel can hold another group with the other
+//! orientation.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     ResizablePanelGroup { on_layout_change: move |layout| save(layout),
+//!         ResizablePanel { default_size: PanelSize::Percent(25.0), min_size: PanelSize::Pixels(160.0), "Sidebar" }
+//!         ResizableHandle {}
+//!         ResizablePanel { "Editor" }
+//!     }
+//! }
+//! ```
+//!
+//! Sizes are kept in percent of the group. Panels without a `default_size`
+//! share the space the others leave.
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_move::{MovePhase, use_move};
+use super::hooks::use_size::use_size;
+use super::roving_focus::Orientation;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A panel size, in percent of the group or in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PanelSize {
+    Percent(f64),
+    Pixels(f64),
+}
+
+impl PanelSize {
+    fn to_percent(self, group: f64) -> f64 {
+        match self {
+            PanelSize::Percent(percent) => percent,
+            PanelSize::Pixels(_) if group <= 0.0 => 0.0,
+            PanelSize::Pixels(pixels) => pixels / group * 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Constraints {
+    default_size: Option<PanelSize>,
+    min_size: Option<PanelSize>,
+    max_size: Option<PanelSize>,
+}
+
+impl Constraints {
+    fn clamp(&self, percent: f64, group: f64) -> f64 {
+        let min = self.min_size.map_or(0.0, |size| size.to_percent(group));
+        let max = self.max_size.map_or(100.0, |size| size.to_percent(group));
+        percent.min(max).max(min)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum ItemKind {
+    Panel(Constraints),
+    Handle,
+}
+
+#[derive(Clone)]
+struct GroupItem {
+    id: usize,
+    kind: ItemKind,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Clone, Copy)]
+struct ResizableContext {
+    orientation: Signal<Orientation>,
+    items: Signal<Vec<GroupItem>>,
+    // 每個面板的大小（百分比），以面板的 id 對應
+    layout: Signal<Vec<(usize, f64)>>,
+    // 群組在拖曳方向上的長度，用來換算像素
+    length: Memo<f64>,
+    on_layout_change: Callback<Vec<f64>>,
+}
+
+impl ResizableContext {
+    // 依文件順序排列，面板與手把可能在掛載後移動
+    fn ordered_items(&self) -> Vec<GroupItem> {
+        let items = self.items.peek().clone();
+        #[cfg(feature = "web")]
+        let items = {
+            let mut items = items;
+            items.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => super::dom::document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            items
+        };
+        items
+    }
+
+    fn size_of(&self, panel: usize) -> Option<f64> {
+        self.layout
+            .read()
+            .iter()
+            .find(|(id, _)| *id == panel)
+            .map(|(_, size)| *size)
+    }
+
+    // 手把兩側的面板
+    fn neighbors(&self, handle: usize) -> Option<((usize, Constraints), (usize, Constraints))> {
+        let items = self.ordered_items();
+        let position = items.iter().position(|item| item.id == handle)?;
+        let panel = |item: &GroupItem| match item.kind {
+            ItemKind::Panel(constraints) => Some((item.id, constraints)),
+            ItemKind::Handle => None,
+        };
+        let before = items[..position].iter().rev().find_map(panel)?;
+        let after = items[position + 1..].iter().find_map(panel)?;
+        Some((before, after))
+    }
+
+    /// Grow the panel before `handle` by `delta` percent and shrink the one
+    /// after it, within both panels' limits.
+    fn resize(&mut self, handle: usize, delta: f64) {
+        let Some(((before, before_limits), (after, after_limits))) = self.neighbors(handle) else {
+            return;
+        };
+        let group = *self.length.peek();
+        let mut layout = self.layout.peek().clone();
+        let size = |id: usize| {
+            layout
+                .iter()
+                .find(|(other, _)| *other == id)
+                .map_or(0.0, |(_, size)| *size)
+        };
+        let (a, b) = (size(before), size(after));
+        let grown = before_limits.clamp(a + delta, group);
+        let shrunk = after_limits.clamp(b - (grown - a), group);
+        let moved = b - shrunk;
+        if moved == 0.0 {
+            return;
+        }
+        for (id, size) in layout.iter_mut() {
+            if *id == before {
+                *size = a + moved;
+            } else if *id == after {
+                *size = shrunk;
+            }
+        }
+        self.layout.set(layout);
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let layout = self.layout.peek();
+        let sizes = self
+            .ordered_items()
+            .iter()
+            .filter_map(|item| {
+                layout
+                    .iter()
+                    .find(|(id, _)| *id == item.id)
+                    .map(|(_, size)| *size)
+            })
+            .collect();
+        drop(layout);
+        self.on_layout_change.call(sizes);
+    }
+}
+
+// 新的面板使用預設大小，沒有預設的平分剩下的空間，最後縮放成總和 100
+fn reconcile(items: &[GroupItem], layout: &[(usize, f64)], group: f64) -> Vec<(usize, f64)> {
+    let panels: Vec<(usize, Constraints)> = items
+        .iter()
+        .filter_map(|item| match item.kind {
+            ItemKind::Panel(constraints) => Some((item.id, constraints)),
+            ItemKind::Handle => None,
+        })
+        .collect();
+    let known: Vec<Option<f64>> = panels
+        .iter()
+        .map(|(id, constraints)| {
+            layout
+                .iter()
+                .find(|(other, _)| other == id)
+                .map(|(_, size)| *size)
+                .or_else(|| constraints.default_size.map(|size| size.to_percent(group)))
+        })
+        .collect();
+    let taken: f64 = known.iter().flatten().sum();
+    let unset = known.iter().filter(|size| size.is_none()).count();
+    let share = if unset > 0 {
+        ((100.0 - taken) / unset as f64).max(0.0)
+    } else {
+        0.0
+    };
+    let mut sizes: Vec<f64> = panels
+        .iter()
+        .zip(&known)
+        .map(|((_, constraints), size)| constraints.clamp(size.unwrap_or(share), group))
+        .collect();
+    let total: f64 = sizes.iter().sum();
+    if total > 0.0 {
+        for size in sizes.iter_mut() {
+            *size *= 100.0 / total;
+        }
+    }
+    panels.iter().map(|(id, _)| *id).zip(sizes).collect()
+}
+
+/// A row or column of [`ResizablePanel`]s separated by
+/// [`ResizableHandle`]s.
+///
+/// `on_layout_change` is called with the panel sizes, in percent and in
+/// document order, whenever a handle resizes them; pass them back as the
+/// panels' `default_size` to restore a saved layout.
+#[component]
+pub fn ResizablePanelGroup(
+    /// `Horizontal` puts the panels side by side.
+    #[props(default)]
+    orientation: Orientation,
+    on_layout_change: Option<EventHandler<Vec<f64>>>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let orientation_signal = use_synced_signal(orientation);
+    let mut node = use_signal(|| None);
+    let size = use_size(node);
+    let length = use_memo(move || {
+        let size = size().unwrap_or_default();
+        match orientation_signal() {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        }
+    });
+    let on_layout_change = use_callback(move |layout: Vec<f64>| {
+        if let Some(handler) = on_layout_change {
+            handler.call(layout);
+        }
+    });
+    let ctx = use_context_provider(|| ResizableContext {
+        orientation: orientation_signal,
+        items: Signal::new(Vec::new()),
+        layout: Signal::new(Vec::new()),
+        length,
+        on_layout_change,
+    });
+
+    // 面板增減時重新分配大小
+    let mut layout = ctx.layout;
+    use_effect(move || {
+        let _ = ctx.items.read();
+        let group = length();
+        let next = reconcile(&ctx.ordered_items(), &layout.peek(), group);
+        if *layout.peek() != next {
+            layout.set(next);
+        }
+    });
+
+    let direction = match orientation {
+        Orientation::Horizontal => "row",
+        Orientation::Vertical => "column",
+    };
+    let own_attributes = vec![
+        Attribute::new(
+            "style",
+            format!("display: flex; flex-direction: {direction}; overflow: hidden;"),
+            None,
+            false,
+        ),
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+fn use_group_item(kind: ItemKind) -> (ResizableContext, usize, Signal<Option<Rc<MountedData>>>) {
+    let mut ctx = use_context::<ResizableContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let node = use_signal(|| None);
+    let kind = use_synced_signal(kind);
+
+    use_effect(move || {
+        let item = GroupItem {
+            id,
+            kind: kind(),
+            node: node(),
+        };
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|other| other.id == id) {
+            Some(existing) => *existing = item,
+            None => items.push(item),
+        }
+    });
+    use_drop(move || {
+        ctx.items.write().retain(|item| item.id != id);
+        ctx.layout.write().retain(|(panel, _)| *panel != id);
+    });
+
+    (ctx, id, node)
+}
+
+/// A panel of a [`ResizablePanelGroup`].
+///
+/// `min_size` and `max_size` limit how far handles resize it.
+#[component]
+pub fn ResizablePanel(
+    default_size: Option<PanelSize>,
+    min_size: Option<PanelSize>,
+    max_size: Option<PanelSize>,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (ctx, id, mut node) = use_group_item(ItemKind::Panel(Constraints {
+        default_size,
+        min_size,
+        max_size,
+    }));
+    let size = ctx.size_of(id);
+
+    // flex-grow 依百分比分配；沒有量到大小前平分
+    let flex = size.map_or_else(|| "1".to_string(), |size| size.to_string());
+    let own_attributes = vec![
+        Attribute::new(
+            "style",
+            format!("flex: {flex} 1 0px; overflow: hidden;"),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "data-panel-size",
+            size.map(|size| format!("{size:.1}")),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The handle between two panels, a focusable `separator`.
+///
+/// Drag it, or use the arrow keys along the group's orientation to move
+/// it by `keyboard_step` percent; Home and End move it as far as the
+/// panels allow. It is `data-state="dragging"` while dragged.
+#[component]
+pub fn ResizableHandle(
+    #[props(default = 10.0)] keyboard_step: f64,
+    #[props(default)] disabled: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (mut ctx, id, mut node) = use_group_item(ItemKind::Handle);
+    let mut dragging = use_signal(|| false);
+    let move_attributes = use_move(move |event| match event.phase {
+        MovePhase::Start => dragging.set(event.pointer_type != "keyboard"),
+        MovePhase::End => dragging.set(false),
+        MovePhase::Move => {
+            let delta = match *ctx.orientation.peek() {
+                Orientation::Horizontal => event.delta_x,
+                Orientation::Vertical => event.delta_y,
+            };
+            let percent = if event.pointer_type == "keyboard" {
+                delta * keyboard_step
+            } else {
+                let length = *ctx.length.peek();
+                if length <= 0.0 {
+                    return;
+                }
+                delta / length * 100.0
+            };
+            ctx.resize(id, percent);
+        }
+    });
+
+    let orientation = (ctx.orientation)();
+    let _ = ctx.items.read();
+    let neighbors = ctx.neighbors(id);
+    let before = neighbors.map(|((before, limits), _)| (ctx.size_of(before), limits));
+    let group = (ctx.length)();
+
+    let mut own_attributes = vec![
+        Attribute::new("role", "separator", None, false),
+        Attribute::new("tabindex", if disabled { "-1" } else { "0" }, None, false),
+        // 分隔線的方向與群組相反
+        Attribute::new(
+            "aria-orientation",
+            match orientation {
+                Orientation::Horizontal => "vertical",
+                Orientation::Vertical => "horizontal",
+            },
+            None,
+            false,
+        ),
+        Attribute::new(
+            "aria-valuenow",
+            before.and_then(|(size, _)| size).map(|size| size.round()),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "aria-valuemin",
+            before.map(|(_, limits)| limits.clamp(0.0, group).round()),
+            None,
+            false,
+        ),
+        Attribute::new(
+            "aria-valuemax",
+            before.map(|(_, limits)| limits.clamp(100.0, group).round()),
+            None,
+            false,
+        ),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new(
+            "style",
+            match orientation {
+                Orientation::Horizontal => "cursor: col-resize; touch-action: none;",
+                Orientation::Vertical => "cursor: row-resize; touch-action: none;",
+            },
+            None,
+            false,
+        ),
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        Attribute::new(
+            "data-state",
+            if dragging() { "dragging" } else { "inactive" },
+            None,
+            false,
+        ),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ];
+    if !disabled {
+        own_attributes.extend(move_attributes);
+        own_attributes.push(dioxus_elements::events::onkeydown(
+            move |event: KeyboardEvent| {
+                let delta = match event.key() {
+                    Key::Home => -100.0,
+                    Key::End => 100.0,
+                    _ => return,
+                };
+                event.prevent_default();
+                ctx.resize(id, delta);
+            },
+        ));
+    }
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! PrimitivesRoot Primitive
+//!
+//! Namespaces everything the primitives generate for one Dioxus root. When
+//! several independent roots share a page, e.g. micro-frontends or embedded
+//! widgets, each one would otherwise number its element ids from the start
+//! and portal into the same `document.body`. Wrap each root's app in a
+//! [`PrimitivesRoot`] with its own `id_prefix` to keep them apart.
+
+use dioxus::prelude::*;
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct RootContext {
+    pub(crate) id_prefix: String,
+}
+
+impl RootContext {
+    /// The id of the element [`Portal`](super::portal::Portal)s of this
+    /// root render into.
+    pub(crate) fn portal_container_id(&self) -> String {
+        format!("{}-portals", self.id_prefix)
+    }
+}
+
+/// Provides the id namespace for the primitives rendered inside it.
+///
+/// Ids from [`use_id`](super::hooks::use_id::use_id) start with `id_prefix`,
+/// and portals render into a container element owned by this root rather
+/// than directly into `document.body`.
+#[component]
+pub fn PrimitivesRoot(
+    /// Must be unique on the page and valid in an HTML id, e.g. `"checkout"`.
+    id_prefix: String,
+    children: Element,
+) -> Element {
+    // 前綴決定了已產生的 id，之後改變也不會生效
+    let ctx = use_context_provider(|| RootContext { id_prefix });
+
+    rsx! {
+        {children}
+        div {
+            id: ctx.portal_container_id(),
+            "data-primitive-portal-root": "",
+        }
+    }
+}
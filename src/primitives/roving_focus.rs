@@ -0,0 +1,282 @@
+//! RovingFocusGroup Primitive
+//!
+//! Implements the roving tabindex pattern shared by toolbars, radio groups,
+//! tabs and menus: the group is a single Tab stop, and the arrow keys move
+//! focus between its items.
+
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::hooks::use_focus_visible::use_focus_visible;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The axis along which arrow keys move focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    /// The value of the `data-orientation` and `aria-orientation` attributes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Orientation::Horizontal => "horizontal",
+            Orientation::Vertical => "vertical",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RovingItem {
+    id: usize,
+    focusable: bool,
+    node: Rc<MountedData>,
+}
+
+#[derive(Clone, Copy)]
+struct RovingFocusContext {
+    orientation: Signal<Option<Orientation>>,
+    dir: Signal<Direction>,
+    loop_focus: Signal<bool>,
+    // 目前的 Tab 停駐點，尚未有項目被聚焦時為 None
+    current_tab_stop: Signal<Option<usize>>,
+    items: Signal<Vec<RovingItem>>,
+}
+
+impl RovingFocusContext {
+    // 可聚焦的項目，依文件順序排列
+    fn focusable_items(&self) -> Vec<RovingItem> {
+        let mut items: Vec<RovingItem> = self
+            .items
+            .peek()
+            .iter()
+            .filter(|item| item.focusable)
+            .cloned()
+            .collect();
+        #[cfg(feature = "web")]
+        sort_by_document_position(&mut items);
+        items
+    }
+}
+
+#[cfg(feature = "web")]
+fn sort_by_document_position(items: &mut [RovingItem]) {
+    items.sort_by(|a, b| super::dom::document_order(&a.node, &b.node));
+}
+
+fn focus_node(node: Rc<MountedData>) {
+    spawn(async move {
+        let _ = node.set_focus(true).await;
+    });
+}
+
+/// A group whose [`RovingFocusItem`]s share a single Tab stop.
+///
+/// Tabbing into the group focuses the active item, or else the item that was
+/// focused last, or else the first focusable item.
+#[component]
+pub fn RovingFocusGroup(
+    /// Restrict arrow-key navigation to one axis. Both axes are used when
+    /// `None`.
+    orientation: Option<Orientation>,
+    /// Wrap around from the last item to the first and vice versa.
+    #[props(default)]
+    loop_focus: bool,
+    /// Defaults to the direction from [`use_direction`].
+    dir: Option<Direction>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let inherited_dir = use_direction();
+    let dir = dir.unwrap_or(inherited_dir);
+    let orientation_signal = use_synced_signal(orientation);
+    let dir_signal = use_synced_signal(dir);
+    let loop_signal = use_synced_signal(loop_focus);
+    let ctx = use_context_provider(|| RovingFocusContext {
+        orientation: orientation_signal,
+        dir: dir_signal,
+        loop_focus: loop_signal,
+        current_tab_stop: Signal::new(None),
+        items: Signal::new(Vec::new()),
+    });
+
+    let has_focusable = ctx.items.read().iter().any(|item| item.focusable);
+    // 已有停駐點時由該項目接收 Tab，否則由群組接收後轉交
+    let tabindex = if ctx.current_tab_stop.read().is_none() && has_focusable {
+        "0"
+    } else {
+        "-1"
+    };
+
+    rsx! {
+        div {
+            tabindex,
+            dir: dir.as_str(),
+            "data-orientation": orientation.map(|orientation| orientation.as_str()),
+            onfocus: move |_| {
+                // 項目的 focus 事件也會冒泡到這裡，只處理群組本身被聚焦的情況
+                if ctx.current_tab_stop.peek().is_some() {
+                    return;
+                }
+                if let Some(item) = ctx.focusable_items().first() {
+                    focus_node(item.node.clone());
+                }
+            },
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+enum FocusIntent {
+    First,
+    Last,
+    Previous,
+    Next,
+}
+
+fn focus_intent(
+    key: &Key,
+    orientation: Option<Orientation>,
+    dir: Direction,
+) -> Option<FocusIntent> {
+    // 從右到左的排版中，左右方向鍵的意義相反
+    let key = match (key, dir) {
+        (Key::ArrowLeft, Direction::Rtl) => Key::ArrowRight,
+        (Key::ArrowRight, Direction::Rtl) => Key::ArrowLeft,
+        (key, _) => key.clone(),
+    };
+
+    match (key, orientation) {
+        (Key::ArrowLeft | Key::ArrowRight, Some(Orientation::Vertical)) => None,
+        (Key::ArrowUp | Key::ArrowDown, Some(Orientation::Horizontal)) => None,
+        (Key::ArrowLeft | Key::ArrowUp, _) => Some(FocusIntent::Previous),
+        (Key::ArrowRight | Key::ArrowDown, _) => Some(FocusIntent::Next),
+        (Key::Home | Key::PageUp, _) => Some(FocusIntent::First),
+        (Key::End | Key::PageDown, _) => Some(FocusIntent::Last),
+        _ => None,
+    }
+}
+
+static NEXT_ITEM_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// An item of a [`RovingFocusGroup`].
+///
+/// Must be rendered inside a `RovingFocusGroup`.
+#[component]
+pub fn RovingFocusItem(
+    /// Whether the item takes part in keyboard navigation.
+    #[props(default = true)]
+    focusable: bool,
+    /// Focus this item when focus enters the group, e.g. the checked radio.
+    #[props(default)]
+    active: bool,
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<RovingFocusContext>();
+    let id = use_hook(|| NEXT_ITEM_ID.fetch_add(1, Ordering::Relaxed));
+
+    use_effect(use_reactive!(|(focusable,)| {
+        if let Some(item) = ctx.items.write().iter_mut().find(|item| item.id == id) {
+            item.focusable = focusable;
+        }
+    }));
+
+    // 進入群組時優先聚焦 active 的項目
+    use_effect(use_reactive!(|(active,)| {
+        if active && focusable && ctx.current_tab_stop.peek().is_none_or(|stop| stop != id) {
+            ctx.current_tab_stop.set(Some(id));
+        }
+    }));
+
+    use_drop(move || {
+        ctx.items.write().retain(|item| item.id != id);
+        if *ctx.current_tab_stop.peek() == Some(id) {
+            ctx.current_tab_stop.set(None);
+        }
+    });
+
+    let focus_visible = use_focus_visible();
+
+    let is_current = *ctx.current_tab_stop.read() == Some(id);
+    let tabindex = if is_current { "0" } else { "-1" };
+    let orientation = *ctx.orientation.read();
+
+    let mut own_attributes = focus_visible.attributes;
+    own_attributes.extend([
+        Attribute::new("tabindex", tabindex, None, false),
+        Attribute::new(
+            "data-orientation",
+            orientation.map(|orientation| orientation.as_str()),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            ctx.items.write().push(RovingItem {
+                id,
+                focusable,
+                node: event.data(),
+            });
+        }),
+        dioxus_elements::events::onmousedown(move |event: MouseEvent| {
+            if focusable {
+                ctx.current_tab_stop.set(Some(id));
+            } else {
+                // 不可聚焦的項目不應在點擊時取得焦點
+                event.prevent_default();
+            }
+        }),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            if focusable {
+                ctx.current_tab_stop.set(Some(id));
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let modifiers = event.modifiers();
+            if modifiers.alt() || modifiers.ctrl() || modifiers.meta() || modifiers.shift() {
+                return;
+            }
+            let Some(intent) = focus_intent(&event.key(), *ctx.orientation.peek(), *ctx.dir.peek())
+            else {
+                return;
+            };
+            event.prevent_default();
+
+            let items = ctx.focusable_items();
+            let len = items.len();
+            let current = items.iter().position(|item| item.id == id);
+            let target = match (intent, current) {
+                (FocusIntent::First, _) => Some(0),
+                (FocusIntent::Last, _) => len.checked_sub(1),
+                (FocusIntent::Next, Some(index)) if index + 1 < len => Some(index + 1),
+                (FocusIntent::Next, Some(_)) if *ctx.loop_focus.peek() => Some(0),
+                (FocusIntent::Previous, Some(index)) if index > 0 => Some(index - 1),
+                (FocusIntent::Previous, Some(_)) if *ctx.loop_focus.peek() => len.checked_sub(1),
+                _ => None,
+            };
+
+            if let Some(item) = target.and_then(|index| items.get(index)) {
+                focus_node(item.node.clone());
+            }
+        }),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! ScrollLock Primitive
+//!
+//! Prevents the page behind a modal overlay from scrolling. While a lock is
+//! held the body gets `overflow: hidden`, its right padding grows by the
+//! width of the removed scrollbar so the layout does not shift, and touch
+//! scrolling outside the topmost [`ScrollLock`] is cancelled for iOS Safari,
+//! which ignores `overflow` on the body.
+//!
+//! Locks are reference counted, so nested and stacked overlays can each hold
+//! one: the page is restored when the last lock is released. Locking
+//! requires the `web` feature; elsewhere this is a no-op.
+
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Lock page scrolling while `locked` is true and the calling component is
+/// mounted.
+pub fn use_scroll_lock(locked: bool) {
+    use_lock(locked, None);
+}
+
+fn use_lock(locked: bool, container: Option<Signal<Option<Rc<MountedData>>>>) {
+    #[cfg(feature = "web")]
+    {
+        let id = use_hook(web::next_id);
+        use_effect(use_reactive!(|(locked,)| {
+            if locked {
+                web::lock(id);
+            } else {
+                web::unlock(id);
+            }
+        }));
+        use_effect(move || {
+            let element = container.and_then(|container| {
+                container
+                    .read()
+                    .as_deref()
+                    .and_then(super::dom::web_element)
+            });
+            web::set_container(id, element);
+        });
+        use_drop(move || web::remove(id));
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = (locked, container);
+}
+
+/// Locks page scrolling while rendered, except inside its own content, which
+/// can still be scrolled by touch.
+#[component]
+pub fn ScrollLock(
+    /// Release the lock without unmounting, e.g. for a non-modal overlay.
+    #[props(default = true)]
+    enabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    use_lock(enabled, Some(container));
+
+    let own_attributes = vec![dioxus_elements::events::onmounted(
+        move |event: MountedEvent| container.set(Some(event.data())),
+    )];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+mod web {
+    use crate::primitives::dom::{EventListener, contains, document};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+
+    const LOCKED_ATTRIBUTE: &str = "data-scroll-locked";
+
+    // 鎖定前 body 的 inline style，解除時還原
+    struct Restore {
+        overflow: String,
+        padding_right: String,
+    }
+
+    #[derive(Default)]
+    struct LockState {
+        // 依鎖定順序排列，最後一個是最上層
+        locks: Vec<usize>,
+        containers: HashMap<usize, web_sys::Element>,
+        restore: Option<Restore>,
+        touch_listener: Option<EventListener>,
+    }
+
+    thread_local! {
+        static STATE: RefCell<LockState> = RefCell::default();
+        static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub(super) fn next_id() -> usize {
+        NEXT_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        })
+    }
+
+    pub(super) fn lock(id: usize) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            if state.locks.contains(&id) {
+                return;
+            }
+            state.locks.push(id);
+            if state.locks.len() == 1 {
+                apply(&mut state);
+            }
+        });
+    }
+
+    pub(super) fn unlock(id: usize) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let Some(index) = state.locks.iter().position(|lock| *lock == id) else {
+                return;
+            };
+            state.locks.remove(index);
+            if state.locks.is_empty() {
+                restore(&mut state);
+            }
+        });
+    }
+
+    pub(super) fn set_container(id: usize, element: Option<web_sys::Element>) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            match element {
+                Some(element) => state.containers.insert(id, element),
+                None => state.containers.remove(&id),
+            };
+        });
+    }
+
+    pub(super) fn remove(id: usize) {
+        unlock(id);
+        set_container(id, None);
+    }
+
+    fn px(value: &str) -> f64 {
+        value.trim().trim_end_matches("px").parse().unwrap_or(0.0)
+    }
+
+    fn apply(state: &mut LockState) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Some(document) = document() else {
+            return;
+        };
+        let (Some(body), Some(root)) = (document.body(), document.document_element()) else {
+            return;
+        };
+
+        // 捲軸寬度 = 視窗寬度 - 根元素的內容寬度
+        let scrollbar_width = window
+            .inner_width()
+            .ok()
+            .and_then(|width| width.as_f64())
+            .map_or(0.0, |width| (width - root.client_width() as f64).max(0.0));
+
+        let style = body.style();
+        state.restore = Some(Restore {
+            overflow: style.get_property_value("overflow").unwrap_or_default(),
+            padding_right: style
+                .get_property_value("padding-right")
+                .unwrap_or_default(),
+        });
+        if scrollbar_width > 0.0 {
+            let padding = window
+                .get_computed_style(&body)
+                .ok()
+                .flatten()
+                .and_then(|computed| computed.get_property_value("padding-right").ok())
+                .map_or(0.0, |padding| px(&padding));
+            let _ =
+                style.set_property("padding-right", &format!("{}px", padding + scrollbar_width));
+        }
+        let _ = style.set_property("overflow", "hidden");
+        let _ = body.set_attribute(LOCKED_ATTRIBUTE, "");
+
+        // iOS Safari 不理會 body 的 overflow，必須直接取消 touchmove
+        state.touch_listener = Some(EventListener::non_passive(
+            &document,
+            "touchmove",
+            |event| {
+                let allowed = STATE.with(|state| {
+                    let state = state.borrow();
+                    let container = state.locks.last().and_then(|id| state.containers.get(id));
+                    match (container, event.target()) {
+                        (Some(container), Some(target)) => contains(container, &target),
+                        _ => false,
+                    }
+                });
+                if !allowed && event.cancelable() {
+                    event.prevent_default();
+                }
+            },
+        ));
+    }
+
+    fn restore(state: &mut LockState) {
+        state.touch_listener = None;
+        let Some(restore) = state.restore.take() else {
+            return;
+        };
+        let Some(body) = document().and_then(|document| document.body()) else {
+            return;
+        };
+        let style = body.style();
+        for (property, value) in [
+            ("overflow", restore.overflow),
+            ("padding-right", restore.padding_right),
+        ] {
+            if value.is_empty() {
+                let _ = style.remove_property(property);
+            } else {
+                let _ = style.set_property(property, &value);
+            }
+        }
+        let _ = body.remove_attribute(LOCKED_ATTRIBUTE);
+    }
+}
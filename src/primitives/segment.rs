@@ -0,0 +1,107 @@
+//! Helpers for inputs split into editable segments, e.g. the year, month and
+//! day of a date, each focusable and edited with digits or the arrow keys.
+
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// The focusable segments of one field, ordered by id whatever order they
+/// are mounted in, so segments shown later still take their place.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct SegmentFocus {
+    nodes: Signal<Vec<(usize, Rc<MountedData>)>>,
+}
+
+impl SegmentFocus {
+    pub(crate) fn register(&mut self, id: usize, node: Rc<MountedData>) {
+        let mut nodes = self.nodes.write();
+        nodes.retain(|(other, _)| *other != id);
+        let index = nodes.partition_point(|(other, _)| *other < id);
+        nodes.insert(index, (id, node));
+    }
+
+    pub(crate) fn unregister(&mut self, id: usize) {
+        self.nodes.write().retain(|(other, _)| *other != id);
+    }
+
+    /// Focus the segment `offset` places after `id`, if there is one.
+    pub(crate) fn focus_relative(&self, id: usize, offset: isize) {
+        let node = {
+            let nodes = self.nodes.peek();
+            nodes
+                .iter()
+                .position(|(other, _)| *other == id)
+                .and_then(|index| index.checked_add_signed(offset))
+                .and_then(|index| nodes.get(index))
+                .map(|(_, node)| node.clone())
+        };
+        focus_node(node);
+    }
+
+    pub(crate) fn focus(&self, id: usize) {
+        let node = {
+            let nodes = self.nodes.peek();
+            nodes
+                .iter()
+                .find(|(other, _)| *other == id)
+                .map(|(_, node)| node.clone())
+        };
+        focus_node(node);
+    }
+
+    pub(crate) fn focus_first(&self) {
+        focus_node(self.nodes.peek().first().map(|(_, node)| node.clone()));
+    }
+}
+
+fn focus_node(node: Option<Rc<MountedData>>) {
+    if let Some(node) = node {
+        spawn(async move {
+            let _ = node.set_focus(true).await;
+        });
+    }
+}
+
+pub(crate) fn use_segment_focus() -> SegmentFocus {
+    SegmentFocus {
+        nodes: use_signal(Vec::new),
+    }
+}
+
+/// Step `value` by `delta` within `min..=max`, wrapping around. An empty
+/// segment starts at `min` going up and at `max` going down.
+pub(crate) fn step(value: Option<u32>, delta: i32, min: u32, max: u32) -> u32 {
+    let Some(value) = value else {
+        return if delta >= 0 { min } else { max };
+    };
+    let span = (max - min + 1) as i64;
+    let offset = (value as i64 - min as i64 + delta as i64).rem_euclid(span);
+    min + offset as u32
+}
+
+/// The result of typing a digit into a numeric segment.
+pub(crate) struct TypedDigit {
+    pub(crate) value: u32,
+    /// The digits typed so far, to continue from with the next digit.
+    pub(crate) buffer: String,
+    /// No further digit could make a valid value; move to the next segment.
+    pub(crate) complete: bool,
+}
+
+/// Add `digit` to the digits typed so far into a segment with values up to
+/// `max`. A digit that would overflow starts a new value.
+pub(crate) fn type_digit(buffer: &str, digit: char, max: u32) -> Option<TypedDigit> {
+    digit.to_digit(10)?;
+    let mut buffer = format!("{buffer}{digit}");
+    let mut value: u32 = buffer.parse().ok()?;
+    if value > max {
+        buffer = digit.to_string();
+        value = digit.to_digit(10)?;
+    }
+    let max_digits = max.to_string().len();
+    let complete = buffer.len() >= max_digits || value * 10 > max;
+    Some(TypedDigit {
+        value,
+        buffer,
+        complete,
+    })
+}
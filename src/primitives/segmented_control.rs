@@ -0,0 +1,235 @@
+//! SegmentedControl Primitive
+//!
+//! A row of buttons of which exactly one is selected, like a set of radio
+//! buttons. The root measures the selected item and exposes its position
+//! and size, relative to the root, as CSS variables for a sliding
+//! indicator:
+//!
+//! - `--primitive-segmented-control-indicator-left` and `-top`
+//! - `--primitive-segmented-control-indicator-width` and `-height`
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     SegmentedControl { default_value: "week", style: "position: relative",
+//!         SegmentedControlIndicator { class: "indicator" }
+//!         SegmentedControlItem { value: "day", "Day" }
+//!         SegmentedControlItem { value: "week", "Week" }
+//!         SegmentedControlItem { value: "month", "Month" }
+//!     }
+//! }
+//! ```
+//!
+//! ```css
+//! .indicator { transition: left 200ms, width 200ms; }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_rect::use_rect;
+use super::roving_focus::{Orientation, RovingFocusGroup, RovingFocusItem};
+use super::slot::{AsChild, merge_attributes};
+use super::toggle::toggle_attributes;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+#[derive(Clone, Copy)]
+struct SegmentedControlContext {
+    value: Memo<String>,
+    set_value: Callback<String>,
+    disabled: Signal<bool>,
+    selected_node: Signal<Option<Rc<MountedData>>>,
+}
+
+/// The root of a segmented control, owning the selected value.
+///
+/// Pass `value` and `on_value_change` to control the selection, or
+/// `default_value` to let the control manage it. The arrow keys move
+/// between the items and select the focused one.
+#[component]
+pub fn SegmentedControl(
+    value: Option<String>,
+    #[props(default)] default_value: String,
+    on_value_change: Option<EventHandler<String>>,
+    #[props(default)] orientation: Orientation,
+    /// Defaults to the direction from [`use_direction`].
+    dir: Option<Direction>,
+    /// Wrap around from the last item to the first and vice versa.
+    #[props(default = true)]
+    loop_focus: bool,
+    #[props(default)] disabled: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let inherited_dir = use_direction();
+    let dir = dir.unwrap_or(inherited_dir);
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let disabled_signal = use_synced_signal(disabled);
+    let ctx = use_context_provider(|| SegmentedControlContext {
+        value,
+        set_value,
+        disabled: disabled_signal,
+        selected_node: Signal::new(None),
+    });
+    let mut root = use_signal(|| None);
+    let root_rect = use_rect(root);
+    let selected_rect = use_rect(ctx.selected_node);
+
+    // 以根元素為原點的座標
+    let indicator = match (root_rect(), selected_rect()) {
+        (Some(root), Some(selected)) => Some(format!(
+            "--primitive-segmented-control-indicator-left: {}px; \
+             --primitive-segmented-control-indicator-top: {}px; \
+             --primitive-segmented-control-indicator-width: {}px; \
+             --primitive-segmented-control-indicator-height: {}px;",
+            selected.x - root.x,
+            selected.y - root.y,
+            selected.width,
+            selected.height,
+        )),
+        _ => None,
+    };
+
+    let own_attributes = vec![
+        Attribute::new("role", "radiogroup", None, false),
+        Attribute::new("aria-orientation", orientation.as_str(), None, false),
+        Attribute::new("aria-disabled", disabled.then_some("true"), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("style", indicator, None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            root.set(Some(event.data()));
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        RovingFocusGroup {
+            orientation,
+            loop_focus,
+            dir,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// An option of the segmented control, with `role="radio"`.
+#[component]
+pub fn SegmentedControlItem(
+    value: String,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<SegmentedControlContext>();
+    let selected = *ctx.value.read() == value;
+    let disabled = disabled || (ctx.disabled)();
+    let mut node = use_signal(|| None);
+
+    // 被選取的項目提供量測用的節點
+    use_effect(use_reactive!(|(selected,)| {
+        let node = node();
+        if selected && node.is_some() {
+            ctx.selected_node.set(node);
+        }
+    }));
+    use_drop(move || {
+        let current = ctx.selected_node.peek().clone();
+        let mine = node.peek().clone();
+        if let (Some(current), Some(mine)) = (current, mine) {
+            if Rc::ptr_eq(&current, &mine) {
+                ctx.selected_node.set(None);
+            }
+        }
+    });
+
+    let select_value = value.clone();
+    let select = use_callback(move |pressed: bool| {
+        if pressed {
+            ctx.set_value.call(select_value.clone());
+        }
+    });
+    let focus_value = value.clone();
+
+    let mut own_attributes = toggle_attributes(selected, disabled, select);
+    own_attributes.extend([
+        Attribute::new("role", "radio", None, false),
+        Attribute::new("aria-checked", selected, None, false),
+        Attribute::new("data-value", value, None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        // 以方向鍵移動焦點時一併選取
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            if !disabled && *ctx.value.peek() != focus_value {
+                ctx.set_value.call(focus_value.clone());
+            }
+        }),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        RovingFocusItem {
+            focusable: !disabled,
+            active: selected,
+            as_child: move |item: Vec<Attribute>| {
+                let attributes = merge_attributes(item, attributes.clone());
+                if let Some(as_child) = as_child {
+                    return as_child.call(attributes);
+                }
+                rsx! {
+                    button {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// An element placed over the selected item, for a sliding highlight.
+///
+/// It is absolutely positioned from the CSS variables of the root, so give
+/// the root `position: relative`. It is not rendered until the selected
+/// item has been measured.
+#[component]
+pub fn SegmentedControlIndicator(
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<SegmentedControlContext>();
+    if ctx.selected_node.read().is_none() {
+        return rsx! {};
+    }
+
+    let own_attributes = vec![
+        Attribute::new("aria-hidden", "true", None, false),
+        Attribute::new(
+            "style",
+            "position: absolute; \
+             left: var(--primitive-segmented-control-indicator-left); \
+             top: var(--primitive-segmented-control-indicator-top); \
+             width: var(--primitive-segmented-control-indicator-width); \
+             height: var(--primitive-segmented-control-indicator-height);",
+            None,
+            false,
+        ),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Slot Primitive
+//!
+//! Lets a primitive part render the user's own element instead of its
+//! default one. A Dioxus element cannot be changed once it is built, so
+//! instead of merging props into a child like Radix's `asChild`, parts accept
+//! an `as_child` callback that receives the part's attributes and event
+//! handlers and spreads them onto the element it returns:
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     RovingFocusItem {
+//!         as_child: move |attributes: Vec<Attribute>| rsx! {
+//!             a { href: "/settings", ..attributes, "Settings" }
+//!         },
+//!     }
+//! }
+//! ```
+
+use dioxus::dioxus_core::AttributeValue;
+use dioxus::prelude::*;
+
+/// Renders a part onto the user's element; see the [module docs](self).
+pub type AsChild = Callback<Vec<Attribute>, Element>;
+
+/// Merge the user's attributes `theirs` into a part's attributes `ours`.
+///
+/// The user's value wins for plain attributes, `class` values are joined and
+/// `style` declarations are appended. Event handlers from both sides are
+/// kept, so both run.
+pub fn merge_attributes(ours: Vec<Attribute>, theirs: Vec<Attribute>) -> Vec<Attribute> {
+    let mut merged = ours;
+
+    for attribute in theirs {
+        if matches!(attribute.value, AttributeValue::Listener(_)) {
+            merged.push(attribute);
+            continue;
+        }
+
+        let existing = merged.iter_mut().find(|existing| {
+            existing.name == attribute.name
+                && existing.namespace == attribute.namespace
+                && !matches!(existing.value, AttributeValue::Listener(_))
+        });
+        let Some(existing) = existing else {
+            merged.push(attribute);
+            continue;
+        };
+
+        let separator = match (attribute.name, attribute.namespace) {
+            ("class", None) => Some(" "),
+            ("style", None) => Some("; "),
+            _ => None,
+        };
+        match (separator, &existing.value, &attribute.value) {
+            (Some(separator), AttributeValue::Text(ours), AttributeValue::Text(theirs)) => {
+                existing.value = AttributeValue::Text(format!("{ours}{separator}{theirs}"));
+            }
+            _ => *existing = attribute,
+        }
+    }
+
+    merged
+}
+
+/// Spreads its attributes onto the element returned by `as_child`.
+///
+/// Useful to give one element the attributes of several parts, or to forward
+/// attributes through a component of your own.
+#[component]
+pub fn Slot(
+    as_child: AsChild,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    as_child.call(attributes)
+}
@@ -0,0 +1,238 @@
+//! Tabs Primitive
+//!
+//! Layered panels of content, one shown at a time, each selected by a tab.
+//! The tabs share a single Tab stop and the arrow keys move between them;
+//! moving focus to a tab selects it unless activation is
+//! [`ActivationMode::Manual`].
+
+use super::context::create_context::use_synced_signal;
+use super::direction::{Direction, use_direction};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::presence::Presence;
+use super::roving_focus::{Orientation, RovingFocusGroup, RovingFocusItem};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::html::input_data::MouseButton;
+use dioxus::prelude::*;
+
+/// When a focused tab becomes the selected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ActivationMode {
+    /// As soon as it receives focus.
+    #[default]
+    Automatic,
+    /// Only once it is pressed, with the pointer, Enter or Space.
+    Manual,
+}
+
+#[derive(Clone)]
+struct TabsContext {
+    value: Memo<String>,
+    set_value: Callback<String>,
+    orientation: Signal<Orientation>,
+    activation_mode: Signal<ActivationMode>,
+    base_id: String,
+}
+
+impl TabsContext {
+    fn trigger_id(&self, value: &str) -> String {
+        format!("{}-trigger-{value}", self.base_id)
+    }
+
+    fn content_id(&self, value: &str) -> String {
+        format!("{}-content-{value}", self.base_id)
+    }
+}
+
+fn state_str(selected: bool) -> &'static str {
+    if selected { "active" } else { "inactive" }
+}
+
+/// The root of a set of tabs, owning the selected value.
+///
+/// Pass `value` and `on_value_change` to control the selection, or
+/// `default_value` to let the tabs manage it.
+#[component]
+pub fn Tabs(
+    value: Option<String>,
+    #[props(default)] default_value: String,
+    on_value_change: Option<EventHandler<String>>,
+    #[props(default)] orientation: Orientation,
+    /// Defaults to the direction from [`use_direction`].
+    dir: Option<Direction>,
+    #[props(default)] activation_mode: ActivationMode,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let inherited_dir = use_direction();
+    let dir = dir.unwrap_or(inherited_dir);
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let orientation_signal = use_synced_signal(orientation);
+    let activation_mode = use_synced_signal(activation_mode);
+    let base_id = use_id();
+    use_context_provider(|| TabsContext {
+        value,
+        set_value,
+        orientation: orientation_signal,
+        activation_mode,
+        base_id,
+    });
+
+    let own_attributes = vec![
+        Attribute::new("dir", dir.as_str(), None, false),
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The row, or column, of tabs.
+#[component]
+pub fn TabsList(
+    /// Wrap around from the last tab to the first and vice versa.
+    #[props(default = true)]
+    loop_focus: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TabsContext>();
+    let orientation = (ctx.orientation)();
+
+    let own_attributes = vec![
+        Attribute::new("role", "tablist", None, false),
+        Attribute::new("aria-orientation", orientation.as_str(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        RovingFocusGroup {
+            orientation,
+            loop_focus,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The tab selecting the [`TabsContent`] with the same `value`.
+#[component]
+pub fn TabsTrigger(
+    value: String,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TabsContext>();
+    let selected = *ctx.value.read() == value;
+    let set_value = ctx.set_value;
+    let activation_mode = ctx.activation_mode;
+
+    let mousedown_value = value.clone();
+    let keydown_value = value.clone();
+    let focus_value = value.clone();
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("role", "tab", None, false),
+        Attribute::new("id", ctx.trigger_id(&value), None, false),
+        Attribute::new("aria-selected", selected, None, false),
+        Attribute::new("aria-controls", ctx.content_id(&value), None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-state", state_str(selected), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onmousedown(move |event: MouseEvent| {
+            // 只有未按住 Ctrl 的主要按鍵才切換，避免干擾開啟選單等操作
+            if !disabled
+                && event.trigger_button() == Some(MouseButton::Primary)
+                && !event.modifiers().ctrl()
+            {
+                set_value.call(mousedown_value.clone());
+            }
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let key = event.key();
+            let pressed = key == Key::Enter || key == Key::Character(" ".into());
+            if pressed && !disabled {
+                set_value.call(keydown_value.clone());
+            }
+        }),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            if !selected && !disabled && *activation_mode.peek() == ActivationMode::Automatic {
+                set_value.call(focus_value.clone());
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        RovingFocusItem {
+            focusable: !disabled,
+            active: selected,
+            as_child: move |item: Vec<Attribute>| {
+                let attributes = merge_attributes(item, attributes.clone());
+                if let Some(as_child) = as_child {
+                    return as_child.call(attributes);
+                }
+                rsx! {
+                    button {
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// The panel shown while the tab with the same `value` is selected.
+#[component]
+pub fn TabsContent(
+    value: String,
+    /// Keep the panel mounted while not selected, for animation libraries;
+    /// see [`Presence`]. The panel is `hidden` while not selected.
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TabsContext>();
+    let selected = *ctx.value.read() == value;
+    let orientation = (ctx.orientation)();
+
+    let own_attributes = vec![
+        Attribute::new("role", "tabpanel", None, false),
+        Attribute::new("id", ctx.content_id(&value), None, false),
+        Attribute::new("aria-labelledby", ctx.trigger_id(&value), None, false),
+        // 面板本身可被 Tab 聚焦，內容中沒有可聚焦元素時也能到達
+        Attribute::new("tabindex", "0", None, false),
+        Attribute::new("hidden", force_mount && !selected, None, false),
+        Attribute::new("data-state", state_str(selected), None, false),
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: selected,
+            force_mount,
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
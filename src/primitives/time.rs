@@ -0,0 +1,28 @@
+//! Timers shared by the primitives.
+
+use std::time::Duration;
+
+/// Wait for `duration` without blocking the renderer.
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// A timestamp in milliseconds, for measuring elapsed time between events.
+///
+/// Only differences between two timestamps are meaningful.
+pub(crate) fn now() -> f64 {
+    #[cfg(feature = "web")]
+    {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map_or(0.0, |performance| performance.now())
+    }
+    #[cfg(not(feature = "web"))]
+    {
+        use std::sync::OnceLock;
+        use std::time::Instant;
+
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
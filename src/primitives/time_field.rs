@@ -0,0 +1,468 @@
+//! TimeField Primitive
+//!
+//! A time input split into hour, minute, second and AM/PM segments. Each
+//! segment is a `spinbutton`: the arrow keys Up and Down change it, digits
+//! type into it and move on once no further digit fits, Backspace clears it,
+//! and Left and Right move between segments. The value is only set once
+//! every shown segment is filled in.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     TimeField { hour_cycle: HourCycle::H12, on_value_change: move |time| alarm.set(time),
+//!         TimeFieldPresets { presets: vec![Time::new(7, 0, 0), Time::new(8, 30, 0)] }
+//!     }
+//! }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::segment::{SegmentFocus, step, type_digit, use_segment_focus};
+use super::slot::merge_attributes;
+use dioxus::prelude::*;
+use std::fmt;
+
+/// A time of day, from `00:00:00` to `23:59:59`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl Time {
+    /// # Panics
+    ///
+    /// Panics if a component is out of range.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Self {
+        assert!(
+            hour < 24 && minute < 60 && second < 60,
+            "invalid time {hour}:{minute}:{second}"
+        );
+        Time {
+            hour,
+            minute,
+            second,
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+/// Whether hours are shown from 1 to 12 with AM/PM, or from 0 to 23.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HourCycle {
+    H12,
+    #[default]
+    H24,
+}
+
+/// The smallest unit the field lets the user edit; smaller units are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Granularity {
+    Hour,
+    #[default]
+    Minute,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    Hour,
+    Minute,
+    Second,
+    DayPeriod,
+}
+
+impl Segment {
+    fn label(&self) -> &'static str {
+        match self {
+            Segment::Hour => "hour",
+            Segment::Minute => "minute",
+            Segment::Second => "second",
+            Segment::DayPeriod => "AM/PM",
+        }
+    }
+}
+
+// 尚未完成的輸入；小時以目前的顯示方式保存，12 小時制時為 1 到 12
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Segments {
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    pm: Option<bool>,
+}
+
+impl Segments {
+    fn from_time(time: Option<Time>, hour_cycle: HourCycle) -> Self {
+        let Some(time) = time else {
+            return Segments::default();
+        };
+        let hour = time.hour as u32;
+        let (hour, pm) = match hour_cycle {
+            HourCycle::H24 => (hour, None),
+            HourCycle::H12 => (
+                if hour % 12 == 0 { 12 } else { hour % 12 },
+                Some(hour >= 12),
+            ),
+        };
+        Segments {
+            hour: Some(hour),
+            minute: Some(time.minute as u32),
+            second: Some(time.second as u32),
+            pm,
+        }
+    }
+
+    fn to_time(self, hour_cycle: HourCycle, granularity: Granularity) -> Option<Time> {
+        let hour = match hour_cycle {
+            HourCycle::H24 => self.hour?,
+            HourCycle::H12 => self.hour? % 12 + if self.pm? { 12 } else { 0 },
+        };
+        let minute = match granularity {
+            Granularity::Hour => 0,
+            _ => self.minute?,
+        };
+        let second = match granularity {
+            Granularity::Second => self.second?,
+            _ => 0,
+        };
+        Some(Time::new(hour as u8, minute as u8, second as u8))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TimeFieldContext {
+    value: Memo<Option<Time>>,
+    set_value: Callback<Option<Time>>,
+    hour_cycle: Signal<HourCycle>,
+    disabled: Signal<bool>,
+    read_only: Signal<bool>,
+}
+
+/// A time input, owning its value.
+///
+/// Pass `value` and `on_value_change` to control the time, or
+/// `default_value` to let the field manage it. The value is `None` until
+/// every segment is filled in.
+#[component]
+pub fn TimeField(
+    value: Option<Option<Time>>,
+    #[props(default)] default_value: Option<Time>,
+    on_value_change: Option<EventHandler<Option<Time>>>,
+    #[props(default)] hour_cycle: HourCycle,
+    #[props(default)] granularity: Granularity,
+    #[props(default)] disabled: bool,
+    #[props(default)] read_only: bool,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    /// Rendered after the segments, e.g. [`TimeFieldPresets`].
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let mut segments = use_signal(|| Segments::from_time(*value.peek(), hour_cycle));
+    let focus = use_segment_focus();
+    let hour_cycle_signal = use_synced_signal(hour_cycle);
+    let disabled_signal = use_synced_signal(disabled);
+    let read_only_signal = use_synced_signal(read_only);
+    use_context_provider(|| TimeFieldContext {
+        value,
+        set_value,
+        hour_cycle: hour_cycle_signal,
+        disabled: disabled_signal,
+        read_only: read_only_signal,
+    });
+
+    // 外部改變值或顯示的段時同步各段；未完成的輸入不受自己送出的 None 影響
+    let mut synced_granularity = use_signal(|| granularity);
+    use_effect(use_reactive!(|(hour_cycle, granularity)| {
+        let time = value();
+        let current = *segments.peek();
+        let completed = current.to_time(hour_cycle, granularity);
+        // 隱藏的段不再需要輸入時，以目前的輸入送出值，而不是清除它
+        if *synced_granularity.peek() != granularity {
+            synced_granularity.set(granularity);
+            if time.is_none() && completed.is_some() {
+                set_value.call(completed);
+                return;
+            }
+        }
+        if time.is_some() || completed.is_some() {
+            segments.set(Segments::from_time(time, hour_cycle));
+        }
+    }));
+
+    let mut update = move |next: Segments| {
+        segments.set(next);
+        let time = next.to_time(hour_cycle, granularity);
+        if time != *value.peek() {
+            set_value.call(time);
+        }
+    };
+    let editable = !disabled && !read_only;
+
+    let mut order = vec![Segment::Hour];
+    if granularity >= Granularity::Minute {
+        order.push(Segment::Minute);
+    }
+    if granularity >= Granularity::Second {
+        order.push(Segment::Second);
+    }
+    if hour_cycle == HourCycle::H12 {
+        order.push(Segment::DayPeriod);
+    }
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-readonly", read_only.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+    let current = segments();
+
+    rsx! {
+        div {
+            ..attributes,
+            for (index, segment) in order.into_iter().enumerate() {
+                if index > 0 && segment != Segment::DayPeriod {
+                    span { aria_hidden: "true", "data-primitive-time-separator": "", ":" }
+                }
+                SegmentSpinner {
+                    key: "{segment:?}",
+                    segment,
+                    current,
+                    hour_cycle,
+                    editable,
+                    disabled,
+                    focus,
+                    on_change: move |next: Segments| update(next),
+                }
+            }
+            {children}
+        }
+    }
+}
+
+#[component]
+fn SegmentSpinner(
+    segment: Segment,
+    current: Segments,
+    hour_cycle: HourCycle,
+    editable: bool,
+    disabled: bool,
+    focus: SegmentFocus,
+    on_change: EventHandler<Segments>,
+) -> Element {
+    // 依段的種類排列焦點順序，而不是掛載順序
+    let id = use_hook(|| match segment {
+        Segment::Hour => 0,
+        Segment::Minute => 1,
+        Segment::Second => 2,
+        Segment::DayPeriod => 3,
+    });
+    let mut buffer = use_signal(String::new);
+    use_drop(move || {
+        let mut focus = focus;
+        focus.unregister(id);
+    });
+
+    let (min, max) = match (segment, hour_cycle) {
+        (Segment::Hour, HourCycle::H12) => (1, 12),
+        (Segment::Hour, HourCycle::H24) => (0, 23),
+        (Segment::Minute | Segment::Second, _) => (0, 59),
+        (Segment::DayPeriod, _) => (0, 1),
+    };
+    let value = match segment {
+        Segment::Hour => current.hour,
+        Segment::Minute => current.minute,
+        Segment::Second => current.second,
+        Segment::DayPeriod => current.pm.map(u32::from),
+    };
+    let with_value = move |value: Option<u32>| {
+        let mut next = current;
+        match segment {
+            Segment::Hour => next.hour = value,
+            Segment::Minute => next.minute = value,
+            Segment::Second => next.second = value,
+            Segment::DayPeriod => next.pm = value.map(|value| value == 1),
+        }
+        next
+    };
+
+    let text = match (segment, value) {
+        (_, None) => "––".to_string(),
+        (Segment::DayPeriod, Some(pm)) => if pm == 1 { "PM" } else { "AM" }.to_string(),
+        (_, Some(value)) => format!("{value:02}"),
+    };
+    let value_text = match value {
+        Some(_) => text.clone(),
+        None => "Empty".to_string(),
+    };
+
+    rsx! {
+        span {
+            role: "spinbutton",
+            tabindex: if disabled { "-1" } else { "0" },
+            inputmode: "numeric",
+            aria_label: segment.label(),
+            aria_valuemin: min,
+            aria_valuemax: max,
+            aria_valuenow: value,
+            aria_valuetext: value_text,
+            aria_disabled: disabled.then_some("true"),
+            aria_readonly: (!editable && !disabled).then_some("true"),
+            "data-segment": format!("{segment:?}").to_lowercase(),
+            "data-placeholder": value.is_none().then_some(""),
+            onmounted: move |event: MountedEvent| {
+                let mut focus = focus;
+                focus.register(id, event.data());
+            },
+            onblur: move |_| buffer.set(String::new()),
+            onkeydown: move |event: KeyboardEvent| {
+                match event.key() {
+                    Key::ArrowLeft => {
+                        event.prevent_default();
+                        focus.focus_relative(id, -1);
+                        return;
+                    }
+                    Key::ArrowRight => {
+                        event.prevent_default();
+                        focus.focus_relative(id, 1);
+                        return;
+                    }
+                    Key::Tab => return,
+                    _ => {}
+                }
+                if !editable {
+                    return;
+                }
+                match event.key() {
+                    Key::ArrowUp => {
+                        event.prevent_default();
+                        buffer.set(String::new());
+                        on_change.call(with_value(Some(step(value, 1, min, max))));
+                    }
+                    Key::ArrowDown => {
+                        event.prevent_default();
+                        buffer.set(String::new());
+                        on_change.call(with_value(Some(step(value, -1, min, max))));
+                    }
+                    Key::Backspace | Key::Delete => {
+                        event.prevent_default();
+                        buffer.set(String::new());
+                        on_change.call(with_value(None));
+                    }
+                    Key::Character(character) if segment == Segment::DayPeriod => {
+                        let pm = match character.to_lowercase().as_str() {
+                            "a" => false,
+                            "p" => true,
+                            _ => return,
+                        };
+                        event.prevent_default();
+                        on_change.call(with_value(Some(u32::from(pm))));
+                        focus.focus_relative(id, 1);
+                    }
+                    Key::Character(character) => {
+                        let Some(digit) = character.chars().next().filter(|_| character.len() == 1)
+                        else {
+                            return;
+                        };
+                        let Some(typed) = type_digit(&buffer.peek(), digit, max) else {
+                            return;
+                        };
+                        event.prevent_default();
+                        // 12 小時制的 0 要等下一個數字才成為有效值
+                        if typed.value >= min {
+                            on_change.call(with_value(Some(typed.value)));
+                        }
+                        if typed.complete {
+                            buffer.set(String::new());
+                            focus.focus_relative(id, 1);
+                        } else {
+                            buffer.set(typed.buffer);
+                        }
+                    }
+                    _ => {}
+                }
+            },
+            {text}
+        }
+    }
+}
+
+/// A dropdown of preset times, e.g. every half hour, rendered as a native
+/// `select` inside [`TimeField`]. Picking one sets the field's value.
+///
+/// The presets are labelled in the field's hour cycle, and the select is
+/// disabled while the field is disabled or read-only.
+#[component]
+pub fn TimeFieldPresets(
+    presets: Vec<Time>,
+    /// The label of the empty option shown while the value is not a preset.
+    #[props(default = String::from("Presets"))]
+    placeholder: String,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let ctx = use_context::<TimeFieldContext>();
+    let value = (ctx.value)();
+    let set_value = ctx.set_value;
+    let hour_cycle = (ctx.hour_cycle)();
+    let selected = value.filter(|time| presets.contains(time));
+    // 唯讀的欄位也不能以預設時間改變值
+    let editable = !(ctx.disabled)() && !(ctx.read_only)();
+
+    let own_attributes = vec![
+        Attribute::new("aria-label", placeholder.clone(), None, false),
+        Attribute::new("disabled", !editable, None, false),
+        dioxus_elements::events::onchange(move |event: FormEvent| {
+            let time = parse_time(&event.value());
+            if editable && time.is_some() {
+                set_value.call(time);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        select {
+            ..attributes,
+            option { value: "", selected: selected.is_none(), disabled: true, {placeholder} }
+            for preset in presets {
+                option {
+                    key: "{preset}",
+                    value: "{preset}",
+                    selected: selected == Some(preset),
+                    {preset_label(preset, hour_cycle)}
+                }
+            }
+        }
+    }
+}
+
+// 以欄位的時制顯示，整分的預設時間不顯示秒
+fn preset_label(time: Time, hour_cycle: HourCycle) -> String {
+    let hour = match hour_cycle {
+        HourCycle::H24 => time.hour,
+        HourCycle::H12 if time.hour % 12 == 0 => 12,
+        HourCycle::H12 => time.hour % 12,
+    };
+    let mut label = format!("{hour:02}:{:02}", time.minute);
+    if time.second != 0 {
+        label.push_str(&format!(":{:02}", time.second));
+    }
+    if hour_cycle == HourCycle::H12 {
+        label.push_str(if time.hour >= 12 { " PM" } else { " AM" });
+    }
+    label
+}
+
+fn parse_time(text: &str) -> Option<Time> {
+    let mut parts = text.split(':').map(|part| part.parse::<u8>().ok());
+    let hour = parts.next()??;
+    let minute = parts.next()??;
+    let second = parts.next().unwrap_or(Some(0))?;
+    (hour < 24 && minute < 60 && second < 60).then(|| Time::new(hour, minute, second))
+}
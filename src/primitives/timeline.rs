@@ -0,0 +1,283 @@
+//! Timeline Primitive
+//!
+//! An ordered list of events, each with an indicator on a line connecting
+//! it to the next, e.g. a changelog or the steps of an order. Items before
+//! the current one are complete and the ones after it are upcoming, which
+//! the parts expose as `data-state`.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     Timeline { current: 1,
+//!         for step in ["Ordered", "Shipped", "Delivered"] {
+//!             TimelineItem {
+//!                 TimelineIndicator {}
+//!                 TimelineConnector {}
+//!                 TimelineContent { {step} }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::context::create_context::use_synced_signal;
+use super::roving_focus::Orientation;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The progress of a [`TimelineItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineStatus {
+    Complete,
+    Current,
+    Upcoming,
+}
+
+impl TimelineStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TimelineStatus::Complete => "complete",
+            TimelineStatus::Current => "current",
+            TimelineStatus::Upcoming => "upcoming",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct TimelineEntry {
+    id: usize,
+    node: Option<Rc<MountedData>>,
+}
+
+#[derive(Clone, Copy)]
+struct TimelineContext {
+    orientation: Signal<Orientation>,
+    alternate: Signal<bool>,
+    current: Signal<Option<usize>>,
+    items: Signal<Vec<TimelineEntry>>,
+}
+
+impl TimelineContext {
+    /// The position of the item among the rendered items, and their count.
+    fn position(&self, id: usize) -> (Option<usize>, usize) {
+        let items = self.items.read().clone();
+        #[cfg(feature = "web")]
+        let items = {
+            let mut items = items;
+            items.sort_by(|a, b| match (&a.node, &b.node) {
+                (Some(a), Some(b)) => super::dom::document_order(a, b),
+                _ => std::cmp::Ordering::Equal,
+            });
+            items
+        };
+        (items.iter().position(|item| item.id == id), items.len())
+    }
+}
+
+/// The root of a timeline, an ordered list of [`TimelineItem`]s.
+///
+/// `current` is the index of the current item; every item is complete when
+/// it is past the last item, and the statuses are left out when it is
+/// `None`. With `alternate`, items are placed on alternating sides of the
+/// line, exposed on each item as `data-side`.
+#[component]
+pub fn Timeline(
+    current: Option<usize>,
+    #[props(default = Orientation::Vertical)] orientation: Orientation,
+    #[props(default)] alternate: bool,
+    /// Render your own element instead of an `ol`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let orientation_signal = use_synced_signal(orientation);
+    let alternate_signal = use_synced_signal(alternate);
+    let current = use_synced_signal(current);
+    use_context_provider(|| TimelineContext {
+        orientation: orientation_signal,
+        alternate: alternate_signal,
+        current,
+        items: Signal::new(Vec::new()),
+    });
+
+    let own_attributes = vec![
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        Attribute::new("data-alternate", alternate.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        ol {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ItemState {
+    status: Option<TimelineStatus>,
+    side: &'static str,
+    last: bool,
+}
+
+impl ItemState {
+    fn attributes(&self, orientation: Orientation) -> Vec<Attribute> {
+        vec![
+            Attribute::new(
+                "data-state",
+                self.status.map(|status| status.as_str()),
+                None,
+                false,
+            ),
+            Attribute::new("data-side", self.side, None, false),
+            Attribute::new("data-last", self.last.then_some(""), None, false),
+            Attribute::new("data-orientation", orientation.as_str(), None, false),
+        ]
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ItemContext {
+    state: Signal<ItemState>,
+}
+
+/// An event of the timeline.
+///
+/// Its status follows the root's `current`; set `status` to override it.
+#[component]
+pub fn TimelineItem(
+    status: Option<TimelineStatus>,
+    /// Render your own element instead of an `li`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<TimelineContext>();
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut node = use_signal(|| None);
+
+    use_effect(move || {
+        let node = node();
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|item| item.id == id) {
+            Some(item) => item.node = node,
+            None => items.push(TimelineEntry { id, node }),
+        }
+    });
+    use_drop(move || ctx.items.write().retain(|item| item.id != id));
+
+    let (index, count) = ctx.position(id);
+    let status = status.or_else(|| {
+        let (index, current) = (index?, (ctx.current)()?);
+        Some(match index.cmp(&current) {
+            std::cmp::Ordering::Less => TimelineStatus::Complete,
+            std::cmp::Ordering::Equal => TimelineStatus::Current,
+            std::cmp::Ordering::Greater => TimelineStatus::Upcoming,
+        })
+    });
+    // 交錯排列時，奇數項目放在另一側
+    let alternate = (ctx.alternate)() && index.is_some_and(|index| index % 2 == 1);
+    let state = ItemState {
+        status,
+        side: if alternate { "start" } else { "end" },
+        last: index.is_some_and(|index| index + 1 == count),
+    };
+    let state_signal = use_synced_signal(state);
+    use_context_provider(|| ItemContext {
+        state: state_signal,
+    });
+
+    let mut own_attributes = state.attributes((ctx.orientation)());
+    own_attributes.extend([
+        Attribute::new(
+            "aria-current",
+            (status == Some(TimelineStatus::Current)).then_some("step"),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+    ]);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        li {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+// 各部件共用的渲染邏輯，只有額外的屬性不同
+fn item_part(
+    extra: Vec<Attribute>,
+    as_child: Option<AsChild>,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TimelineContext>();
+    let item = use_context::<ItemContext>();
+    let mut own_attributes = (item.state)().attributes((ctx.orientation)());
+    own_attributes.extend(extra);
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The marker of an item on the line, e.g. a dot or an icon.
+#[component]
+pub fn TimelineIndicator(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let extra = vec![Attribute::new("aria-hidden", "true", None, false)];
+    item_part(extra, as_child, attributes, children)
+}
+
+/// The line from an item to the next one. Style it away on the last item
+/// with `[data-last]`.
+#[component]
+pub fn TimelineConnector(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let extra = vec![Attribute::new("aria-hidden", "true", None, false)];
+    item_part(extra, as_child, attributes, children)
+}
+
+/// The description of an item.
+#[component]
+pub fn TimelineContent(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    item_part(Vec::new(), as_child, attributes, children)
+}
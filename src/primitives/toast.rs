@@ -0,0 +1,504 @@
+//! Toast Primitive
+//!
+//! Brief notifications shown in a corner of the page, dismissed after a
+//! while. Toasts pause while the pointer is over the viewport, while focus
+//! is inside it and while the window is in the background, so they do not
+//! disappear before they have been read. A shortcut, `F8` by default, moves
+//! focus to the viewport.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     HotkeyProvider {
+//!         ToastProvider {
+//!             Toast { open: saved(), on_open_change: move |open| saved.set(open),
+//!                 ToastTitle { "Saved" }
+//!                 ToastDescription { "Your changes have been saved." }
+//!                 ToastAction { "Undo" }
+//!                 ToastClose { "Dismiss" }
+//!             }
+//!             ToastViewport {}
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The shortcut is registered with [`use_hotkey`], so it needs a
+//! [`HotkeyProvider`](super::hotkeys::HotkeyProvider) above the viewport.
+//! Each toast's text is announced through the
+//! [`Announcer`](super::announcer::Announcer) when it is shown. Without an
+//! `Announcer` above the toast, the toast itself is an `aria-live` region.
+
+use super::announcer::use_announce;
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_state_machine::{MachineConfig, use_state_machine};
+use super::hooks::use_timer::use_timeout;
+use super::hotkeys::{Hotkey, use_hotkey};
+use super::portal::{Portal, PortalTarget};
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// How urgently a toast is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastKind {
+    /// The result of something the user did, announced right away, or with
+    /// `aria-live="assertive"` without an
+    /// [`Announcer`](super::announcer::Announcer).
+    #[default]
+    Foreground,
+    /// Something that happened on its own, e.g. a background sync, announced
+    /// politely.
+    Background,
+}
+
+impl ToastKind {
+    /// The value of the `data-kind` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToastKind::Foreground => "foreground",
+            ToastKind::Background => "background",
+        }
+    }
+}
+
+// 開啟中的 toast：倒數中、暫停中（懸停或在佇列中等待），或正在關閉
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToastState {
+    Visible,
+    Paused,
+    Dismissing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToastEvent {
+    Pause,
+    Resume,
+    Dismiss,
+    Reopen,
+}
+
+#[derive(Clone, Copy)]
+struct ToastProviderContext {
+    duration: Signal<Duration>,
+    max_visible: Signal<usize>,
+    // 依開啟順序排列的 toast；超過 max_visible 的暫停並等待
+    queue: Signal<Vec<usize>>,
+    // 指標或焦點在 viewport 中、或視窗不在前景時暫停所有 toast
+    hovered: Signal<bool>,
+    focused: Signal<bool>,
+    window_blurred: Signal<bool>,
+    viewport: Signal<Option<String>>,
+}
+
+impl ToastProviderContext {
+    fn paused(&self) -> bool {
+        (self.hovered)() || (self.focused)() || (self.window_blurred)()
+    }
+}
+
+/// Provides the queue shared by the toasts and the [`ToastViewport`] below
+/// it.
+#[component]
+pub fn ToastProvider(
+    /// How long a toast stays open, unless it sets its own `duration`.
+    #[props(default = Duration::from_secs(5))]
+    duration: Duration,
+    /// How many toasts are shown at once. The others wait, paused, until one
+    /// closes.
+    #[props(default = 3)]
+    max_visible: usize,
+    children: Element,
+) -> Element {
+    let duration = use_synced_signal(duration);
+    let max_visible = use_synced_signal(max_visible);
+    let ctx = use_context_provider(|| ToastProviderContext {
+        duration,
+        max_visible,
+        queue: Signal::new(Vec::new()),
+        hovered: Signal::new(false),
+        focused: Signal::new(false),
+        window_blurred: Signal::new(false),
+        viewport: Signal::new(None),
+    });
+
+    #[cfg(feature = "web")]
+    {
+        use super::hooks::use_global_event::use_window_event;
+        let mut window_blurred = ctx.window_blurred;
+        use_window_event("blur", false, move |_: web_sys::FocusEvent| {
+            window_blurred.set(true)
+        });
+        use_window_event("focus", false, move |_: web_sys::FocusEvent| {
+            window_blurred.set(false)
+        });
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = ctx;
+
+    rsx! { {children} }
+}
+
+/// The region the toasts are rendered into, e.g. fixed in a corner of the
+/// page.
+#[component]
+pub fn ToastViewport(
+    /// The shortcut that moves focus to the viewport; see
+    /// [`Hotkey::parse`].
+    #[props(default = String::from("F8"))]
+    hotkey: String,
+    /// The accessible name of the viewport. `{hotkey}` is replaced with the
+    /// shortcut.
+    #[props(default = String::from("Notifications ({hotkey})"))]
+    label: String,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<ToastProviderContext>();
+    let id = use_id();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+
+    use_effect({
+        let id = id.clone();
+        move || ctx.viewport.set(Some(id.clone()))
+    });
+    use_drop(move || ctx.viewport.set(None));
+
+    use_hotkey(&hotkey, move || {
+        let node = node.peek().clone();
+        if let Some(node) = node {
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        }
+    });
+
+    let shortcut = Hotkey::parse(&hotkey)
+        .map(|hotkey| hotkey.to_string())
+        .unwrap_or(hotkey);
+    let label = label.replace("{hotkey}", &shortcut);
+
+    let own_attributes = vec![
+        Attribute::new("id", id, None, false),
+        Attribute::new("tabindex", "-1", None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| node.set(Some(event.data()))),
+        dioxus_elements::events::onpointerenter(move |_: PointerEvent| ctx.hovered.set(true)),
+        dioxus_elements::events::onpointerleave(move |_: PointerEvent| ctx.hovered.set(false)),
+        dioxus_elements::events::onfocusin(move |_: FocusEvent| ctx.focused.set(true)),
+        dioxus_elements::events::onfocusout(move |_: FocusEvent| ctx.focused.set(false)),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        section {
+            role: "region",
+            aria_label: label,
+            ol {
+                ..attributes,
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ToastContext {
+    dismiss: Callback<()>,
+}
+
+static NEXT_TOAST_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A notification, rendered into the [`ToastViewport`] while open.
+///
+/// Pass `open` and `on_open_change` to control whether it is shown, or
+/// `default_open` to show it once on mount. It closes itself after its
+/// duration, on Escape, or through a [`ToastClose`] or [`ToastAction`].
+#[component]
+pub fn Toast(
+    open: Option<bool>,
+    #[props(default = true)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Overrides the provider's duration for this toast.
+    duration: Option<Duration>,
+    #[props(default)] kind: ToastKind,
+    /// Keep the toast mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Render your own element instead of an `li`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut provider = use_context::<ToastProviderContext>();
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let id = use_hook(|| NEXT_TOAST_ID.fetch_add(1, Ordering::Relaxed));
+    let announce = use_announce();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    // 是否已朗讀過這次開啟的內容
+    let mut announced = use_signal(|| false);
+
+    let mut machine = use_state_machine(move || {
+        MachineConfig::new(ToastState::Visible)
+            .on(ToastState::Visible, ToastEvent::Pause, ToastState::Paused)
+            .on(ToastState::Paused, ToastEvent::Resume, ToastState::Visible)
+            .on(
+                ToastState::Dismissing,
+                ToastEvent::Reopen,
+                ToastState::Visible,
+            )
+            .on_any(ToastEvent::Dismiss, ToastState::Dismissing)
+            .enter(ToastState::Dismissing, move |_| set_open.call(false))
+    });
+
+    let duration = duration.unwrap_or_else(|| (provider.duration)());
+    let mut timer = use_timeout(duration, move || {
+        machine.send(ToastEvent::Dismiss);
+    });
+    // 是否已為這次開啟啟動過計時器，之後只從暫停處繼續
+    let mut started = use_signal(|| false);
+
+    // 開啟時排入佇列，關閉或卸載時移出
+    use_effect(move || {
+        let open = open();
+        let mut queue = provider.queue.write();
+        let queued = queue.contains(&id);
+        if open && !queued {
+            queue.push(id);
+        } else if !open && queued {
+            queue.retain(|queued| *queued != id);
+        }
+    });
+    use_drop(move || provider.queue.write().retain(|queued| *queued != id));
+
+    // 暫停條件與佇列位置決定是否倒數
+    use_effect(move || {
+        let waiting = provider
+            .queue
+            .read()
+            .iter()
+            .position(|queued| *queued == id)
+            .is_none_or(|index| index >= (provider.max_visible)());
+        let pause = provider.paused() || waiting;
+        match (machine.state(), pause) {
+            (ToastState::Visible, true) => machine.send(ToastEvent::Pause),
+            (ToastState::Paused, false) => machine.send(ToastEvent::Resume),
+            _ => false,
+        };
+    });
+
+    use_effect(move || {
+        let open = open();
+        match (open, machine.state()) {
+            (false, _) => {
+                timer.reset();
+                started.set(false);
+            }
+            // 以受控的 open 保持開啟，或關閉後再次開啟
+            (true, ToastState::Dismissing) => {
+                started.set(false);
+                machine.send(ToastEvent::Reopen);
+            }
+            (true, ToastState::Paused) => timer.pause(),
+            (true, ToastState::Visible) => {
+                if *started.peek() {
+                    timer.resume();
+                } else {
+                    started.set(true);
+                    timer.start();
+                }
+            }
+        }
+    });
+
+    // 顯示時朗讀一次，在佇列中等待的 toast 等到顯示後才朗讀
+    use_effect(move || {
+        if !open() {
+            if *announced.peek() {
+                announced.set(false);
+            }
+            return;
+        }
+        if !announce.is_available() {
+            return;
+        }
+        let waiting = provider
+            .queue
+            .read()
+            .iter()
+            .position(|queued| *queued == id)
+            .is_some_and(|index| index >= (provider.max_visible)());
+        let Some(node) = node.read().clone() else {
+            return;
+        };
+        if waiting || *announced.peek() {
+            return;
+        }
+        announced.set(true);
+
+        #[cfg(feature = "web")]
+        if let Some(text) = super::dom::text_content(&node).filter(|text| !text.is_empty()) {
+            match kind {
+                ToastKind::Foreground => announce.assertive(text),
+                ToastKind::Background => announce.polite(text),
+            }
+        }
+        #[cfg(not(feature = "web"))]
+        let _ = (node, announce);
+    });
+
+    use_context_provider(|| ToastContext {
+        dismiss: Callback::new(move |()| {
+            machine.send(ToastEvent::Dismiss);
+        }),
+    });
+
+    let open = open();
+    let waiting = provider
+        .queue
+        .read()
+        .iter()
+        .position(|queued| *queued == id)
+        .is_some_and(|index| index >= (provider.max_visible)());
+    let state = machine.state();
+    // 沒有 Announcer 時由 toast 自己作為即時播報區域
+    let live_region = !announce.is_available();
+    let live = match kind {
+        ToastKind::Foreground => "assertive",
+        ToastKind::Background => "polite",
+    };
+
+    let own_attributes = vec![
+        Attribute::new("role", live_region.then_some("status"), None, false),
+        Attribute::new("aria-live", live_region.then_some(live), None, false),
+        Attribute::new("aria-atomic", live_region.then_some("true"), None, false),
+        Attribute::new("tabindex", "0", None, false),
+        Attribute::new(
+            "data-state",
+            if open { "open" } else { "closed" },
+            None,
+            false,
+        ),
+        Attribute::new(
+            "data-paused",
+            (state == ToastState::Paused).then_some(""),
+            None,
+            false,
+        ),
+        Attribute::new("data-kind", kind.as_str(), None, false),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| node.set(Some(event.data()))),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            if event.key() == Key::Escape {
+                event.prevent_default();
+                machine.send(ToastEvent::Dismiss);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    let Some(viewport) = (provider.viewport)() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        Portal { target: PortalTarget::Id(viewport), display: "contents",
+            Presence {
+                // 佇列中等待的 toast 先不顯示
+                present: open && !waiting,
+                force_mount,
+                // 卸載後的元素不再用來讀取內容，重新開啟時等新的元素掛載
+                on_exit_complete: move |_| {
+                    if !force_mount {
+                        node.set(None);
+                    }
+                },
+                as_child: move |presence: Vec<Attribute>| {
+                    let attributes = merge_attributes(presence, attributes.clone());
+                    if let Some(as_child) = as_child {
+                        return as_child.call(attributes);
+                    }
+                    rsx! {
+                        li {
+                            ..attributes,
+                            {children.clone()}
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// The title of a toast.
+#[component]
+pub fn ToastTitle(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The message of a toast.
+#[component]
+pub fn ToastDescription(
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button for an action the toast offers, e.g. undo. Closes the toast.
+///
+/// Toasts disappear on their own, so make the action available elsewhere in
+/// the app as well.
+#[component]
+pub fn ToastAction(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        ToastClose { as_child, ..attributes, {children} }
+    }
+}
+
+/// A button that closes the toast.
+#[component]
+pub fn ToastClose(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<ToastContext>();
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        dioxus_elements::events::onclick(move |_: MouseEvent| ctx.dismiss.call(())),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Toggle Primitive
+//!
+//! A two-state button that is either pressed or not, e.g. a bold button in a
+//! text editor toolbar.
+
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+
+fn state_str(pressed: bool) -> &'static str {
+    if pressed { "on" } else { "off" }
+}
+
+// 其他切換類元件共用的按鈕屬性
+pub(crate) fn toggle_attributes(
+    pressed: bool,
+    disabled: bool,
+    set_pressed: Callback<bool>,
+) -> Vec<Attribute> {
+    vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-state", state_str(pressed), None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            if !disabled {
+                set_pressed.call(!pressed);
+            }
+        }),
+    ]
+}
+
+/// A button toggled between pressed and not pressed.
+///
+/// Pass `pressed` and `on_pressed_change` to control the state, or
+/// `default_pressed` to let the toggle manage it.
+#[component]
+pub fn Toggle(
+    pressed: Option<bool>,
+    #[props(default)] default_pressed: bool,
+    on_pressed_change: Option<EventHandler<bool>>,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (pressed, set_pressed) =
+        use_controllable_state(pressed, || default_pressed, on_pressed_change);
+    let pressed = pressed();
+
+    let mut own_attributes = toggle_attributes(pressed, disabled, set_pressed);
+    own_attributes.push(Attribute::new("aria-pressed", pressed, None, false));
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,444 @@
+//! Tooltip Primitive
+//!
+//! A short description of an element, shown while the element is hovered or
+//! focused. A tooltip opens after a delay, but once one is open, moving to
+//! an adjacent trigger opens its tooltip right away. It closes on Escape,
+//! when its trigger is activated, or when the pointer and focus leave.
+//!
+//! The behavior of each tooltip is a
+//! [`StateMachine`](super::hooks::use_state_machine::StateMachine): closed,
+//! waiting to open, open, and closing while the pointer moves from the
+//! trigger to the content.
+
+pub use super::popper::PopperArrow as TooltipArrow;
+
+use super::context::create_context::use_synced_signal;
+use super::dismissable_layer::{DismissEvent, DismissableLayer};
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::hooks::use_id::use_id;
+use super::hooks::use_state_machine::{MachineConfig, StateMachine, use_state_machine};
+use super::hooks::use_timer::{Timer, use_timeout};
+use super::popper::{Align, PopperAnchor, PopperContent, PopperRoot, Side};
+use super::presence::Presence;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+// 指標從觸發元素移到內容上的時間
+const CLOSE_DELAY: Duration = Duration::from_millis(100);
+
+/// The states of a tooltip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TooltipState {
+    Closed,
+    /// The pointer rests on the trigger; the tooltip opens after the delay.
+    DelayedOpen,
+    Open,
+    /// The pointer left the trigger; the tooltip closes unless it reaches
+    /// the content in time.
+    Closing,
+}
+
+impl TooltipState {
+    /// Whether the content is shown.
+    pub fn is_open(&self) -> bool {
+        matches!(self, TooltipState::Open | TooltipState::Closing)
+    }
+}
+
+/// The events a tooltip reacts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TooltipEvent {
+    PointerEnter,
+    PointerLeave,
+    Focus,
+    Blur,
+    /// The open or close delay elapsed.
+    Timeout,
+    /// Escape, activation of the trigger, or another tooltip opening.
+    Dismiss,
+}
+
+#[derive(Clone, Copy)]
+struct TooltipProviderContext {
+    delay_duration: Signal<Duration>,
+    disable_hoverable_content: Signal<bool>,
+    // 剛關閉一個提示後的一段時間內，下一個提示不需等待即可開啟
+    open_delayed: Signal<bool>,
+    skip_delay_timer: Timer,
+    open_tooltip: Signal<Option<usize>>,
+}
+
+/// Shares delays between the tooltips inside it, so moving from one trigger
+/// to the next skips the delay.
+#[component]
+pub fn TooltipProvider(
+    /// How long the pointer must rest on a trigger before its tooltip opens.
+    #[props(default = Duration::from_millis(700))]
+    delay_duration: Duration,
+    /// How long after a tooltip closes the next one still opens without
+    /// delay.
+    #[props(default = Duration::from_millis(300))]
+    skip_delay_duration: Duration,
+    /// Close tooltips as soon as the pointer leaves the trigger, instead of
+    /// letting the pointer move onto the content.
+    #[props(default)]
+    disable_hoverable_content: bool,
+    children: Element,
+) -> Element {
+    let delay_duration = use_synced_signal(delay_duration);
+    let disable_hoverable_content = use_synced_signal(disable_hoverable_content);
+    let mut open_delayed = use_signal(|| true);
+    let skip_delay_timer = use_timeout(skip_delay_duration, move || open_delayed.set(true));
+    use_context_provider(|| TooltipProviderContext {
+        delay_duration,
+        disable_hoverable_content,
+        open_delayed,
+        skip_delay_timer,
+        open_tooltip: Signal::new(None),
+    });
+
+    rsx! { {children} }
+}
+
+#[derive(Clone)]
+struct TooltipContext {
+    machine: StateMachine<TooltipState, TooltipEvent>,
+    open: Memo<bool>,
+    opened_after_delay: Signal<bool>,
+    content_id: String,
+}
+
+static NEXT_TOOLTIP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The root of a tooltip. Must be rendered inside a [`TooltipProvider`].
+#[component]
+pub fn Tooltip(
+    open: Option<bool>,
+    #[props(default)] default_open: bool,
+    on_open_change: Option<EventHandler<bool>>,
+    /// Overrides the provider's `delay_duration` for this tooltip.
+    delay_duration: Option<Duration>,
+    children: Element,
+) -> Element {
+    let provider = use_context::<TooltipProviderContext>();
+    let id = use_hook(|| NEXT_TOOLTIP_ID.fetch_add(1, Ordering::Relaxed));
+    let initially_open = open.unwrap_or(default_open);
+    let (open, set_open) = use_controllable_state(open, || default_open, on_open_change);
+    let opened_after_delay = use_signal(|| false);
+
+    let machine = use_state_machine(move || {
+        let TooltipProviderContext {
+            disable_hoverable_content,
+            open_delayed,
+            skip_delay_timer,
+            open_tooltip,
+            ..
+        } = provider;
+        let initial = if initially_open {
+            TooltipState::Open
+        } else {
+            TooltipState::Closed
+        };
+
+        MachineConfig::new(initial)
+            .on_if(
+                TooltipState::Closed,
+                TooltipEvent::PointerEnter,
+                TooltipState::DelayedOpen,
+                move |_, _| *open_delayed.peek(),
+            )
+            .on(
+                TooltipState::Closed,
+                TooltipEvent::PointerEnter,
+                TooltipState::Open,
+            )
+            .on(
+                TooltipState::Closed,
+                TooltipEvent::Focus,
+                TooltipState::Open,
+            )
+            .on(
+                TooltipState::DelayedOpen,
+                TooltipEvent::Timeout,
+                TooltipState::Open,
+            )
+            .on(
+                TooltipState::DelayedOpen,
+                TooltipEvent::Focus,
+                TooltipState::Open,
+            )
+            .on(
+                TooltipState::DelayedOpen,
+                TooltipEvent::PointerLeave,
+                TooltipState::Closed,
+            )
+            .on_if(
+                TooltipState::Open,
+                TooltipEvent::PointerLeave,
+                TooltipState::Closing,
+                move |_, _| !*disable_hoverable_content.peek(),
+            )
+            .on(
+                TooltipState::Open,
+                TooltipEvent::PointerLeave,
+                TooltipState::Closed,
+            )
+            .on(TooltipState::Open, TooltipEvent::Blur, TooltipState::Closed)
+            .on(
+                TooltipState::Closing,
+                TooltipEvent::PointerEnter,
+                TooltipState::Open,
+            )
+            .on(
+                TooltipState::Closing,
+                TooltipEvent::Timeout,
+                TooltipState::Closed,
+            )
+            .on(
+                TooltipState::Closing,
+                TooltipEvent::Blur,
+                TooltipState::Closed,
+            )
+            .on_any(TooltipEvent::Dismiss, TooltipState::Closed)
+            .enter(TooltipState::Open, move |event| {
+                let (mut opened_after_delay, mut open_delayed, mut open_tooltip) =
+                    (opened_after_delay, open_delayed, open_tooltip);
+                let mut skip_delay_timer = skip_delay_timer;
+                // 從 Closing 回到 Open 時維持原本的開啟方式
+                if *event != TooltipEvent::PointerEnter || *open_tooltip.peek() != Some(id) {
+                    opened_after_delay.set(*event == TooltipEvent::Timeout);
+                }
+                skip_delay_timer.reset();
+                open_delayed.set(false);
+                open_tooltip.set(Some(id));
+            })
+            .enter(TooltipState::Closed, move |_| {
+                let mut open_tooltip = open_tooltip;
+                let mut skip_delay_timer = skip_delay_timer;
+                if *open_tooltip.peek() == Some(id) {
+                    open_tooltip.set(None);
+                    skip_delay_timer.start();
+                }
+            })
+    });
+
+    let delay = delay_duration.unwrap_or_else(|| (provider.delay_duration)());
+    let mut open_timer = use_timeout(delay, move || {
+        let mut machine = machine;
+        machine.send(TooltipEvent::Timeout);
+    });
+    let mut close_timer = use_timeout(CLOSE_DELAY, move || {
+        let mut machine = machine;
+        machine.send(TooltipEvent::Timeout);
+    });
+
+    use_effect(move || {
+        let state = machine.state();
+        match state {
+            TooltipState::DelayedOpen => open_timer.start(),
+            TooltipState::Closing => close_timer.start(),
+            _ => {
+                open_timer.reset();
+                close_timer.reset();
+            }
+        }
+        set_open.call(state.is_open());
+    });
+
+    // 受控的 open 被設為 false 時關閉
+    use_effect(move || {
+        let mut machine = machine;
+        if !open() && machine.peek().is_open() {
+            machine.send(TooltipEvent::Dismiss);
+        }
+    });
+
+    // 同一時間只顯示一個提示
+    use_effect(move || {
+        let open_tooltip = (provider.open_tooltip)();
+        let mut machine = machine;
+        if open_tooltip.is_some_and(|open| open != id) && machine.peek() != TooltipState::Closed {
+            machine.send(TooltipEvent::Dismiss);
+        }
+    });
+
+    use_drop(move || {
+        let mut open_tooltip = provider.open_tooltip;
+        if *open_tooltip.peek() == Some(id) {
+            open_tooltip.set(None);
+        }
+    });
+
+    let content_id = use_id();
+    use_context_provider(|| TooltipContext {
+        machine,
+        open,
+        opened_after_delay,
+        content_id,
+    });
+
+    rsx! {
+        PopperRoot { {children} }
+    }
+}
+
+/// The element the tooltip describes. Its `aria-describedby` points at the
+/// content while the tooltip is open.
+#[component]
+pub fn TooltipTrigger(
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TooltipContext>();
+    let mut machine = ctx.machine;
+    let open = (ctx.open)();
+    // 按下觸發元素時會先取得焦點，這次的 focus 不應再開啟提示
+    let mut pointer_down = use_signal(|| false);
+
+    let state = if !open {
+        "closed"
+    } else if (ctx.opened_after_delay)() {
+        "delayed-open"
+    } else {
+        "instant-open"
+    };
+
+    let own_attributes = vec![
+        Attribute::new(
+            "aria-describedby",
+            open.then(|| ctx.content_id.clone()),
+            None,
+            false,
+        ),
+        Attribute::new("data-state", state, None, false),
+        dioxus_elements::events::onpointerenter(move |event: PointerEvent| {
+            // 觸控沒有懸停，只在按住時由瀏覽器的長按處理
+            if event.pointer_type() != "touch" {
+                machine.send(TooltipEvent::PointerEnter);
+            }
+        }),
+        dioxus_elements::events::onpointerleave(move |event: PointerEvent| {
+            pointer_down.set(false);
+            if event.pointer_type() != "touch" {
+                machine.send(TooltipEvent::PointerLeave);
+            }
+        }),
+        dioxus_elements::events::onpointerdown(move |_: PointerEvent| {
+            pointer_down.set(true);
+            machine.send(TooltipEvent::Dismiss);
+        }),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            if !*pointer_down.peek() {
+                machine.send(TooltipEvent::Focus);
+            }
+        }),
+        dioxus_elements::events::onblur(move |_: FocusEvent| {
+            pointer_down.set(false);
+            machine.send(TooltipEvent::Blur);
+        }),
+        dioxus_elements::events::onclick(move |_: MouseEvent| {
+            machine.send(TooltipEvent::Dismiss);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        PopperAnchor {
+            as_child: move |anchor: Vec<Attribute>| {
+                let attributes = merge_attributes(anchor, attributes.clone());
+                if let Some(as_child) = as_child {
+                    return as_child.call(attributes);
+                }
+                rsx! {
+                    button {
+                        r#type: "button",
+                        ..attributes,
+                        {children.clone()}
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// The tooltip, positioned next to the trigger while open.
+#[component]
+pub fn TooltipContent(
+    #[props(default = Side::Top)] side: Side,
+    #[props(default)] side_offset: f64,
+    #[props(default)] align: Align,
+    #[props(default)] align_offset: f64,
+    #[props(default = true)] avoid_collisions: bool,
+    #[props(default)] collision_padding: f64,
+    /// Keep the content mounted while closed, for animation libraries; see
+    /// [`Presence`].
+    #[props(default)]
+    force_mount: bool,
+    /// Fired on Escape; prevent it to keep the tooltip open.
+    on_escape_key_down: Option<EventHandler<DismissEvent>>,
+    /// Fired on a pointer press outside the content; prevent it to keep the
+    /// tooltip open.
+    on_pointer_down_outside: Option<EventHandler<DismissEvent>>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let ctx = use_context::<TooltipContext>();
+    let mut machine = ctx.machine;
+    let open = (ctx.open)();
+
+    let own_attributes = vec![
+        Attribute::new("role", "tooltip", None, false),
+        Attribute::new("id", ctx.content_id.clone(), None, false),
+        dioxus_elements::events::onpointerenter(move |_: PointerEvent| {
+            machine.send(TooltipEvent::PointerEnter);
+        }),
+        dioxus_elements::events::onpointerleave(move |_: PointerEvent| {
+            machine.send(TooltipEvent::PointerLeave);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Presence {
+            present: open,
+            force_mount,
+            as_child: move |presence: Vec<Attribute>| {
+                let attributes = merge_attributes(presence, attributes.clone());
+                let children = children.clone();
+                rsx! {
+                    PopperContent {
+                        side,
+                        side_offset,
+                        align,
+                        align_offset,
+                        avoid_collisions,
+                        collision_padding,
+                        DismissableLayer {
+                            on_escape_key_down,
+                            on_pointer_down_outside,
+                            // 焦點移動由觸發元素的 blur 處理
+                            on_focus_outside: move |event: DismissEvent| event.prevent_default(),
+                            on_dismiss: move |_| {
+                                let mut machine = machine;
+                                machine.send(TooltipEvent::Dismiss);
+                            },
+                            as_child: move |layer: Vec<Attribute>| {
+                                let attributes = merge_attributes(layer, attributes.clone());
+                                rsx! {
+                                    div {
+                                        ..attributes,
+                                        {children.clone()}
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! TransferList Primitive
+//!
+//! Two multiple-selection [`Listbox`]es side by side, with buttons moving
+//! the selected items, or all of them, from one list to the other. The
+//! value is the set of items in the target list.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     TransferList { on_value_change: move |chosen| columns.set(chosen),
+//!         TransferListSource {
+//!             for column in COLUMNS {
+//!                 TransferListItem { value: column, {column} }
+//!             }
+//!         }
+//!         TransferListMoveTrigger { to: TransferSide::Target, "›" }
+//!         TransferListMoveTrigger { to: TransferSide::Target, all: true, "»" }
+//!         TransferListMoveTrigger { to: TransferSide::Source, "‹" }
+//!         TransferListMoveTrigger { to: TransferSide::Source, all: true, "«" }
+//!         TransferListTarget {
+//!             for column in COLUMNS {
+//!                 TransferListItem { value: column, {column} }
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Render every item in both lists: each list only shows the items on its
+//! side. Without a mouse, ArrowRight in the source list and ArrowLeft in
+//! the target list move the selected items across, and every move is
+//! announced through the [`Announcer`](super::announcer::Announcer).
+
+use super::announcer::use_announce;
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_controllable_state::use_controllable_state;
+use super::listbox::{Listbox, ListboxItem, SelectionMode};
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// One of the two lists of a [`TransferList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferSide {
+    /// The items not chosen yet.
+    Source,
+    /// The chosen items, which make up the value.
+    Target,
+}
+
+impl TransferSide {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TransferSide::Source => "source",
+            TransferSide::Target => "target",
+        }
+    }
+
+    fn other(&self) -> TransferSide {
+        match self {
+            TransferSide::Source => TransferSide::Target,
+            TransferSide::Target => TransferSide::Source,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct TransferItemEntry {
+    id: usize,
+    value: String,
+    text_value: String,
+    disabled: bool,
+}
+
+#[derive(Clone, Copy)]
+struct TransferListContext {
+    value: Memo<Vec<String>>,
+    set_value: Callback<Vec<String>>,
+    source_selected: Signal<Vec<String>>,
+    target_selected: Signal<Vec<String>>,
+    source_label: Signal<String>,
+    target_label: Signal<String>,
+    disabled: Signal<bool>,
+    // 兩個清單都會註冊同一個項目，以 value 去除重複
+    items: Signal<Vec<TransferItemEntry>>,
+}
+
+impl TransferListContext {
+    fn side_of(&self, value: &str) -> TransferSide {
+        if self.value.read().iter().any(|other| other == value) {
+            TransferSide::Target
+        } else {
+            TransferSide::Source
+        }
+    }
+
+    fn selected(&self, side: TransferSide) -> Signal<Vec<String>> {
+        match side {
+            TransferSide::Source => self.source_selected,
+            TransferSide::Target => self.target_selected,
+        }
+    }
+
+    fn label(&self, side: TransferSide) -> Signal<String> {
+        match side {
+            TransferSide::Source => self.source_label,
+            TransferSide::Target => self.target_label,
+        }
+    }
+
+    /// The enabled items that would move to `to`, in the order they were
+    /// rendered.
+    fn movable(&self, to: TransferSide, all: bool) -> Vec<TransferItemEntry> {
+        let from = to.other();
+        let selected = self.selected(from).read().clone();
+        let mut movable: Vec<TransferItemEntry> = Vec::new();
+        for entry in self.items.read().iter() {
+            let eligible = !entry.disabled
+                && self.side_of(&entry.value) == from
+                && (all || selected.contains(&entry.value))
+                && !movable.iter().any(|other| other.value == entry.value);
+            if eligible {
+                movable.push(entry.clone());
+            }
+        }
+        movable
+    }
+
+    fn transfer(&mut self, to: TransferSide, all: bool) -> Vec<TransferItemEntry> {
+        let moved = self.movable(to, all);
+        if moved.is_empty() || *self.disabled.peek() {
+            return Vec::new();
+        }
+        let mut value = self.value.peek().clone();
+        match to {
+            TransferSide::Target => value.extend(moved.iter().map(|entry| entry.value.clone())),
+            TransferSide::Source => {
+                value.retain(|value| !moved.iter().any(|entry| entry.value == *value))
+            }
+        }
+        self.set_value.call(value);
+        self.selected(to.other()).set(Vec::new());
+        moved
+    }
+}
+
+/// The root of a transfer list, owning which items are in the target list.
+///
+/// Pass `value` and `on_value_change` to control the target items, or
+/// `default_value` to let the list manage them. Items moved to the target
+/// are appended to the value.
+#[component]
+pub fn TransferList(
+    value: Option<Vec<String>>,
+    #[props(default)] default_value: Vec<String>,
+    on_value_change: Option<EventHandler<Vec<String>>>,
+    /// The accessible name of the source list, also used in announcements.
+    #[props(default = String::from("Available"))]
+    source_label: String,
+    /// The accessible name of the target list, also used in announcements.
+    #[props(default = String::from("Selected"))]
+    target_label: String,
+    #[props(default)] disabled: bool,
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let (value, set_value) = use_controllable_state(value, || default_value, on_value_change);
+    let source_label = use_synced_signal(source_label);
+    let target_label = use_synced_signal(target_label);
+    let disabled_signal = use_synced_signal(disabled);
+    use_context_provider(|| TransferListContext {
+        value,
+        set_value,
+        source_selected: Signal::new(Vec::new()),
+        target_selected: Signal::new(Vec::new()),
+        source_label,
+        target_label,
+        disabled: disabled_signal,
+        items: Signal::new(Vec::new()),
+    });
+
+    let own_attributes = vec![
+        Attribute::new("role", "group", None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SideContext {
+    side: TransferSide,
+}
+
+#[component]
+fn TransferListSide(
+    side: TransferSide,
+    as_child: Option<AsChild>,
+    attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<TransferListContext>();
+    let announce = use_announce();
+    use_context_provider(|| SideContext { side });
+    let mut selected = ctx.selected(side);
+
+    let own_attributes = vec![
+        Attribute::new("aria-label", ctx.label(side)(), None, false),
+        Attribute::new("data-side", side.as_str(), None, false),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let key = match side {
+                TransferSide::Source => Key::ArrowRight,
+                TransferSide::Target => Key::ArrowLeft,
+            };
+            if event.key() != key {
+                return;
+            }
+            event.prevent_default();
+            let to = side.other();
+            let moved = ctx.transfer(to, false);
+            let label = ctx.label(to).peek().clone();
+            if let Some(message) = moved_message(&moved, &label) {
+                announce.polite(message);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        Listbox {
+            value: selected(),
+            on_value_change: move |next| selected.set(next),
+            selection_mode: SelectionMode::Multiple,
+            disabled: (ctx.disabled)(),
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The list of items not in the value, a multiple-selection [`Listbox`].
+#[component]
+pub fn TransferListSource(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        TransferListSide { side: TransferSide::Source, as_child, attributes, {children} }
+    }
+}
+
+/// The list of items in the value, a multiple-selection [`Listbox`].
+#[component]
+pub fn TransferListTarget(
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    rsx! {
+        TransferListSide { side: TransferSide::Target, as_child, attributes, {children} }
+    }
+}
+
+/// An item of a transfer list, shown only in the list it is currently in.
+#[component]
+pub fn TransferListItem(
+    value: String,
+    /// The text used for typeahead and announcements. Defaults to `value`.
+    text_value: Option<String>,
+    #[props(default)] disabled: bool,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<TransferListContext>();
+    let side = use_context::<SideContext>().side;
+    let id = use_hook(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let text_value = text_value.unwrap_or_else(|| value.clone());
+    let entry = use_synced_signal((value.clone(), text_value.clone(), disabled));
+
+    use_effect(move || {
+        let (value, text_value, disabled) = entry();
+        let entry = TransferItemEntry {
+            id,
+            value,
+            text_value,
+            disabled,
+        };
+        let mut items = ctx.items.write();
+        match items.iter_mut().find(|other| other.id == id) {
+            Some(existing) => *existing = entry,
+            None => items.push(entry),
+        }
+    });
+    use_drop(move || ctx.items.write().retain(|entry| entry.id != id));
+
+    if ctx.side_of(&value) != side {
+        return rsx! {};
+    }
+
+    rsx! {
+        ListboxItem {
+            value,
+            text_value,
+            disabled,
+            as_child,
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// A button moving the selected items, or all items with `all`, into the
+/// `to` list. It is disabled while there is nothing to move.
+#[component]
+pub fn TransferListMoveTrigger(
+    to: TransferSide,
+    #[props(default)] all: bool,
+    /// Render your own element instead of a `button`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<TransferListContext>();
+    let announce = use_announce();
+    let disabled = (ctx.disabled)() || ctx.movable(to, all).is_empty();
+
+    let own_attributes = vec![
+        Attribute::new("type", "button", None, false),
+        Attribute::new("disabled", disabled, None, false),
+        Attribute::new("data-disabled", disabled.then_some(""), None, false),
+        Attribute::new("data-to", to.as_str(), None, false),
+        dioxus_elements::events::onclick(move |_| {
+            let moved = ctx.transfer(to, all);
+            let label = ctx.label(to).peek().clone();
+            if let Some(message) = moved_message(&moved, &label) {
+                announce.polite(message);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        button {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+fn moved_message(moved: &[TransferItemEntry], label: &str) -> Option<String> {
+    match moved {
+        [] => None,
+        [entry] => Some(format!("Moved {} to {}", entry.text_value, label)),
+        moved => Some(format!("Moved {} items to {}", moved.len(), label)),
+    }
+}
@@ -0,0 +1,470 @@
+//! VirtualList Primitive
+//!
+//! Renders only the items of a long list that are in view, plus a few on
+//! either side, inside a scrolling viewport. Items have a fixed size or are
+//! measured once rendered, starting from an estimate.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     VirtualList { count: rows.len(), estimate_size: 36.0,
+//!         VirtualListViewport { style: "height: 400px",
+//!             render_item: move |index: usize| rsx! {
+//!                 VirtualListItem { index, "{rows[index]}" }
+//!             },
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The items share a single Tab stop. The arrow keys, Page Up, Page Down,
+//! Home and End move focus between them, rendering the target item first,
+//! and the focused item stays rendered while it is scrolled out of view.
+//! Other primitives such as a combobox can render their items through
+//! `render_item` and bring the highlighted one into view with
+//! [`VirtualListHandle::scroll_to_index`].
+
+use super::context::create_context::use_synced_signal;
+use super::hooks::use_size::use_size;
+use super::roving_focus::Orientation;
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// 每個項目的起點，固定大小時直接計算
+#[derive(Debug, Clone, PartialEq)]
+struct Layout {
+    fixed: Option<f64>,
+    starts: Vec<f64>,
+    total: f64,
+}
+
+impl Layout {
+    fn new(
+        count: usize,
+        fixed: Option<f64>,
+        estimate: f64,
+        measured: &HashMap<usize, f64>,
+    ) -> Self {
+        if let Some(size) = fixed {
+            return Layout {
+                fixed,
+                starts: Vec::new(),
+                total: size * count as f64,
+            };
+        }
+        let mut starts = Vec::with_capacity(count);
+        let mut total = 0.0;
+        for index in 0..count {
+            starts.push(total);
+            total += measured.get(&index).copied().unwrap_or(estimate);
+        }
+        Layout {
+            fixed,
+            starts,
+            total,
+        }
+    }
+
+    fn start(&self, index: usize) -> f64 {
+        match self.fixed {
+            Some(size) => size * index as f64,
+            None => self.starts.get(index).copied().unwrap_or(self.total),
+        }
+    }
+
+    // 位在 offset 的項目
+    fn index_at(&self, offset: f64, count: usize) -> usize {
+        let index = match self.fixed {
+            Some(size) if size > 0.0 => (offset / size).floor().max(0.0) as usize,
+            Some(_) => 0,
+            None => self
+                .starts
+                .partition_point(|start| *start <= offset)
+                .saturating_sub(1),
+        };
+        index.min(count.saturating_sub(1))
+    }
+}
+
+/// Scrolls a [`VirtualList`] from outside or inside it.
+///
+/// Create one with [`use_virtual_list_handle`] and pass it to the list's
+/// `handle`, or get the enclosing list's with [`use_virtual_list`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct VirtualListHandle {
+    scroll_target: Signal<Option<usize>>,
+}
+
+impl VirtualListHandle {
+    /// Render the item at `index` and scroll it into view.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        self.scroll_target.set(Some(index));
+    }
+}
+
+/// A handle to pass to a [`VirtualList`], to scroll it from its parent.
+pub fn use_virtual_list_handle() -> VirtualListHandle {
+    VirtualListHandle {
+        scroll_target: use_signal(|| None),
+    }
+}
+
+/// The handle of the enclosing [`VirtualList`].
+pub fn use_virtual_list() -> VirtualListHandle {
+    let ctx = use_context::<VirtualListContext>();
+    VirtualListHandle {
+        scroll_target: ctx.scroll_target,
+    }
+}
+
+#[derive(Clone, Copy)]
+struct VirtualListContext {
+    count: Signal<usize>,
+    orientation: Signal<Orientation>,
+    overscan: Signal<usize>,
+    layout: Memo<Layout>,
+    measured: Signal<HashMap<usize, f64>>,
+    scroll_offset: Signal<f64>,
+    viewport_size: Signal<f64>,
+    // 擁有 Tab 停駐點的項目，捲出畫面時仍會渲染
+    active: Signal<usize>,
+    focus_pending: Signal<bool>,
+    scroll_target: Signal<Option<usize>>,
+}
+
+impl VirtualListContext {
+    /// The indices to render, in order.
+    fn rendered(&self) -> Vec<usize> {
+        let count = (self.count)();
+        if count == 0 {
+            return Vec::new();
+        }
+        let layout = self.layout.read();
+        let offset = (self.scroll_offset)();
+        let overscan = (self.overscan)();
+        let first = layout.index_at(offset, count).saturating_sub(overscan);
+        let last =
+            (layout.index_at(offset + (self.viewport_size)(), count) + overscan).min(count - 1);
+
+        let mut indices: Vec<usize> = (first..=last).collect();
+        let extra = [Some((self.active)()), (self.scroll_target)()];
+        for index in extra.into_iter().flatten() {
+            if index < count && !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+        indices.sort_unstable();
+        indices
+    }
+
+    fn focus(&mut self, index: usize) {
+        let count = *self.count.peek();
+        if count == 0 {
+            return;
+        }
+        self.active.set(index.min(count - 1));
+        self.focus_pending.set(true);
+    }
+}
+
+/// The root of a virtual list, owning its layout.
+///
+/// Set `item_size` when every item has the same size, in pixels along the
+/// list's orientation; otherwise items are measured once rendered and
+/// `estimate_size` is used for the others. `overscan` items are rendered on
+/// either side of the visible ones.
+#[component]
+pub fn VirtualList(
+    count: usize,
+    item_size: Option<f64>,
+    #[props(default = 32.0)] estimate_size: f64,
+    #[props(default = 4)] overscan: usize,
+    #[props(default = Orientation::Vertical)] orientation: Orientation,
+    handle: Option<VirtualListHandle>,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let count_signal = use_synced_signal(count);
+    let orientation_signal = use_synced_signal(orientation);
+    let overscan = use_synced_signal(overscan);
+    let sizing = use_synced_signal((item_size, estimate_size));
+    let measured = use_signal(HashMap::new);
+    let layout = use_memo(move || {
+        let (item_size, estimate_size) = sizing();
+        Layout::new(count_signal(), item_size, estimate_size, &measured.read())
+    });
+    let own_target = use_signal(|| None);
+    let scroll_target = handle.map_or(own_target, |handle| handle.scroll_target);
+    let mut ctx = use_context_provider(|| VirtualListContext {
+        count: count_signal,
+        orientation: orientation_signal,
+        overscan,
+        layout,
+        measured,
+        scroll_offset: Signal::new(0.0),
+        viewport_size: Signal::new(0.0),
+        active: Signal::new(0),
+        focus_pending: Signal::new(false),
+        scroll_target,
+    });
+
+    // 項目數量減少時，移除多餘的量測結果並保持停駐點有效
+    use_effect(move || {
+        let count = count_signal();
+        if ctx.measured.peek().keys().any(|index| *index >= count) {
+            ctx.measured.write().retain(|index, _| *index < count);
+        }
+        if count > 0 && *ctx.active.peek() >= count {
+            ctx.active.set(count - 1);
+        }
+    });
+
+    let own_attributes = vec![
+        Attribute::new("data-orientation", orientation.as_str(), None, false),
+        Attribute::new("aria-orientation", orientation.as_str(), None, false),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
+
+/// The scrolling element, rendering the items in view with `render_item`.
+///
+/// Give it a fixed height, or width for a horizontal list. `render_item`
+/// is called with an item's index and should return a
+/// [`VirtualListItem`] with that index.
+#[component]
+pub fn VirtualListViewport(
+    render_item: Callback<usize, Element>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+) -> Element {
+    let mut ctx = use_context::<VirtualListContext>();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let size = use_size(node);
+    let vertical = (ctx.orientation)() == Orientation::Vertical;
+
+    use_effect(move || {
+        let vertical = *ctx.orientation.peek() == Orientation::Vertical;
+        if let Some(size) = size() {
+            ctx.viewport_size
+                .set(if vertical { size.height } else { size.width });
+        }
+    });
+
+    let layout = ctx.layout.read().clone();
+    let total = layout.total;
+    let rendered = ctx.rendered();
+    let spacer = if vertical {
+        format!("position: relative; width: 100%; height: {total}px;")
+    } else {
+        format!("position: relative; height: 100%; width: {total}px;")
+    };
+
+    let own_attributes = vec![
+        Attribute::new(
+            "style",
+            if vertical {
+                "overflow-y: auto;"
+            } else {
+                "overflow-x: auto;"
+            },
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            let data = event.data();
+            node.set(Some(data.clone()));
+            // 沒有 ResizeObserver 時只量測一次
+            spawn(async move {
+                if let Ok(rect) = data.get_client_rect().await {
+                    let vertical = *ctx.orientation.peek() == Orientation::Vertical;
+                    let size = if vertical {
+                        rect.size.height
+                    } else {
+                        rect.size.width
+                    };
+                    if *ctx.viewport_size.peek() == 0.0 {
+                        ctx.viewport_size.set(size);
+                    }
+                }
+            });
+        }),
+        dioxus_elements::events::onscroll(move |_: ScrollEvent| {
+            let Some(node) = node.peek().clone() else {
+                return;
+            };
+            spawn(async move {
+                if let Ok(offset) = node.get_scroll_offset().await {
+                    let vertical = *ctx.orientation.peek() == Orientation::Vertical;
+                    ctx.scroll_offset
+                        .set(if vertical { offset.y } else { offset.x });
+                }
+            });
+        }),
+        dioxus_elements::events::onkeydown(move |event: KeyboardEvent| {
+            let count = *ctx.count.peek();
+            if count == 0 {
+                return;
+            }
+            let vertical = *ctx.orientation.peek() == Orientation::Vertical;
+            let (previous_key, next_key) = if vertical {
+                (Key::ArrowUp, Key::ArrowDown)
+            } else {
+                (Key::ArrowLeft, Key::ArrowRight)
+            };
+            let active = *ctx.active.peek();
+            // 一頁的項目數，至少一個
+            let page = {
+                let layout = ctx.layout.peek();
+                let start = layout.start(active);
+                let end = layout.index_at(start + *ctx.viewport_size.peek(), count);
+                end.saturating_sub(active).max(1)
+            };
+            let key = event.key();
+            let target = if key == next_key {
+                active + 1
+            } else if key == previous_key {
+                active.saturating_sub(1)
+            } else if key == Key::PageDown {
+                active + page
+            } else if key == Key::PageUp {
+                active.saturating_sub(page)
+            } else if key == Key::Home {
+                0
+            } else if key == Key::End {
+                count - 1
+            } else {
+                return;
+            };
+            event.prevent_default();
+            ctx.focus(target);
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    rsx! {
+        div {
+            ..attributes,
+            div { style: spacer,
+                for index in rendered {
+                    VirtualListSlot { key: "{index}", index, render_item }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn VirtualListSlot(index: usize, render_item: Callback<usize, Element>) -> Element {
+    render_item.call(index)
+}
+
+/// An item of the list, positioned at its offset in the viewport.
+///
+/// `aria-setsize` and `aria-posinset` describe its place in the whole list,
+/// since most items are not rendered; give it a role that supports them,
+/// such as `option` or `listitem`.
+#[component]
+pub fn VirtualListItem(
+    index: usize,
+    /// Render your own element instead of a `div`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let mut ctx = use_context::<VirtualListContext>();
+    let mut node = use_signal(|| None::<Rc<MountedData>>);
+    let size = use_size(node);
+    let index_signal = use_synced_signal(index);
+
+    // 動態大小時記錄量測結果
+    use_effect(move || {
+        let Some(size) = size() else {
+            return;
+        };
+        if ctx.layout.peek().fixed.is_some() {
+            return;
+        }
+        let vertical = *ctx.orientation.peek() == Orientation::Vertical;
+        let size = if vertical { size.height } else { size.width };
+        let index = index_signal();
+        if ctx.measured.peek().get(&index) != Some(&size) {
+            ctx.measured.write().insert(index, size);
+        }
+    });
+
+    // 以鍵盤移到這個項目，或要求捲動到這個項目時
+    use_effect(move || {
+        let index = index_signal();
+        let Some(node) = node() else {
+            return;
+        };
+        if (ctx.active)() == index && (ctx.focus_pending)() {
+            ctx.focus_pending.set(false);
+            spawn(async move {
+                let _ = node.set_focus(true).await;
+            });
+        } else if (ctx.scroll_target)() == Some(index) {
+            ctx.scroll_target.set(None);
+            spawn(async move {
+                let _ = node.scroll_to(ScrollBehavior::Instant).await;
+            });
+        }
+    });
+
+    let vertical = (ctx.orientation)() == Orientation::Vertical;
+    let start = ctx.layout.read().start(index);
+    let fixed = ctx.layout.read().fixed;
+    let position = match (vertical, fixed) {
+        (true, Some(size)) => format!("top: {start}px; left: 0; width: 100%; height: {size}px;"),
+        (true, None) => format!("top: {start}px; left: 0; width: 100%;"),
+        (false, Some(size)) => format!("left: {start}px; top: 0; height: 100%; width: {size}px;"),
+        (false, None) => format!("left: {start}px; top: 0; height: 100%;"),
+    };
+    let active = (ctx.active)() == index;
+
+    let own_attributes = vec![
+        Attribute::new("tabindex", if active { "0" } else { "-1" }, None, false),
+        Attribute::new("aria-setsize", (ctx.count)().to_string(), None, false),
+        Attribute::new("aria-posinset", (index + 1).to_string(), None, false),
+        Attribute::new("data-index", index.to_string(), None, false),
+        Attribute::new(
+            "style",
+            format!("position: absolute; {position}"),
+            None,
+            false,
+        ),
+        dioxus_elements::events::onmounted(move |event: MountedEvent| {
+            node.set(Some(event.data()));
+        }),
+        dioxus_elements::events::onfocus(move |_: FocusEvent| {
+            if *ctx.active.peek() != index {
+                ctx.active.set(index);
+            }
+        }),
+    ];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        div {
+            ..attributes,
+            {children}
+        }
+    }
+}
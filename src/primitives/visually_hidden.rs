@@ -0,0 +1,35 @@
+//! VisuallyHidden Primitive
+//!
+//! Hides content from the screen while keeping it available to assistive
+//! technology, e.g. a label for an icon-only button.
+
+use super::slot::{AsChild, merge_attributes};
+use dioxus::prelude::*;
+
+pub(crate) const VISUALLY_HIDDEN_STYLE: &str = "position: absolute; border: 0; width: 1px; \
+    height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); \
+    white-space: nowrap; word-wrap: normal;";
+
+/// Renders `children` in a `span` that is invisible but still read by
+/// screen readers.
+#[component]
+pub fn VisuallyHidden(
+    /// Render your own element instead of a `span`; see [`AsChild`].
+    as_child: Option<AsChild>,
+    #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
+    children: Element,
+) -> Element {
+    let own_attributes = vec![Attribute::new("style", VISUALLY_HIDDEN_STYLE, None, false)];
+    let attributes = merge_attributes(own_attributes, attributes);
+
+    if let Some(as_child) = as_child {
+        return as_child.call(attributes);
+    }
+
+    rsx! {
+        span {
+            ..attributes,
+            {children}
+        }
+    }
+}